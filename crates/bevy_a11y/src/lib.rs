@@ -19,7 +19,7 @@ use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
     prelude::{Component, Entity, Event},
     schedule::SystemSet,
-    system::Resource,
+    system::{Commands, Resource},
 };
 
 /// Wrapper struct for [`accesskit::ActionRequest`]. Required to allow it to be used as an `Event`.
@@ -91,6 +91,38 @@ impl From<NodeBuilder> for AccessibilityNode {
     }
 }
 
+/// A callback invoked with the actions requested on an entity by assistive technologies.
+pub type AccessibilityActionHandler =
+    Box<dyn FnMut(Entity, &accesskit::ActionRequest, &mut Commands) + Send + Sync>;
+
+/// Component handling the actions (click, expand, scroll, ...) that assistive technologies
+/// request on its entity.
+///
+/// Requests targeting the entity's [`AccessibilityNode`] are dispatched to this handler by the
+/// windowing backend, so they don't have to be read from the [`ActionRequest`] events and matched
+/// to their entity manually. The events are still sent.
+#[derive(Component)]
+pub struct OnAccessibilityAction(AccessibilityActionHandler);
+
+impl OnAccessibilityAction {
+    /// Creates a component calling `handler` for each action requested on its entity.
+    pub fn new(
+        handler: impl FnMut(Entity, &accesskit::ActionRequest, &mut Commands) + Send + Sync + 'static,
+    ) -> Self {
+        Self(Box::new(handler))
+    }
+
+    /// Calls the handler with an action requested on `entity`.
+    pub fn handle(
+        &mut self,
+        entity: Entity,
+        request: &accesskit::ActionRequest,
+        commands: &mut Commands,
+    ) {
+        (self.0)(entity, request, commands);
+    }
+}
+
 /// Resource representing which entity has keyboard focus, if any.
 #[derive(Resource, Default, Deref, DerefMut)]
 pub struct Focus(pub Option<Entity>);
@@ -11,7 +11,10 @@ use bevy_ecs::{
     system::SystemId,
 };
 #[cfg(feature = "bevy_state")]
-use bevy_state::{prelude::*, state::FreelyMutableState};
+use bevy_state::{
+    prelude::*,
+    state::{FreelyMutableState, MatchingStateSchedule},
+};
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
 use bevy_utils::{tracing::debug, HashMap};
@@ -285,8 +288,32 @@ impl App {
     }
 
     #[cfg(feature = "bevy_state")]
-    /// Inserts a specific [`State`] to the current [`App`] and overrides any [`State`] previously
-    /// added of the same type.
+    /// Initializes a [`State`] with the starting value returned by `initial_state`.
+    ///
+    /// This is useful to pick the starting value when the app is built, e.g. from a resource or a
+    /// command line flag, to skip the main menu in development builds.
+    ///
+    /// This method is idempotent: it has no effect, and `initial_state` isn't called, when called
+    /// again using the same generic type.
+    ///
+    /// See [`App::init_state`] for the resources and schedules added.
+    pub fn init_state_with<S: FreelyMutableState>(
+        &mut self,
+        initial_state: impl FnOnce(&mut World) -> S,
+    ) -> &mut Self {
+        self.main_mut().init_state_with::<S>(initial_state);
+        self
+    }
+
+    #[cfg(feature = "bevy_state")]
+    /// Inserts a specific [`State`] to the current [`App`].
+    ///
+    /// Unlike [`App::init_state`], which starts from the [`Default`] value, the app starts in
+    /// `state` and runs its [`OnEnter`] schedule at startup. This is useful when the starting
+    /// state depends on command line flags or saved data.
+    ///
+    /// This method is idempotent: it has no effect when called again using the same generic type.
+    /// Use [`NextState`](bevy_state::state::NextState) to change the state afterwards.
     ///
     /// Adds [`State<S>`] and [`NextState<S>`] resources, and enables use of the [`OnEnter`], [`OnTransition`] and [`OnExit`] schedules.
     /// These schedules are triggered before [`Update`](crate::Update) and at startup.
@@ -304,6 +331,13 @@ impl App {
     #[cfg(feature = "bevy_state")]
     /// Sets up a type implementing [`ComputedStates`].
     ///
+    /// This adds the [`StateTransitionEvent<S>`] event and the systems computing [`State<S>`]
+    /// whenever one of its sources changes, ordered after the states it depends on. The
+    /// [`OnEnter`], [`OnTransition`] and [`OnExit`] schedules of `S` can then be used as for any
+    /// other state.
+    ///
+    /// The source states must be added to the app as well, e.g. with [`App::init_state`].
+    ///
     /// This method is idempotent: it has no effect when called again using the same generic type.
     pub fn add_computed_state<S: ComputedStates>(&mut self) -> &mut Self {
         self.main_mut().add_computed_state::<S>();
@@ -313,12 +347,77 @@ impl App {
     #[cfg(feature = "bevy_state")]
     /// Sets up a type implementing [`SubStates`].
     ///
+    /// This adds the [`NextState<S>`] resource, the [`StateTransitionEvent<S>`] event and the
+    /// systems creating or removing [`State<S>`] whenever one of its sources changes, ordered after
+    /// the states it depends on. While it exists, the state can be changed through
+    /// [`NextState<S>`] like the states added with [`App::init_state`].
+    ///
+    /// The source states must be added to the app as well, e.g. with [`App::init_state`].
+    ///
     /// This method is idempotent: it has no effect when called again using the same generic type.
     pub fn add_sub_state<S: SubStates>(&mut self) -> &mut Self {
         self.main_mut().add_sub_state::<S>();
         self
     }
 
+    #[cfg(feature = "bevy_state")]
+    /// Makes the transitions of the `Before` state apply before the ones of the `After` state
+    /// when both change in the same frame, including their [`OnExit`], [`OnTransition`] and
+    /// [`OnEnter`] schedules.
+    ///
+    /// See [`order_state_transitions`](bevy_state::state::order_state_transitions) for details.
+    pub fn order_state_transitions<Before: States, After: States>(&mut self) -> &mut Self {
+        self.main_mut().order_state_transitions::<Before, After>();
+        self
+    }
+
+    #[cfg(feature = "bevy_state")]
+    /// Adds systems to the given schedule that only run while the state machine is in `state`.
+    ///
+    /// Unlike adding each system with `.run_if(in_state(state))`, the condition is evaluated once
+    /// per schedule run for all the systems added this way to the same schedule and state.
+    ///
+    /// See [`InState`](bevy_state::condition::InState) for details.
+    pub fn add_systems_in_state<S: States, M>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        state: S,
+        systems: impl IntoSystemConfigs<M>,
+    ) -> &mut Self {
+        self.main_mut()
+            .add_systems_in_state(schedule, state, systems);
+        self
+    }
+
+    #[cfg(feature = "bevy_state")]
+    /// Adds systems to an [`OnEnterMatching`](bevy_state::state::OnEnterMatching) or
+    /// [`OnExitMatching`](bevy_state::state::OnExitMatching) schedule, registering it so the
+    /// transitions of its state run it.
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_state::prelude::*;
+    /// # use bevy_state::state::OnEnterMatching;
+    /// #[derive(States, Clone, Copy, Default, Eq, PartialEq, Hash, Debug)]
+    /// struct Level(u32);
+    ///
+    /// # let mut app = App::new();
+    /// app.init_state::<Level>().add_matching_state_systems(
+    ///     OnEnterMatching::new("boss_levels", |level: &Level| level.0 >= 10),
+    ///     spawn_boss,
+    /// );
+    /// # fn spawn_boss() {}
+    /// ```
+    pub fn add_matching_state_systems<M>(
+        &mut self,
+        schedule: impl MatchingStateSchedule,
+        systems: impl IntoSystemConfigs<M>,
+    ) -> &mut Self {
+        self.main_mut()
+            .add_matching_state_systems(schedule, systems);
+        self
+    }
+
     /// Adds one or more systems to the given schedule in this app's [`Schedules`].
     ///
     /// # Examples
@@ -1222,6 +1321,149 @@ mod tests {
             .run();
     }
 
+    #[cfg(feature = "bevy_state")]
+    #[test]
+    fn init_state_with() {
+        use bevy_ecs::system::Resource;
+        use bevy_state::prelude::{State, States};
+
+        #[derive(States, Default, Clone, PartialEq, Eq, Hash, Debug)]
+        enum GameState {
+            #[default]
+            Menu,
+            InGame,
+        }
+
+        #[derive(Resource)]
+        struct SkipMenu;
+
+        let mut app = App::new();
+        app.insert_resource(SkipMenu)
+            .init_state_with(|world| match world.contains_resource::<SkipMenu>() {
+                true => GameState::InGame,
+                false => GameState::Menu,
+            })
+            // The state is already initialized, so this has no effect.
+            .init_state_with(|_| GameState::Menu);
+
+        assert_eq!(
+            app.world().resource::<State<GameState>>().get(),
+            &GameState::InGame
+        );
+    }
+
+    #[cfg(feature = "bevy_state")]
+    #[test]
+    fn insert_state_enters_the_given_state() {
+        use bevy_ecs::{
+            event::Events,
+            system::{ResMut, Resource},
+        };
+        use bevy_state::{
+            prelude::{OnEnter, State, States},
+            state::StateTransitionEvent,
+        };
+
+        #[derive(States, Default, Clone, PartialEq, Eq, Hash, Debug)]
+        enum GameState {
+            #[default]
+            Menu,
+            InGame,
+            Editor,
+        }
+
+        #[derive(Resource, Default)]
+        struct Entered(Vec<GameState>);
+
+        let mut app = App::new();
+        app.init_resource::<Entered>()
+            .insert_state(GameState::InGame)
+            // Inserting again has no effect.
+            .insert_state(GameState::Editor);
+        for state in [GameState::Menu, GameState::InGame, GameState::Editor] {
+            app.add_systems(
+                OnEnter(state.clone()),
+                move |mut entered: ResMut<Entered>| {
+                    entered.0.push(state.clone());
+                },
+            );
+        }
+        app.update();
+
+        assert_eq!(
+            app.world().resource::<State<GameState>>().get(),
+            &GameState::InGame
+        );
+        assert_eq!(app.world().resource::<Entered>().0, [GameState::InGame]);
+        let transitions = app
+            .world_mut()
+            .resource_mut::<Events<StateTransitionEvent<GameState>>>()
+            .drain()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            transitions,
+            [StateTransitionEvent {
+                before: None,
+                after: Some(GameState::InGame),
+            }]
+        );
+    }
+
+    #[cfg(feature = "bevy_state")]
+    #[test]
+    fn add_computed_and_sub_states() {
+        use bevy_state::prelude::{ComputedStates, NextState, State, StateSet, States, SubStates};
+
+        #[derive(States, Default, Clone, PartialEq, Eq, Hash, Debug)]
+        enum GameState {
+            #[default]
+            Menu,
+            InGame,
+        }
+
+        #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+        struct InMenu;
+
+        impl ComputedStates for InMenu {
+            type SourceStates = GameState;
+
+            fn compute(sources: GameState) -> Option<Self> {
+                (sources == GameState::Menu).then_some(InMenu)
+            }
+        }
+
+        #[derive(SubStates, Default, Clone, PartialEq, Eq, Hash, Debug)]
+        #[source(GameState = GameState::InGame)]
+        enum Phase {
+            #[default]
+            Playing,
+            Paused,
+        }
+
+        let mut app = App::new();
+        app.init_state::<GameState>()
+            .add_computed_state::<InMenu>()
+            .add_sub_state::<Phase>();
+
+        app.update();
+        assert!(app.world().contains_resource::<State<InMenu>>());
+        assert!(!app.world().contains_resource::<State<Phase>>());
+
+        app.world_mut()
+            .insert_resource(NextState::Pending(GameState::InGame));
+        app.update();
+        assert!(!app.world().contains_resource::<State<InMenu>>());
+        assert_eq!(
+            app.world().resource::<State<Phase>>().get(),
+            &Phase::Playing
+        );
+
+        app.world_mut()
+            .insert_resource(NextState::Pending(Phase::Paused));
+        app.update();
+        assert_eq!(app.world().resource::<State<Phase>>().get(), &Phase::Paused);
+    }
+
     #[test]
     fn app_exit_size() {
         // There wont be many of them so the size isn't a issue but
@@ -7,8 +7,12 @@ use bevy_ecs::{
 };
 #[cfg(feature = "bevy_state")]
 use bevy_state::{
+    condition::{configure_in_state_set, InState},
     prelude::*,
-    state::{setup_state_transitions_in_world, FreelyMutableState},
+    state::{
+        setup_state_transitions_in_world, FreelyMutableState, MatchingStateSchedule,
+        NextStateConflict, NextStateConflictPolicy,
+    },
 };
 
 #[cfg(feature = "trace")]
@@ -305,9 +309,27 @@ impl SubApp {
             setup_state_transitions_in_world(&mut self.world, Some(Startup.intern()));
             self.init_resource::<State<S>>()
                 .init_resource::<NextState<S>>()
-                .add_event::<StateTransitionEvent<S>>();
+                .init_resource::<NextStateConflictPolicy<S>>()
+                .add_event::<StateTransitionEvent<S>>()
+                .add_event::<NextStateConflict<S>>();
             let schedule = self.get_schedule_mut(StateTransition).unwrap();
             S::register_state(schedule);
+            let state = self.world.resource::<State<S>>().get().clone();
+            self.send_initial_state_transition(state);
+        }
+
+        self
+    }
+
+    #[cfg(feature = "bevy_state")]
+    /// See [`App::init_state_with`].
+    pub fn init_state_with<S: FreelyMutableState>(
+        &mut self,
+        initial_state: impl FnOnce(&mut World) -> S,
+    ) -> &mut Self {
+        if !self.world.contains_resource::<State<S>>() {
+            let state = initial_state(&mut self.world);
+            self.insert_state(state);
         }
 
         self
@@ -318,17 +340,67 @@ impl SubApp {
     pub fn insert_state<S: FreelyMutableState>(&mut self, state: S) -> &mut Self {
         if !self.world.contains_resource::<State<S>>() {
             setup_state_transitions_in_world(&mut self.world, Some(Startup.intern()));
-            self.insert_resource::<State<S>>(State::new(state))
+            self.insert_resource::<State<S>>(State::new(state.clone()))
                 .init_resource::<NextState<S>>()
-                .add_event::<StateTransitionEvent<S>>();
+                .init_resource::<NextStateConflictPolicy<S>>()
+                .add_event::<StateTransitionEvent<S>>()
+                .add_event::<NextStateConflict<S>>();
 
             let schedule = self.get_schedule_mut(StateTransition).unwrap();
             S::register_state(schedule);
+            self.send_initial_state_transition(state);
         }
 
         self
     }
 
+    #[cfg(feature = "bevy_state")]
+    /// See [`App::order_state_transitions`].
+    pub fn order_state_transitions<Before: States, After: States>(&mut self) -> &mut Self {
+        setup_state_transitions_in_world(&mut self.world, Some(Startup.intern()));
+        let schedule = self.get_schedule_mut(StateTransition).unwrap();
+        bevy_state::state::order_state_transitions::<Before, After>(schedule);
+
+        self
+    }
+
+    #[cfg(feature = "bevy_state")]
+    /// See [`App::add_matching_state_systems`].
+    pub fn add_matching_state_systems<M>(
+        &mut self,
+        schedule: impl MatchingStateSchedule,
+        systems: impl IntoSystemConfigs<M>,
+    ) -> &mut Self {
+        schedule.register(&mut self.world);
+        self.add_systems(schedule, systems)
+    }
+
+    #[cfg(feature = "bevy_state")]
+    /// See [`App::add_systems_in_state`].
+    pub fn add_systems_in_state<S: States, M>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        state: S,
+        systems: impl IntoSystemConfigs<M>,
+    ) -> &mut Self {
+        let mut schedules = self.world.resource_mut::<Schedules>();
+        let schedule = schedules.entry(schedule);
+        configure_in_state_set(schedule, state.clone());
+        schedule.add_systems(systems.in_set(InState(state)));
+
+        self
+    }
+
+    #[cfg(feature = "bevy_state")]
+    /// Sends the transition entering the initial value of a state, so that the computed states and
+    /// sub-states depending on it are derived on the first run of [`StateTransition`].
+    fn send_initial_state_transition<S: States>(&mut self, state: S) {
+        self.world.send_event(StateTransitionEvent {
+            before: None,
+            after: Some(state),
+        });
+    }
+
     #[cfg(feature = "bevy_state")]
     /// See [`App::add_computed_state`].
     pub fn add_computed_state<S: ComputedStates>(&mut self) -> &mut Self {
@@ -354,7 +426,9 @@ impl SubApp {
         {
             setup_state_transitions_in_world(&mut self.world, Some(Startup.intern()));
             self.init_resource::<NextState<S>>();
+            self.init_resource::<NextStateConflictPolicy<S>>();
             self.add_event::<StateTransitionEvent<S>>();
+            self.add_event::<NextStateConflict<S>>();
             let schedule = self.get_schedule_mut(StateTransition).unwrap();
             S::register_sub_state_systems(schedule);
         }
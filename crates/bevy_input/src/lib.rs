@@ -18,11 +18,14 @@ pub mod common_conditions;
 pub mod gamepad;
 pub mod keyboard;
 pub mod mouse;
+mod timestamped;
 pub mod touch;
+pub mod touch_gesture;
 pub mod touchpad;
 
 pub use axis::*;
 pub use button_input::*;
+pub use timestamped::*;
 
 /// Most commonly used re-exported types.
 pub mod prelude {
@@ -69,10 +72,12 @@ impl Plugin for InputPlugin {
         app
             // keyboard
             .add_event::<KeyboardInput>()
+            .add_event::<Timestamped<KeyboardInput>>()
             .init_resource::<ButtonInput<KeyCode>>()
             .add_systems(PreUpdate, keyboard_input_system.in_set(InputSystem))
             // mouse
             .add_event::<MouseButtonInput>()
+            .add_event::<Timestamped<MouseButtonInput>>()
             .add_event::<MouseMotion>()
             .add_event::<MouseWheel>()
             .init_resource::<ButtonInput<MouseButton>>()
@@ -107,6 +112,7 @@ impl Plugin for InputPlugin {
             )
             // touch
             .add_event::<TouchInput>()
+            .add_event::<Timestamped<TouchInput>>()
             .init_resource::<Touches>()
             .add_systems(PreUpdate, touch_screen_input_system.in_set(InputSystem));
 
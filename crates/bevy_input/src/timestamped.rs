@@ -0,0 +1,87 @@
+//! The timestamps of the input events.
+
+use bevy_ecs::event::Event;
+use bevy_utils::Instant;
+
+/// An input event with the time the windowing backend received it, to measure input latency or
+/// smooth input over time.
+///
+/// Backends send it right after the event itself, e.g. a `Timestamped<KeyboardInput>` after each
+/// [`KeyboardInput`](crate::keyboard::KeyboardInput) they translate. Events sent by other means,
+/// e.g. in tests, have no timestamp. Use [`with_timestamps`] to read the events with their
+/// timestamp.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct Timestamped<E> {
+    /// The input event.
+    pub event: E,
+    /// When the windowing backend received the event.
+    pub timestamp: Instant,
+}
+
+/// Pairs each of `events` with the timestamp of its [`Timestamped`] counterpart in `timestamped`,
+/// or `None` for the events sent without one.
+///
+/// Both iterators must read the events of the same frames, e.g. from an `EventReader<E>` and an
+/// `EventReader<Timestamped<E>>` in the same system.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_input::{keyboard::KeyboardInput, with_timestamps, Timestamped};
+/// # use bevy_utils::Instant;
+/// fn key_latency(
+///     mut keys: EventReader<KeyboardInput>,
+///     mut timestamped_keys: EventReader<Timestamped<KeyboardInput>>,
+/// ) {
+///     for (key, timestamp) in with_timestamps(keys.read(), timestamped_keys.read()) {
+///         if let Some(timestamp) = timestamp {
+///             println!("{:?} was handled after {:?}", key.key_code, timestamp.elapsed());
+///         }
+///     }
+/// }
+/// ```
+pub fn with_timestamps<'a, E: PartialEq + 'a>(
+    events: impl IntoIterator<Item = &'a E>,
+    timestamped: impl IntoIterator<Item = &'a Timestamped<E>>,
+) -> impl Iterator<Item = (&'a E, Option<Instant>)> {
+    let mut timestamped = timestamped.into_iter().peekable();
+    events.into_iter().map(move |event| {
+        let timestamp = timestamped
+            .next_if(|timestamped| timestamped.event == *event)
+            .map(|timestamped| timestamped.timestamp);
+        (event, timestamp)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_utils::Duration;
+
+    use super::*;
+
+    #[test]
+    fn events_are_paired_with_their_timestamp() {
+        let start = Instant::now();
+        let timestamped = [
+            Timestamped {
+                event: 1,
+                timestamp: start,
+            },
+            Timestamped {
+                event: 3,
+                timestamp: start + Duration::from_millis(1),
+            },
+        ];
+
+        let timestamps = with_timestamps(&[1, 2, 3], &timestamped)
+            .map(|(event, timestamp)| (*event, timestamp))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            timestamps,
+            [
+                (1, Some(start)),
+                (2, None),
+                (3, Some(start + Duration::from_millis(1)))
+            ]
+        );
+    }
+}
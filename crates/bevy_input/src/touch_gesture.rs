@@ -0,0 +1,561 @@
+//! Recognition of touch gestures from the raw [`TouchInput`] events.
+
+use std::f32::consts::{PI, TAU};
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::prelude::*;
+use bevy_math::Vec2;
+use bevy_reflect::Reflect;
+use bevy_utils::{Duration, HashMap, Instant};
+
+#[cfg(feature = "serialize")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+use crate::{
+    touch::{TouchInput, TouchPhase},
+    with_timestamps, InputSystem, Timestamped,
+};
+
+/// Opt-in plugin recognizing taps, double taps, long presses, swipes, pinches and rotations from
+/// the [`TouchInput`] events, so touchscreen apps don't each reimplement the gesture math.
+///
+/// The gestures are recognized separately for each window, and sent as the [`TapGesture`],
+/// [`DoubleTapGesture`], [`LongPressGesture`], [`SwipeGesture`], [`PinchGesture`] and
+/// [`RotateGesture`] events during [`PreUpdate`], after the [`InputSystem`] set. The thresholds
+/// telling the gestures apart are set by the [`TouchGestureSettings`] resource.
+///
+/// Taps, long presses and swipes are only recognized for a single finger: once a second finger
+/// touches the window, the touches are only used for pinches and rotations until all fingers
+/// are lifted.
+#[derive(Default)]
+pub struct TouchGesturePlugin;
+
+impl Plugin for TouchGesturePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TouchGestureSettings>()
+            .add_event::<TapGesture>()
+            .add_event::<DoubleTapGesture>()
+            .add_event::<LongPressGesture>()
+            .add_event::<SwipeGesture>()
+            .add_event::<PinchGesture>()
+            .add_event::<RotateGesture>()
+            .add_systems(PreUpdate, touch_gesture_system.after(InputSystem))
+            .register_type::<TapGesture>()
+            .register_type::<DoubleTapGesture>()
+            .register_type::<LongPressGesture>()
+            .register_type::<SwipeGesture>()
+            .register_type::<PinchGesture>()
+            .register_type::<RotateGesture>();
+    }
+}
+
+/// The thresholds used by the [`TouchGesturePlugin`] to recognize the gestures.
+///
+/// Distances are in logical pixels, like the [`TouchInput`] positions.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct TouchGestureSettings {
+    /// The longest a finger can stay down for a tap.
+    pub tap_max_duration: Duration,
+    /// The farthest a finger can move during a tap or a long press.
+    pub tap_max_distance: f32,
+    /// The longest time between two taps making a double tap.
+    pub double_tap_interval: Duration,
+    /// The farthest apart two taps making a double tap can be.
+    pub double_tap_max_distance: f32,
+    /// How long a finger must stay down without moving for a long press.
+    pub long_press_duration: Duration,
+    /// The shortest distance a finger must move for a swipe.
+    pub swipe_min_distance: f32,
+    /// The longest a finger can stay down for a swipe.
+    pub swipe_max_duration: Duration,
+}
+
+impl Default for TouchGestureSettings {
+    fn default() -> Self {
+        Self {
+            tap_max_duration: Duration::from_millis(300),
+            tap_max_distance: 10.0,
+            double_tap_interval: Duration::from_millis(300),
+            double_tap_max_distance: 40.0,
+            long_press_duration: Duration::from_millis(500),
+            swipe_min_distance: 50.0,
+            swipe_max_duration: Duration::from_millis(500),
+        }
+    }
+}
+
+/// A finger quickly touched and left the window without moving.
+///
+/// The second tap of a double tap sends a [`DoubleTapGesture`] instead.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct TapGesture {
+    /// The window touched.
+    pub window: Entity,
+    /// Where the finger touched the window.
+    pub position: Vec2,
+}
+
+/// A finger tapped the window twice in a row, at about the same place.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct DoubleTapGesture {
+    /// The window touched.
+    pub window: Entity,
+    /// Where the finger touched the window the second time.
+    pub position: Vec2,
+}
+
+/// A finger stayed down on the window without moving.
+///
+/// The event is sent as soon as the finger has been down for long enough, lifting it afterwards
+/// doesn't send a [`TapGesture`].
+#[derive(Event, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct LongPressGesture {
+    /// The window touched.
+    pub window: Entity,
+    /// Where the finger touches the window.
+    pub position: Vec2,
+}
+
+/// A finger quickly moved across the window before being lifted.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct SwipeGesture {
+    /// The window touched.
+    pub window: Entity,
+    /// Where the finger touched the window.
+    pub start: Vec2,
+    /// Where the finger left the window.
+    pub end: Vec2,
+    /// The average velocity of the finger, in logical pixels per second.
+    pub velocity: Vec2,
+}
+
+/// Two fingers moved closer or farther apart.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct PinchGesture {
+    /// The window touched.
+    pub window: Entity,
+    /// The point halfway between the fingers.
+    pub focal_point: Vec2,
+    /// The ratio of the distance between the fingers to the distance at the previous event.
+    ///
+    /// Values above `1.0` indicate magnification (zooming in) and values below `1.0` indicate
+    /// shrinking (zooming out).
+    pub scale: f32,
+}
+
+/// Two fingers turned around each other.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct RotateGesture {
+    /// The window touched.
+    pub window: Entity,
+    /// The point halfway between the fingers.
+    pub focal_point: Vec2,
+    /// The angle the fingers turned by since the previous event, in radians.
+    ///
+    /// Positive values indicate rotation counterclockwise and negative values indicate rotation
+    /// clockwise.
+    pub delta: f32,
+}
+
+/// A finger followed by the [`touch_gesture_system`].
+#[derive(Debug, Clone, Copy)]
+struct TrackedTouch {
+    start_position: Vec2,
+    start_time: Instant,
+    position: Vec2,
+    long_pressed: bool,
+}
+
+/// The gestures in progress in a window.
+#[derive(Debug, Default)]
+struct WindowGestures {
+    touches: HashMap<u64, TrackedTouch>,
+    /// Whether more than one finger touched the window since all fingers were last lifted.
+    multi_touch: bool,
+    /// The distance and angle between the two fingers at the previous event.
+    two_fingers: Option<(f32, f32)>,
+    /// When and where the last tap happened, if it may start a double tap.
+    last_tap: Option<(Instant, Vec2)>,
+}
+
+impl WindowGestures {
+    /// Returns the focal point, distance and angle of the two fingers, if exactly two fingers
+    /// touch the window.
+    fn two_fingers(&self) -> Option<(Vec2, f32, f32)> {
+        if self.touches.len() != 2 {
+            return None;
+        }
+        // Order the fingers so the angle doesn't depend on the iteration order.
+        let mut touches = self.touches.iter().collect::<Vec<_>>();
+        touches.sort_unstable_by_key(|(id, _)| **id);
+        let (a, b) = (touches[0].1, touches[1].1);
+        // Window coordinates point down, flip them so angles grow counterclockwise.
+        let offset = (b.position - a.position) * Vec2::new(1.0, -1.0);
+        Some((
+            (a.position + b.position) / 2.0,
+            offset.length(),
+            offset.y.atan2(offset.x),
+        ))
+    }
+}
+
+/// Recognizes the gestures from the [`TouchInput`] events, see [`TouchGesturePlugin`].
+#[allow(clippy::too_many_arguments)]
+fn touch_gesture_system(
+    settings: Res<TouchGestureSettings>,
+    mut touch_inputs: EventReader<TouchInput>,
+    mut timestamped_touch_inputs: EventReader<Timestamped<TouchInput>>,
+    mut taps: EventWriter<TapGesture>,
+    mut double_taps: EventWriter<DoubleTapGesture>,
+    mut long_presses: EventWriter<LongPressGesture>,
+    mut swipes: EventWriter<SwipeGesture>,
+    mut pinches: EventWriter<PinchGesture>,
+    mut rotations: EventWriter<RotateGesture>,
+    mut windows: Local<HashMap<Entity, WindowGestures>>,
+) {
+    for (event, timestamp) in with_timestamps(touch_inputs.read(), timestamped_touch_inputs.read())
+    {
+        let time = timestamp.unwrap_or_else(Instant::now);
+        let gestures = windows.entry(event.window).or_default();
+        match event.phase {
+            TouchPhase::Started => {
+                gestures.touches.insert(
+                    event.id,
+                    TrackedTouch {
+                        start_position: event.position,
+                        start_time: time,
+                        position: event.position,
+                        long_pressed: false,
+                    },
+                );
+                if gestures.touches.len() > 1 {
+                    gestures.multi_touch = true;
+                }
+                gestures.two_fingers = gestures
+                    .two_fingers()
+                    .map(|(_, distance, angle)| (distance, angle));
+            }
+            TouchPhase::Moved => {
+                let Some(touch) = gestures.touches.get_mut(&event.id) else {
+                    continue;
+                };
+                touch.position = event.position;
+
+                let Some((focal_point, distance, angle)) = gestures.two_fingers() else {
+                    continue;
+                };
+                if let Some((previous_distance, previous_angle)) = gestures.two_fingers {
+                    if previous_distance > 0.0 && distance != previous_distance {
+                        pinches.send(PinchGesture {
+                            window: event.window,
+                            focal_point,
+                            scale: distance / previous_distance,
+                        });
+                    }
+                    // Wrap the delta so crossing the negative x axis doesn't jump by a full turn.
+                    let delta = (angle - previous_angle + PI).rem_euclid(TAU) - PI;
+                    if delta != 0.0 {
+                        rotations.send(RotateGesture {
+                            window: event.window,
+                            focal_point,
+                            delta,
+                        });
+                    }
+                }
+                gestures.two_fingers = Some((distance, angle));
+            }
+            TouchPhase::Ended | TouchPhase::Canceled => {
+                let Some(touch) = gestures.touches.remove(&event.id) else {
+                    continue;
+                };
+                gestures.two_fingers = gestures
+                    .two_fingers()
+                    .map(|(_, distance, angle)| (distance, angle));
+                let multi_touch = gestures.multi_touch;
+                if gestures.touches.is_empty() {
+                    gestures.multi_touch = false;
+                }
+                if event.phase == TouchPhase::Canceled || multi_touch || touch.long_pressed {
+                    continue;
+                }
+
+                let duration = time.saturating_duration_since(touch.start_time);
+                let distance = event.position.distance(touch.start_position);
+                if distance <= settings.tap_max_distance && duration <= settings.tap_max_duration {
+                    let double_tap = gestures.last_tap.take().is_some_and(|(tap_time, tap)| {
+                        time.saturating_duration_since(tap_time) <= settings.double_tap_interval
+                            && event.position.distance(tap) <= settings.double_tap_max_distance
+                    });
+                    if double_tap {
+                        double_taps.send(DoubleTapGesture {
+                            window: event.window,
+                            position: event.position,
+                        });
+                    } else {
+                        gestures.last_tap = Some((time, event.position));
+                        taps.send(TapGesture {
+                            window: event.window,
+                            position: event.position,
+                        });
+                    }
+                } else if distance >= settings.swipe_min_distance
+                    && duration <= settings.swipe_max_duration
+                {
+                    swipes.send(SwipeGesture {
+                        window: event.window,
+                        start: touch.start_position,
+                        end: event.position,
+                        velocity: (event.position - touch.start_position)
+                            / duration.as_secs_f32().max(f32::EPSILON),
+                    });
+                }
+            }
+        }
+    }
+
+    let now = Instant::now();
+    for (window, gestures) in windows.iter_mut() {
+        if !gestures.multi_touch {
+            for touch in gestures.touches.values_mut() {
+                if !touch.long_pressed
+                    && touch.position.distance(touch.start_position) <= settings.tap_max_distance
+                    && now.saturating_duration_since(touch.start_time)
+                        >= settings.long_press_duration
+                {
+                    touch.long_pressed = true;
+                    long_presses.send(LongPressGesture {
+                        window: *window,
+                        position: touch.position,
+                    });
+                }
+            }
+        }
+        if gestures.last_tap.is_some_and(|(tap_time, _)| {
+            now.saturating_duration_since(tap_time) > settings.double_tap_interval
+        }) {
+            gestures.last_tap = None;
+        }
+    }
+    windows.retain(|_, gestures| !gestures.touches.is_empty() || gestures.last_tap.is_some());
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::Update;
+
+    use super::*;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_event::<TouchInput>()
+            .add_event::<Timestamped<TouchInput>>()
+            .init_resource::<TouchGestureSettings>()
+            .add_event::<TapGesture>()
+            .add_event::<DoubleTapGesture>()
+            .add_event::<LongPressGesture>()
+            .add_event::<SwipeGesture>()
+            .add_event::<PinchGesture>()
+            .add_event::<RotateGesture>()
+            .add_systems(Update, touch_gesture_system);
+        app
+    }
+
+    fn touch(app: &mut App, id: u64, phase: TouchPhase, position: Vec2, timestamp: Instant) {
+        let event = TouchInput {
+            phase,
+            position,
+            window: Entity::PLACEHOLDER,
+            force: None,
+            id,
+        };
+        app.world_mut().send_event(event);
+        app.world_mut().send_event(Timestamped { event, timestamp });
+    }
+
+    fn read<E: Event + Clone>(app: &mut App) -> Vec<E> {
+        app.world_mut()
+            .resource_mut::<Events<E>>()
+            .drain()
+            .collect::<Vec<_>>()
+    }
+
+    #[test]
+    fn tap_and_double_tap() {
+        let mut app = app();
+        let start = Instant::now();
+        let position = Vec2::new(100.0, 100.0);
+
+        touch(&mut app, 0, TouchPhase::Started, position, start);
+        touch(
+            &mut app,
+            0,
+            TouchPhase::Ended,
+            position,
+            start + Duration::from_millis(50),
+        );
+        app.update();
+        assert_eq!(
+            read::<TapGesture>(&mut app),
+            vec![TapGesture {
+                window: Entity::PLACEHOLDER,
+                position
+            }]
+        );
+
+        touch(
+            &mut app,
+            1,
+            TouchPhase::Started,
+            position,
+            start + Duration::from_millis(100),
+        );
+        touch(
+            &mut app,
+            1,
+            TouchPhase::Ended,
+            position,
+            start + Duration::from_millis(150),
+        );
+        app.update();
+        assert!(read::<TapGesture>(&mut app).is_empty());
+        assert_eq!(read::<DoubleTapGesture>(&mut app).len(), 1);
+    }
+
+    #[test]
+    fn long_press() {
+        let mut app = app();
+        let start = Instant::now() - Duration::from_secs(1);
+        let position = Vec2::new(100.0, 100.0);
+
+        touch(&mut app, 0, TouchPhase::Started, position, start);
+        app.update();
+        assert_eq!(read::<LongPressGesture>(&mut app).len(), 1);
+
+        // The long press is only sent once, and lifting the finger isn't a tap.
+        touch(&mut app, 0, TouchPhase::Ended, position, Instant::now());
+        app.update();
+        assert!(read::<LongPressGesture>(&mut app).is_empty());
+        assert!(read::<TapGesture>(&mut app).is_empty());
+    }
+
+    #[test]
+    fn swipe() {
+        let mut app = app();
+        let start = Instant::now();
+
+        touch(&mut app, 0, TouchPhase::Started, Vec2::ZERO, start);
+        touch(
+            &mut app,
+            0,
+            TouchPhase::Moved,
+            Vec2::new(100.0, 0.0),
+            start + Duration::from_millis(100),
+        );
+        touch(
+            &mut app,
+            0,
+            TouchPhase::Ended,
+            Vec2::new(200.0, 0.0),
+            start + Duration::from_millis(200),
+        );
+        app.update();
+        let swipes = read::<SwipeGesture>(&mut app);
+        assert_eq!(swipes.len(), 1);
+        assert_eq!(swipes[0].end, Vec2::new(200.0, 0.0));
+        assert!((swipes[0].velocity.x - 1000.0).abs() < 1.0);
+        assert!(read::<TapGesture>(&mut app).is_empty());
+    }
+
+    #[test]
+    fn pinch_and_rotate() {
+        let mut app = app();
+        let start = Instant::now();
+
+        touch(
+            &mut app,
+            0,
+            TouchPhase::Started,
+            Vec2::new(-10.0, 0.0),
+            start,
+        );
+        touch(
+            &mut app,
+            1,
+            TouchPhase::Started,
+            Vec2::new(10.0, 0.0),
+            start,
+        );
+        // Move the second finger above the focal point, turning by a quarter counterclockwise
+        // and doubling the distance between the fingers.
+        touch(
+            &mut app,
+            1,
+            TouchPhase::Moved,
+            Vec2::new(-10.0, -40.0),
+            start + Duration::from_millis(50),
+        );
+        app.update();
+
+        let pinches = read::<PinchGesture>(&mut app);
+        assert_eq!(pinches.len(), 1);
+        assert!((pinches[0].scale - 2.0).abs() < 1e-5);
+        assert_eq!(pinches[0].focal_point, Vec2::new(-10.0, -20.0));
+        let rotations = read::<RotateGesture>(&mut app);
+        assert_eq!(rotations.len(), 1);
+        assert!((rotations[0].delta - std::f32::consts::FRAC_PI_2).abs() < 1e-5);
+
+        // Lifting the fingers after a pinch isn't a tap.
+        touch(
+            &mut app,
+            0,
+            TouchPhase::Ended,
+            Vec2::new(-10.0, 0.0),
+            start + Duration::from_millis(100),
+        );
+        touch(
+            &mut app,
+            1,
+            TouchPhase::Ended,
+            Vec2::new(-10.0, -40.0),
+            start + Duration::from_millis(100),
+        );
+        app.update();
+        assert!(read::<TapGesture>(&mut app).is_empty());
+    }
+}
@@ -204,8 +204,8 @@ impl Plugin for LogPlugin {
 
             let fmt_layer = tracing_subscriber::fmt::Layer::default().with_writer(std::io::stderr);
 
-            // bevy_render::renderer logs a `tracy.frame_mark` event every frame
-            // at Level::INFO. Formatted logs should omit it.
+            // bevy_render::renderer logs a `tracy.frame_mark` event every frame, and the
+            // bevy_winit runner every update cycle, at Level::INFO. Formatted logs should omit it.
             #[cfg(feature = "tracing-tracy")]
             let fmt_layer =
                 fmt_layer.with_filter(tracing_subscriber::filter::FilterFn::new(|meta| {
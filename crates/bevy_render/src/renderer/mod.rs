@@ -18,6 +18,7 @@ use crate::{
 use bevy_ecs::{prelude::*, system::SystemState};
 use bevy_time::TimeSender;
 use bevy_utils::Instant;
+use bevy_window::PresentFeedback;
 use std::sync::Arc;
 use wgpu::{
     Adapter, AdapterInfo, CommandBuffer, CommandEncoder, Instance, Queue, RequestAdapterOptions,
@@ -80,6 +81,7 @@ pub fn render_system(world: &mut World, state: &mut SystemState<Query<Entity, Wi
             world.entity_mut(view_entity).remove::<ViewTarget>();
         }
 
+        let mut presented = false;
         let mut windows = world.resource_mut::<ExtractedWindows>();
         for window in windows.values_mut() {
             if let Some(wrapped_texture) = window.swap_chain_texture.take() {
@@ -89,10 +91,17 @@ pub fn render_system(world: &mut World, state: &mut SystemState<Query<Entity, Wi
                     // by wgpu.
                     // https://docs.rs/winit/0.29.9/wasm32-unknown-unknown/winit/window/struct.Window.html#method.pre_present_notify
                     surface_texture.present();
+                    presented = true;
                 }
             }
         }
 
+        if presented {
+            if let Some(present_feedback) = world.get_resource::<PresentFeedback>() {
+                present_feedback.report_presented(Instant::now());
+            }
+        }
+
         #[cfg(feature = "tracing-tracy")]
         bevy_utils::tracing::event!(
             bevy_utils::tracing::Level::INFO,
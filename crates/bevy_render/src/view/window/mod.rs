@@ -12,7 +12,8 @@ use bevy_ecs::{entity::EntityHashMap, prelude::*};
 use bevy_utils::warn_once;
 use bevy_utils::{default, tracing::debug, HashSet};
 use bevy_window::{
-    CompositeAlphaMode, PresentMode, PrimaryWindow, RawHandleWrapper, Window, WindowClosing,
+    ClosingWindow, CompositeAlphaMode, PresentFeedback, PresentMode, PrimaryWindow,
+    RawHandleWrapper, Window, WindowClosing,
 };
 use std::{
     num::NonZeroU32,
@@ -38,7 +39,11 @@ impl Plugin for WindowRenderPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(ScreenshotPlugin);
 
+        let present_feedback = app.world().get_resource::<PresentFeedback>().cloned();
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            if let Some(present_feedback) = present_feedback {
+                render_app.insert_resource(present_feedback);
+            }
             render_app
                 .init_resource::<ExtractedWindows>()
                 .init_resource::<WindowSurfaces>()
@@ -118,7 +123,9 @@ fn extract_windows(
     mut extracted_windows: ResMut<ExtractedWindows>,
     screenshot_manager: Extract<Res<ScreenshotManager>>,
     mut closing: Extract<EventReader<WindowClosing>>,
-    windows: Extract<Query<(Entity, &Window, &RawHandleWrapper, Option<&PrimaryWindow>)>>,
+    windows: Extract<
+        Query<(Entity, &Window, &RawHandleWrapper, Option<&PrimaryWindow>), Without<ClosingWindow>>,
+    >,
     mut removed: Extract<RemovedComponents<RawHandleWrapper>>,
     mut window_surfaces: ResMut<WindowSurfaces>,
 ) {
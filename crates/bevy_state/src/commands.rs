@@ -0,0 +1,176 @@
+use bevy_ecs::{system::Commands, world::World};
+use bevy_utils::tracing::warn;
+
+use crate::state::{FreelyMutableState, NextState, State, StateTransitionEvent};
+
+/// Extension trait for [`Commands`] adding state transitions.
+///
+/// This lets systems queue transitions without a [`ResMut<NextState<S>>`](NextState) parameter,
+/// e.g. systems generated by macros or run as one-shot systems.
+pub trait CommandsStatesExt {
+    /// Queues a transition of [`State<S>`] to `state`, as [`NextState::set`] does.
+    ///
+    /// The transition happens the next time the [`StateTransition`](crate::state::StateTransition)
+    /// schedule runs.
+    fn set_state<S: FreelyMutableState>(&mut self, state: S);
+
+    /// Queues a transition of [`State<S>`] to the value returned by `f`, as [`NextState::update`]
+    /// does.
+    fn update_state<S: FreelyMutableState>(&mut self, f: impl FnOnce(&S) -> S + Send + 'static);
+
+    /// Queues a transition of [`State<S>`] to its current value, running its
+    /// [`OnExit`](crate::state::OnExit), [`OnTransition`](crate::state::OnTransition) and
+    /// [`OnEnter`](crate::state::OnEnter) schedules again.
+    ///
+    /// This is useful to restart a level or reload a menu. A transition to another value queued
+    /// in [`NextState<S>`] takes precedence.
+    fn reenter_state<S: FreelyMutableState>(&mut self);
+}
+
+impl CommandsStatesExt for Commands<'_, '_> {
+    fn set_state<S: FreelyMutableState>(&mut self, state: S) {
+        self.add(move |world: &mut World| {
+            let Some(mut next_state) = world.get_resource_mut::<NextState<S>>() else {
+                warn!(
+                    "Unable to set the state of {}: it hasn't been added to the app",
+                    std::any::type_name::<S>()
+                );
+                return;
+            };
+            next_state.set(state);
+        });
+    }
+
+    fn update_state<S: FreelyMutableState>(&mut self, f: impl FnOnce(&S) -> S + Send + 'static) {
+        self.add(move |world: &mut World| {
+            let Some(current) = world
+                .get_resource::<State<S>>()
+                .map(|state| state.get().clone())
+            else {
+                warn!(
+                    "Unable to update the state of {}: it doesn't exist",
+                    std::any::type_name::<S>()
+                );
+                return;
+            };
+            let Some(mut next_state) = world.get_resource_mut::<NextState<S>>() else {
+                warn!(
+                    "Unable to update the state of {}: it hasn't been added to the app",
+                    std::any::type_name::<S>()
+                );
+                return;
+            };
+            next_state.update(&current, f);
+        });
+    }
+
+    fn reenter_state<S: FreelyMutableState>(&mut self) {
+        self.add(|world: &mut World| {
+            let Some(state) = world
+                .get_resource::<State<S>>()
+                .map(|state| state.get().clone())
+            else {
+                warn!(
+                    "Unable to reenter the state of {}: it doesn't exist",
+                    std::any::type_name::<S>()
+                );
+                return;
+            };
+            world.send_event(StateTransitionEvent {
+                before: Some(state.clone()),
+                after: Some(state),
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::event::EventRegistry;
+    use bevy_ecs::prelude::*;
+    use bevy_state_macros::States;
+
+    use super::*;
+    use crate as bevy_state;
+    use crate::state::{
+        setup_state_transitions_in_world, FreelyMutableState, OnEnter, OnExit, StateTransition,
+    };
+
+    #[derive(States, PartialEq, Eq, Debug, Default, Hash, Clone)]
+    enum GameState {
+        #[default]
+        Menu,
+        InGame,
+    }
+
+    #[derive(Resource, Default)]
+    struct TransitionCounter {
+        enter: usize,
+        exit: usize,
+    }
+
+    fn setup_world() -> World {
+        let mut world = World::new();
+        EventRegistry::register_event::<StateTransitionEvent<GameState>>(&mut world);
+        world.init_resource::<State<GameState>>();
+        world.init_resource::<NextState<GameState>>();
+        world.init_resource::<TransitionCounter>();
+        setup_state_transitions_in_world(&mut world, None);
+
+        let mut schedules = world.resource_mut::<Schedules>();
+        GameState::register_state(schedules.get_mut(StateTransition).unwrap());
+        schedules.add_systems(
+            OnEnter(GameState::InGame),
+            |mut counter: ResMut<TransitionCounter>| counter.enter += 1,
+        );
+        schedules.add_systems(
+            OnExit(GameState::InGame),
+            |mut counter: ResMut<TransitionCounter>| counter.exit += 1,
+        );
+
+        // Run the transitions for the initial state.
+        world.run_schedule(StateTransition);
+        world
+    }
+
+    #[test]
+    fn set_state() {
+        let mut world = setup_world();
+
+        world.commands().set_state(GameState::InGame);
+        world.flush_commands();
+        world.run_schedule(StateTransition);
+
+        assert_eq!(
+            world.resource::<State<GameState>>().get(),
+            &GameState::InGame
+        );
+        assert_eq!(world.resource::<TransitionCounter>().enter, 1);
+    }
+
+    #[test]
+    fn reenter_state() {
+        let mut world = setup_world();
+        world.insert_resource(NextState::Pending(GameState::InGame));
+        world.run_schedule(StateTransition);
+
+        world.commands().reenter_state::<GameState>();
+        world.flush_commands();
+        world.run_schedule(StateTransition);
+
+        assert_eq!(
+            world.resource::<State<GameState>>().get(),
+            &GameState::InGame
+        );
+        assert_eq!(world.resource::<TransitionCounter>().enter, 2);
+        assert_eq!(world.resource::<TransitionCounter>().exit, 1);
+        let transition = world
+            .resource::<Events<StateTransitionEvent<GameState>>>()
+            .iter_current_update_events()
+            .last()
+            .unwrap();
+        assert!(transition.is_reentry());
+        assert_eq!(transition.exited(), Some(&GameState::InGame));
+        assert_eq!(transition.entered(), Some(&GameState::InGame));
+    }
+}
@@ -1,4 +1,8 @@
-use bevy_ecs::{change_detection::DetectChanges, system::Res};
+use bevy_ecs::{
+    change_detection::DetectChanges,
+    schedule::{IntoSystemSetConfigs, Schedule, SystemSet},
+    system::Res,
+};
 use bevy_utils::warn_once;
 
 use crate::state::{State, States};
@@ -173,6 +177,55 @@ pub fn state_changed<S: States>(current_state: Option<Res<State<S>>>) -> bool {
     current_state.is_changed()
 }
 
+/// A [`SystemSet`] whose systems only run while the state machine is in the contained state,
+/// once it is configured with [`configure_in_state_set`].
+///
+/// Each system using [`in_state`] evaluates its own condition. With many systems depending on the
+/// same state, adding them to this set instead evaluates the condition once per schedule run for
+/// all of them.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_state::prelude::*;
+/// # use bevy_state::condition::{configure_in_state_set, InState};
+/// # let mut schedule = Schedule::default();
+/// #[derive(States, Clone, Copy, Default, Eq, PartialEq, Hash, Debug)]
+/// enum GameState {
+///     #[default]
+///     Playing,
+///     Paused,
+/// }
+///
+/// configure_in_state_set(&mut schedule, GameState::Playing);
+/// schedule.add_systems((move_player, move_enemies).in_set(InState(GameState::Playing)));
+/// # fn move_player() {}
+/// # fn move_enemies() {}
+/// ```
+#[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct InState<S: States>(pub S);
+
+/// Configures the [`InState(state)`](InState) set of `schedule` to only run while the state
+/// machine is in `state`.
+///
+/// This is idempotent: the condition is only added the first time the set is configured.
+pub fn configure_in_state_set<S: States>(schedule: &mut Schedule, state: S) {
+    // The conditions are moved out of the graph when the schedule is built, so the configured sets
+    // are recorded with a marker set instead.
+    let marker = InStateConfigured(state.clone());
+    let configured = schedule.graph().system_sets().any(|(_, set, _)| {
+        set.as_dyn_eq()
+            .as_any()
+            .downcast_ref::<InStateConfigured<S>>()
+            == Some(&marker)
+    });
+    if !configured {
+        schedule.configure_sets((InState(state.clone()).run_if(in_state(state)), marker));
+    }
+}
+
+#[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+struct InStateConfigured<S: States>(S);
+
 #[cfg(test)]
 mod tests {
     use crate as bevy_state;
@@ -201,4 +254,42 @@ mod tests {
                 .distributive_run_if(state_changed::<TestState>),
         );
     }
+
+    #[test]
+    fn in_state_set_is_configured_once() {
+        use bevy_ecs::prelude::*;
+
+        use super::{configure_in_state_set, InState};
+
+        #[derive(Resource, Default)]
+        struct Counter(usize);
+
+        let mut world = World::new();
+        world.init_resource::<State<TestState>>();
+        world.init_resource::<Counter>();
+        let mut schedule = Schedule::default();
+        configure_in_state_set(&mut schedule, TestState::A);
+        configure_in_state_set(&mut schedule, TestState::A);
+        schedule.add_systems(
+            (
+                |mut counter: ResMut<Counter>| counter.0 += 1,
+                |mut counter: ResMut<Counter>| counter.0 += 1,
+            )
+                .in_set(InState(TestState::A)),
+        );
+        let conditions = schedule
+            .graph()
+            .system_sets()
+            .map(|(_, _, conditions)| conditions.len())
+            .sum::<usize>();
+        assert_eq!(conditions, 1);
+
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 2);
+
+        configure_in_state_set(&mut schedule, TestState::A);
+        world.insert_resource(State::new(TestState::B));
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 2);
+    }
 }
@@ -27,13 +27,21 @@
 //! - The [`in_state<S>`](crate::condition::in_state) and [`state_changed<S>`](crate::condition::state_changed) run conditions - which are used
 //!   to determine whether a system should run based on the current state.
 
+/// Provides extension methods for [`Commands`](bevy_ecs::system::Commands) to change states
+pub mod commands;
 /// Provides definitions for the runtime conditions that interact with the state system
 pub mod condition;
+/// Provides snapshots of states, to roll them back later
+pub mod snapshot;
 /// Provides definitions for the basic traits required by the state system
 pub mod state;
+/// Provides extension methods for [`World`](bevy_ecs::world::World) to add and remove states at runtime
+pub mod world;
 
 /// Most commonly used re-exported types.
 pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::commands::CommandsStatesExt;
     #[doc(hidden)]
     pub use crate::condition::*;
     #[doc(hidden)]
@@ -41,4 +49,6 @@ pub mod prelude {
         apply_state_transition, ComputedStates, NextState, OnEnter, OnExit, OnTransition, State,
         StateSet, StateTransition, StateTransitionEvent, States, SubStates,
     };
+    #[doc(hidden)]
+    pub use crate::world::WorldStatesExt;
 }
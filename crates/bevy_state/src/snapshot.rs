@@ -0,0 +1,184 @@
+use bevy_ecs::world::World;
+
+use crate::{
+    state::{FreelyMutableState, NextState, State, StateTransitionEvent},
+    world::WorldStatesExt,
+};
+
+/// A snapshot of the values of one or more state machines, which can be restored later.
+///
+/// This is intended for rollback networking and replay systems, which must rewind the game flow
+/// and not just component data.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_state::prelude::*;
+/// # use bevy_state::snapshot::StateSnapshot;
+/// #[derive(States, Clone, Copy, Default, Eq, PartialEq, Hash, Debug)]
+/// enum GameState {
+///     #[default]
+///     Playing,
+///     GameOver,
+/// }
+///
+/// # let mut world = World::new();
+/// # world.insert_state(GameState::Playing);
+/// let mut snapshot = StateSnapshot::default();
+/// snapshot.capture::<GameState>(&world);
+///
+/// // Later, e.g. when a late input invalidates the last frames:
+/// snapshot.restore(&mut world);
+/// ```
+///
+/// Only the states captured explicitly are restored: [`ComputedStates`](crate::state::ComputedStates)
+/// are derived again from their sources, so they don't need to be captured.
+#[derive(Default, Clone)]
+pub struct StateSnapshot {
+    entries: Vec<Box<dyn SnapshotEntry>>,
+}
+
+impl StateSnapshot {
+    /// Records the current value of the state `S`, or its absence.
+    ///
+    /// Capturing the same state again overrides the previous value.
+    pub fn capture<S: FreelyMutableState>(&mut self, world: &World) -> &mut Self {
+        let state = world
+            .get_resource::<State<S>>()
+            .map(|state| state.get().clone());
+        self.entries
+            .retain(|entry| entry.as_any().downcast_ref::<Option<S>>().is_none());
+        self.entries.push(Box::new(state));
+        self
+    }
+
+    /// Returns the captured value of the state `S`, or `None` if it wasn't captured.
+    ///
+    /// The inner `Option` is `None` if the state didn't exist when it was captured.
+    pub fn get<S: FreelyMutableState>(&self) -> Option<Option<&S>> {
+        self.entries
+            .iter()
+            .find_map(|entry| entry.as_any().downcast_ref::<Option<S>>())
+            .map(Option::as_ref)
+    }
+
+    /// Restores the captured states, discarding their pending transitions.
+    ///
+    /// A [`StateTransitionEvent`] is sent for each state whose value differs from the captured one,
+    /// so its [`OnExit`](crate::state::OnExit), [`OnTransition`](crate::state::OnTransition) and
+    /// [`OnEnter`](crate::state::OnEnter) schedules run, and the states depending on it are
+    /// updated, the next time the [`StateTransition`](crate::state::StateTransition) schedule runs.
+    pub fn restore(&self, world: &mut World) {
+        for entry in &self.entries {
+            entry.restore(world);
+        }
+    }
+}
+
+trait SnapshotEntry: Send + Sync + 'static {
+    fn as_any(&self) -> &dyn std::any::Any;
+
+    fn clone_box(&self) -> Box<dyn SnapshotEntry>;
+
+    fn restore(&self, world: &mut World);
+}
+
+impl<S: FreelyMutableState> SnapshotEntry for Option<S> {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn SnapshotEntry> {
+        Box::new(self.clone())
+    }
+
+    fn restore(&self, world: &mut World) {
+        let Some(captured) = self else {
+            world.remove_state::<S>();
+            return;
+        };
+        if let Some(mut next_state) = world.get_resource_mut::<NextState<S>>() {
+            next_state.reset();
+        }
+        let Some(mut state) = world.get_resource_mut::<State<S>>() else {
+            world.insert_state(captured.clone());
+            return;
+        };
+        if state.0 == *captured {
+            return;
+        }
+
+        let before = std::mem::replace(&mut state.0, captured.clone());
+        world.send_event(StateTransitionEvent {
+            before: Some(before),
+            after: Some(captured.clone()),
+        });
+    }
+}
+
+impl Clone for Box<dyn SnapshotEntry> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::prelude::*;
+    use bevy_state_macros::States;
+
+    use super::*;
+    use crate as bevy_state;
+    use crate::state::{setup_state_transitions_in_world, OnEnter, StateTransition};
+
+    #[derive(States, PartialEq, Eq, Debug, Default, Hash, Clone)]
+    enum GameState {
+        #[default]
+        Playing,
+        GameOver,
+    }
+
+    #[derive(States, PartialEq, Eq, Debug, Default, Hash, Clone)]
+    struct Round(u32);
+
+    #[derive(Resource, Default)]
+    struct PlayingEntered(usize);
+
+    #[test]
+    fn restore_snapshot() {
+        let mut world = World::new();
+        world.init_resource::<PlayingEntered>();
+        setup_state_transitions_in_world(&mut world, None);
+        world.resource_mut::<Schedules>().add_systems(
+            OnEnter(GameState::Playing),
+            |mut entered: ResMut<PlayingEntered>| entered.0 += 1,
+        );
+        world.insert_state(GameState::Playing);
+        world.run_schedule(StateTransition);
+
+        let mut snapshot = StateSnapshot::default();
+        snapshot
+            .capture::<GameState>(&world)
+            .capture::<Round>(&world);
+        assert_eq!(snapshot.get::<GameState>(), Some(Some(&GameState::Playing)));
+        assert_eq!(snapshot.get::<Round>(), Some(None));
+
+        world.insert_state(Round(3));
+        world
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::GameOver);
+        world.run_schedule(StateTransition);
+        assert_eq!(
+            world.resource::<State<GameState>>().get(),
+            &GameState::GameOver
+        );
+
+        snapshot.clone().restore(&mut world);
+        world.run_schedule(StateTransition);
+        assert_eq!(
+            world.resource::<State<GameState>>().get(),
+            &GameState::Playing
+        );
+        assert!(!world.contains_resource::<State<Round>>());
+        assert_eq!(world.resource::<PlayingEntered>().0, 2);
+    }
+}
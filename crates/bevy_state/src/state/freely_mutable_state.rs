@@ -19,17 +19,26 @@ pub trait FreelyMutableState: States {
             .add_systems(
                 should_run_transition::<Self, OnEnter<Self>>
                     .pipe(run_enter::<Self>)
-                    .in_set(StateTransitionSteps::EnterSchedules),
+                    .in_set(StateTransitionSteps::EnterSchedules)
+                    .in_set(RunStateTransitionSchedules::<Self>::new(
+                        StateTransitionSteps::EnterSchedules,
+                    )),
             )
             .add_systems(
                 should_run_transition::<Self, OnExit<Self>>
                     .pipe(run_exit::<Self>)
-                    .in_set(StateTransitionSteps::ExitSchedules),
+                    .in_set(StateTransitionSteps::ExitSchedules)
+                    .in_set(RunStateTransitionSchedules::<Self>::new(
+                        StateTransitionSteps::ExitSchedules,
+                    )),
             )
             .add_systems(
                 should_run_transition::<Self, OnTransition<Self>>
                     .pipe(run_transition::<Self>)
-                    .in_set(StateTransitionSteps::TransitionSchedules),
+                    .in_set(StateTransitionSteps::TransitionSchedules)
+                    .in_set(RunStateTransitionSchedules::<Self>::new(
+                        StateTransitionSteps::TransitionSchedules,
+                    )),
             )
             .configure_sets(
                 ApplyStateTransition::<Self>::apply()
@@ -497,4 +497,271 @@ mod tests {
             "Should Only Exit Twice"
         );
     }
+
+    #[derive(States, PartialEq, Eq, Debug, Default, Hash, Clone)]
+    enum AssetState {
+        #[default]
+        Loading,
+        Loaded,
+    }
+
+    #[derive(Resource, Default)]
+    struct EnterOrder(Vec<&'static str>);
+
+    #[test]
+    fn ordered_state_transitions_apply_in_order() {
+        let mut world = World::new();
+        EventRegistry::register_event::<StateTransitionEvent<AssetState>>(&mut world);
+        EventRegistry::register_event::<StateTransitionEvent<SimpleState>>(&mut world);
+        world.init_resource::<State<AssetState>>();
+        world.init_resource::<State<SimpleState>>();
+        world.init_resource::<EnterOrder>();
+        setup_state_transitions_in_world(&mut world, None);
+
+        let mut schedules = world.resource_mut::<Schedules>();
+        let apply_changes = schedules.get_mut(StateTransition).unwrap();
+        SimpleState::register_state(apply_changes);
+        AssetState::register_state(apply_changes);
+        order_state_transitions::<AssetState, SimpleState>(apply_changes);
+
+        // The sets of the two states are ordered in the dependency graph, so the order doesn't
+        // depend on how the executor sorts independent systems.
+        let graph = apply_changes.graph();
+        let set_node = |set: &dyn SystemSet| {
+            graph
+                .system_sets()
+                .find(|(_, other, _)| *other == set)
+                .map(|(node, _, _)| node)
+                .unwrap()
+        };
+        let ordered = |before: &dyn SystemSet, after: &dyn SystemSet| {
+            graph
+                .dependency()
+                .graph()
+                .contains_edge(set_node(before), set_node(after))
+        };
+        assert!(ordered(
+            &ApplyStateTransition::<AssetState>::apply(),
+            &ApplyStateTransition::<SimpleState>::apply()
+        ));
+        for step in [
+            StateTransitionSteps::ExitSchedules,
+            StateTransitionSteps::TransitionSchedules,
+            StateTransitionSteps::EnterSchedules,
+        ] {
+            assert!(ordered(
+                &RunStateTransitionSchedules::<AssetState>::new(step.clone()),
+                &RunStateTransitionSchedules::<SimpleState>::new(step)
+            ));
+        }
+
+        schedules.add_systems(
+            OnEnter(AssetState::Loaded),
+            |mut order: ResMut<EnterOrder>| order.0.push("asset"),
+        );
+        schedules.add_systems(
+            OnEnter(SimpleState::B(true)),
+            |mut order: ResMut<EnterOrder>| order.0.push("simple"),
+        );
+
+        world.run_schedule(StateTransition);
+        world.insert_resource(NextState::Pending(SimpleState::B(true)));
+        world.insert_resource(NextState::Pending(AssetState::Loaded));
+        world.run_schedule(StateTransition);
+
+        assert_eq!(world.resource::<EnterOrder>().0, ["asset", "simple"]);
+    }
+
+    #[test]
+    fn conflicting_next_state_writes_follow_the_policy() {
+        let cases = [
+            (
+                NextStateConflictResolution::LastWins,
+                0,
+                SimpleState::B(false),
+            ),
+            (
+                NextStateConflictResolution::FirstWins,
+                0,
+                SimpleState::B(true),
+            ),
+            (
+                NextStateConflictResolution::Priority,
+                1,
+                SimpleState::B(false),
+            ),
+            (
+                NextStateConflictResolution::Priority,
+                -1,
+                SimpleState::B(true),
+            ),
+        ];
+        for (resolution, priority, expected) in cases {
+            let mut world = World::new();
+            EventRegistry::register_event::<StateTransitionEvent<SimpleState>>(&mut world);
+            EventRegistry::register_event::<NextStateConflict<SimpleState>>(&mut world);
+            world.init_resource::<State<SimpleState>>();
+            world.init_resource::<NextState<SimpleState>>();
+            world.insert_resource(NextStateConflictPolicy::<SimpleState>::new(resolution));
+            let mut schedules = Schedules::new();
+            let mut apply_changes = Schedule::new(StateTransition);
+            SimpleState::register_state(&mut apply_changes);
+            schedules.insert(apply_changes);
+            world.insert_resource(schedules);
+            setup_state_transitions_in_world(&mut world, None);
+
+            // The first value is inserted directly, and treated as written with the default priority.
+            world.insert_resource(NextState::Pending(SimpleState::B(true)));
+            world
+                .resource_mut::<NextState<SimpleState>>()
+                .set_with_priority(SimpleState::B(false), priority);
+            world.run_schedule(StateTransition);
+
+            assert_eq!(world.resource::<State<SimpleState>>().0, expected);
+            let conflicts = world
+                .resource_mut::<Events<NextStateConflict<SimpleState>>>()
+                .drain()
+                .collect::<Vec<_>>();
+            assert_eq!(conflicts.len(), 1);
+            assert_eq!(conflicts[0].kept, expected);
+
+            // Re-inserting the next state keeps the policy.
+            world.insert_resource(NextState::Pending(SimpleState::A));
+            assert_eq!(
+                world
+                    .resource::<NextStateConflictPolicy<SimpleState>>()
+                    .resolution,
+                resolution
+            );
+        }
+    }
+
+    #[test]
+    fn conflicting_next_state_writes_are_reported_with_the_error_resolution() {
+        let mut world = World::new();
+        EventRegistry::register_event::<StateTransitionEvent<SimpleState>>(&mut world);
+        EventRegistry::register_event::<NextStateConflict<SimpleState>>(&mut world);
+        world.init_resource::<State<SimpleState>>();
+        world.init_resource::<NextState<SimpleState>>();
+        world.insert_resource(NextStateConflictPolicy::<SimpleState>::new(
+            NextStateConflictResolution::Error,
+        ));
+        let mut schedules = Schedules::new();
+        let mut apply_changes = Schedule::new(StateTransition);
+        SimpleState::register_state(&mut apply_changes);
+        schedules.insert(apply_changes);
+        world.insert_resource(schedules);
+        setup_state_transitions_in_world(&mut world, None);
+
+        let mut next_state = world.resource_mut::<NextState<SimpleState>>();
+        next_state.set(SimpleState::B(true));
+        next_state.set(SimpleState::B(true));
+        next_state.set(SimpleState::B(false));
+        world.run_schedule(StateTransition);
+
+        assert_eq!(
+            world.resource::<State<SimpleState>>().0,
+            SimpleState::B(false)
+        );
+        let conflicts = world
+            .resource_mut::<Events<NextStateConflict<SimpleState>>>()
+            .drain()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            conflicts,
+            [NextStateConflict {
+                kept: SimpleState::B(false),
+                rejected: SimpleState::B(true),
+            }]
+        );
+    }
+
+    #[derive(States, PartialEq, Eq, Debug, Default, Hash, Clone)]
+    struct Level(u32);
+
+    #[test]
+    fn data_carrying_states_update_and_match_predicates() {
+        let mut world = World::new();
+        EventRegistry::register_event::<StateTransitionEvent<Level>>(&mut world);
+        world.init_resource::<State<Level>>();
+        world.init_resource::<NextState<Level>>();
+        world.init_resource::<ComputedStateTransitionCounter>();
+        setup_state_transitions_in_world(&mut world, None);
+
+        let on_enter = OnEnterMatching::new("level_2", |level: &Level| level.0 >= 2);
+        let on_exit = OnExitMatching::new("level_2", |level: &Level| level.0 >= 2);
+        // Schedules that aren't registered don't run.
+        let unregistered = OnEnterMatching::new("unregistered", |_: &Level| true);
+        on_enter.register(&mut world);
+        on_exit.register(&mut world);
+        on_exit.register(&mut world);
+        let mut schedules = world.resource_mut::<Schedules>();
+        Level::register_state(schedules.get_mut(StateTransition).unwrap());
+        schedules.add_systems(
+            on_enter,
+            |mut counter: ResMut<ComputedStateTransitionCounter>| counter.enter += 1,
+        );
+        schedules.add_systems(
+            on_exit,
+            |mut counter: ResMut<ComputedStateTransitionCounter>| counter.exit += 1,
+        );
+        schedules.add_systems(
+            unregistered,
+            |mut counter: ResMut<ComputedStateTransitionCounter>| counter.enter += 100,
+        );
+        world.run_schedule(StateTransition);
+
+        for expected in 1..=3 {
+            world.resource_scope(|world, mut next_level: Mut<NextState<Level>>| {
+                let level = world.resource::<State<Level>>();
+                next_level.update(level, |level| Level(level.0 + 1));
+            });
+            world.run_schedule(StateTransition);
+            assert_eq!(world.resource::<State<Level>>().0, Level(expected));
+        }
+
+        // Updates in the same frame build on each other.
+        world.resource_scope(|world, mut next_level: Mut<NextState<Level>>| {
+            let level = world.resource::<State<Level>>();
+            next_level.update(level, |level| Level(level.0 + 1));
+            next_level.update(level, |level| Level(level.0 + 1));
+        });
+        world.run_schedule(StateTransition);
+        assert_eq!(world.resource::<State<Level>>().0, Level(5));
+
+        let counter = world.resource::<ComputedStateTransitionCounter>();
+        assert_eq!(counter.enter, 3);
+        assert_eq!(counter.exit, 2);
+    }
+
+    #[derive(Resource, Default)]
+    struct EnteredFrom(Vec<Option<Level>>);
+
+    #[test]
+    fn transition_context_is_available_during_transition_schedules() {
+        let mut world = World::new();
+        EventRegistry::register_event::<StateTransitionEvent<Level>>(&mut world);
+        world.init_resource::<State<Level>>();
+        world.init_resource::<NextState<Level>>();
+        world.init_resource::<EnteredFrom>();
+        setup_state_transitions_in_world(&mut world, None);
+
+        let any_level = OnEnterMatching::new("any_level", |_: &Level| true);
+        any_level.register(&mut world);
+        let mut schedules = world.resource_mut::<Schedules>();
+        Level::register_state(schedules.get_mut(StateTransition).unwrap());
+        schedules.add_systems(
+            any_level,
+            |context: Res<StateTransitionContext<Level>>, mut entered: ResMut<EnteredFrom>| {
+                entered.0.push(context.exited().cloned());
+            },
+        );
+        world.run_schedule(StateTransition);
+
+        world.insert_resource(NextState::Pending(Level(4)));
+        world.run_schedule(StateTransition);
+
+        assert_eq!(world.resource::<EnteredFrom>().0, [None, Some(Level(0))]);
+        assert!(!world.contains_resource::<StateTransitionContext<Level>>());
+    }
 }
@@ -1,9 +1,20 @@
-use std::ops::Deref;
+use std::{
+    any::{Any, TypeId},
+    marker::PhantomData,
+    mem,
+    ops::Deref,
+    sync::{Mutex, OnceLock, PoisonError},
+};
 
 use bevy_ecs::{
+    event::Event,
     system::Resource,
     world::{FromWorld, World},
 };
+use bevy_utils::{
+    tracing::{error, warn},
+    HashMap,
+};
 
 use super::{freely_mutable_state::FreelyMutableState, states::States};
 
@@ -87,8 +98,10 @@ impl<S: States> Deref for State<S> {
 ///
 /// To queue a transition, just set the contained value to `Some(next_state)`.
 ///
-/// Note that these transitions can be overridden by other systems:
-/// only the actual value of this resource at the time of [`apply_state_transition`](crate::state::apply_state_transition) matters.
+/// Note that these transitions can be overridden by other systems: by default, only the actual
+/// value of this resource at the time of [`apply_state_transition`](crate::state::apply_state_transition) matters.
+/// Conflicting writes in the same frame are resolved and reported with the
+/// [`NextStateConflictPolicy`].
 ///
 /// ```
 /// use bevy_state::prelude::*;
@@ -123,11 +136,219 @@ pub enum NextState<S: FreelyMutableState> {
 impl<S: FreelyMutableState> NextState<S> {
     /// Tentatively set a pending state transition to `Some(state)`.
     pub fn set(&mut self, state: S) {
+        self.set_with_priority(state, 0);
+    }
+
+    /// Tentatively set a pending state transition to `Some(state)`, with a `priority` used by
+    /// [`NextStateConflictResolution::Priority`] to resolve a conflict with other writes in the
+    /// same frame. [`NextState::set`] writes with the default priority of `0`.
+    pub fn set_with_priority(&mut self, state: S, priority: i32) {
+        self.record_write(|writes| writes.push((state.clone(), priority)));
         *self = Self::Pending(state);
     }
 
+    /// Queues a transition to the value returned by `f`, which is called with the pending state
+    /// if there is one, or the `current` state otherwise.
+    ///
+    /// This is convenient for states carrying data, e.g.
+    /// `next_level.update(&level, |level| Level(level.0 + 1))`: several updates in the same frame
+    /// build on each other rather than conflicting.
+    pub fn update(&mut self, current: &S, f: impl FnOnce(&S) -> S) {
+        let next = match self {
+            Self::Pending(pending) => f(pending),
+            Self::Unchanged => f(current),
+        };
+        self.record_write(|writes| match writes.last_mut() {
+            Some((written, _)) => *written = next.clone(),
+            None => writes.push((next.clone(), 0)),
+        });
+        *self = Self::Pending(next);
+    }
+
     /// Remove any pending changes to [`State<S>`]
     pub fn reset(&mut self) {
+        self.take_writes();
         *self = Self::Unchanged;
     }
+
+    /// Records a write in the values set since the last transition.
+    fn record_write(&self, f: impl FnOnce(&mut Vec<(S, i32)>)) {
+        let mut all_writes = NEXT_STATE_WRITES
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let writes = all_writes
+            .entry(self.writes_key())
+            .or_insert_with(|| Box::<Vec<(S, i32)>>::default())
+            .downcast_mut::<Vec<(S, i32)>>()
+            .expect("the writes are keyed by the type of the state");
+        self.sync_writes(writes);
+        f(writes);
+    }
+
+    /// Takes the values set since the last transition with their priority, ending with the
+    /// pending state.
+    pub(crate) fn take_writes(&self) -> Vec<(S, i32)> {
+        let mut writes = NEXT_STATE_WRITES
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&self.writes_key())
+            .and_then(|writes| writes.downcast::<Vec<(S, i32)>>().ok())
+            .map(|writes| *writes)
+            .unwrap_or_default();
+        self.sync_writes(&mut writes);
+        writes
+    }
+
+    /// Restarts the recorded writes from the pending state when they don't end with it, e.g.
+    /// when the resource was assigned or inserted directly.
+    fn sync_writes(&self, writes: &mut Vec<(S, i32)>) {
+        let pending = match self {
+            Self::Pending(pending) => Some(pending),
+            Self::Unchanged => None,
+        };
+        if writes.last().map(|(written, _)| written) != pending {
+            writes.clear();
+            writes.extend(pending.map(|pending| (pending.clone(), 0)));
+        }
+    }
+
+    fn writes_key(&self) -> (usize, TypeId) {
+        (std::ptr::from_ref(self) as usize, TypeId::of::<S>())
+    }
+}
+
+/// The values set on each [`NextState`] since its last transition, with their priority.
+///
+/// [`NextState`] stays a plain enum that systems can match on, so its writes are recorded here,
+/// keyed by the address of the resource, for
+/// [`apply_state_transition`](crate::state::apply_state_transition) to resolve the conflicts.
+#[allow(clippy::type_complexity)]
+static NEXT_STATE_WRITES: OnceLock<Mutex<HashMap<(usize, TypeId), Box<dyn Any + Send>>>> =
+    OnceLock::new();
+
+/// How conflicting transitions of [`NextState<S>`] are resolved, e.g. when two systems set
+/// different values in the same frame.
+///
+/// Every conflict is reported with a [`NextStateConflict<S>`] event, whatever the resolution.
+/// The resource is added with the state, and can be inserted beforehand to configure it:
+///
+/// ```
+/// # use bevy_state::prelude::*;
+/// # use bevy_state::state::{NextStateConflictPolicy, NextStateConflictResolution};
+/// # use bevy_ecs::prelude::*;
+/// # #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// # enum GameState { #[default] MainMenu, InGame }
+/// # let mut world = World::new();
+/// world.insert_resource(NextStateConflictPolicy::<GameState>::new(
+///     NextStateConflictResolution::Warn,
+/// ));
+/// ```
+#[derive(Resource, Debug)]
+pub struct NextStateConflictPolicy<S: FreelyMutableState> {
+    /// How the conflicts are resolved.
+    pub resolution: NextStateConflictResolution,
+    _marker: PhantomData<S>,
+}
+
+impl<S: FreelyMutableState> Default for NextStateConflictPolicy<S> {
+    fn default() -> Self {
+        Self::new(NextStateConflictResolution::default())
+    }
+}
+
+impl<S: FreelyMutableState> NextStateConflictPolicy<S> {
+    /// Creates a policy resolving the conflicts with `resolution`.
+    pub fn new(resolution: NextStateConflictResolution) -> Self {
+        Self {
+            resolution,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Resolves the values written to [`NextState<S>`] in order, returning the state to apply
+    /// and the conflicts between different values.
+    pub(crate) fn resolve(&self, writes: Vec<(S, i32)>) -> (Option<S>, Vec<NextStateConflict<S>>) {
+        let mut writes = writes.into_iter();
+        let Some((mut pending, mut pending_priority)) = writes.next() else {
+            return (None, Vec::new());
+        };
+        let mut conflicts = Vec::new();
+        for (state, priority) in writes {
+            if state == pending {
+                pending_priority = pending_priority.max(priority);
+                continue;
+            }
+
+            let replace = match self.resolution {
+                NextStateConflictResolution::LastWins
+                | NextStateConflictResolution::Warn
+                | NextStateConflictResolution::Error => true,
+                NextStateConflictResolution::FirstWins => false,
+                NextStateConflictResolution::Priority => priority >= pending_priority,
+            };
+            let conflict = if replace {
+                NextStateConflict {
+                    kept: state.clone(),
+                    rejected: mem::replace(&mut pending, state),
+                }
+            } else {
+                NextStateConflict {
+                    kept: pending.clone(),
+                    rejected: state,
+                }
+            };
+            if replace {
+                pending_priority = priority;
+            }
+            match self.resolution {
+                NextStateConflictResolution::Warn => warn!(
+                    "Conflicting transitions of {}: {:?} overrides {:?}",
+                    std::any::type_name::<S>(),
+                    conflict.kept,
+                    conflict.rejected
+                ),
+                NextStateConflictResolution::Error => error!(
+                    "Conflicting transitions of {}: {:?} overrides {:?}",
+                    std::any::type_name::<S>(),
+                    conflict.kept,
+                    conflict.rejected
+                ),
+                _ => {}
+            }
+            conflicts.push(conflict);
+        }
+        (Some(pending), conflicts)
+    }
+}
+
+/// How a [`NextStateConflictPolicy`] resolves a transition set to a different value than the one
+/// already pending in the same frame.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub enum NextStateConflictResolution {
+    /// The last value set wins.
+    #[default]
+    LastWins,
+    /// The last value set wins, and a warning is logged.
+    Warn,
+    /// The conflict is a bug: the last value set wins, and an error is logged.
+    Error,
+    /// The first value set wins.
+    FirstWins,
+    /// The value set with the highest priority by [`NextState::set_with_priority`] wins.
+    /// On a tie, the last value set wins.
+    Priority,
+}
+
+/// Event sent by [`apply_state_transition`](crate::state::apply_state_transition) for each
+/// transition of [`NextState<S>`] overridden by another in the same frame, see
+/// [`NextStateConflictPolicy`].
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct NextStateConflict<S: FreelyMutableState> {
+    /// The pending state after the conflict was resolved.
+    pub kept: S,
+    /// The state that was discarded.
+    pub rejected: S,
 }
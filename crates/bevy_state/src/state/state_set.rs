@@ -10,8 +10,8 @@ use self::sealed::StateSetSealed;
 use super::{
     apply_state_transition, computed_states::ComputedStates, internal_apply_state_transition,
     run_enter, run_exit, run_transition, should_run_transition, sub_states::SubStates,
-    ApplyStateTransition, OnEnter, OnExit, OnTransition, State, StateTransitionEvent,
-    StateTransitionSteps, States,
+    ApplyStateTransition, OnEnter, OnExit, OnTransition, RunStateTransitionSchedules, State,
+    StateTransitionEvent, StateTransitionSteps, States,
 };
 
 mod sealed {
@@ -119,17 +119,26 @@ impl<S: InnerStateSet> StateSet for S {
             .add_systems(
                 should_run_transition::<T, OnEnter<T>>
                     .pipe(run_enter::<T>)
-                    .in_set(StateTransitionSteps::EnterSchedules),
+                    .in_set(StateTransitionSteps::EnterSchedules)
+                    .in_set(RunStateTransitionSchedules::<T>::new(
+                        StateTransitionSteps::EnterSchedules,
+                    )),
             )
             .add_systems(
                 should_run_transition::<T, OnExit<T>>
                     .pipe(run_exit::<T>)
-                    .in_set(StateTransitionSteps::ExitSchedules),
+                    .in_set(StateTransitionSteps::ExitSchedules)
+                    .in_set(RunStateTransitionSchedules::<T>::new(
+                        StateTransitionSteps::ExitSchedules,
+                    )),
             )
             .add_systems(
                 should_run_transition::<T, OnTransition<T>>
                     .pipe(run_transition::<T>)
-                    .in_set(StateTransitionSteps::TransitionSchedules),
+                    .in_set(StateTransitionSteps::TransitionSchedules)
+                    .in_set(RunStateTransitionSchedules::<T>::new(
+                        StateTransitionSteps::TransitionSchedules,
+                    )),
             )
             .configure_sets(
                 ApplyStateTransition::<T>::apply()
@@ -183,17 +192,26 @@ impl<S: InnerStateSet> StateSet for S {
             .add_systems(
                 should_run_transition::<T, OnEnter<T>>
                     .pipe(run_enter::<T>)
-                    .in_set(StateTransitionSteps::EnterSchedules),
+                    .in_set(StateTransitionSteps::EnterSchedules)
+                    .in_set(RunStateTransitionSchedules::<T>::new(
+                        StateTransitionSteps::EnterSchedules,
+                    )),
             )
             .add_systems(
                 should_run_transition::<T, OnExit<T>>
                     .pipe(run_exit::<T>)
-                    .in_set(StateTransitionSteps::ExitSchedules),
+                    .in_set(StateTransitionSteps::ExitSchedules)
+                    .in_set(RunStateTransitionSchedules::<T>::new(
+                        StateTransitionSteps::ExitSchedules,
+                    )),
             )
             .add_systems(
                 should_run_transition::<T, OnTransition<T>>
                     .pipe(run_transition::<T>)
-                    .in_set(StateTransitionSteps::TransitionSchedules),
+                    .in_set(StateTransitionSteps::TransitionSchedules)
+                    .in_set(RunStateTransitionSchedules::<T>::new(
+                        StateTransitionSteps::TransitionSchedules,
+                    )),
             )
             .configure_sets(
                 ApplyStateTransition::<T>::apply()
@@ -232,9 +250,9 @@ macro_rules! impl_state_set_sealed_tuples {
 
                 schedule
                     .add_systems(system.in_set(ApplyStateTransition::<T>::apply()))
-                    .add_systems(should_run_transition::<T, OnEnter<T>>.pipe(run_enter::<T>).in_set(StateTransitionSteps::EnterSchedules))
-                    .add_systems(should_run_transition::<T, OnExit<T>>.pipe(run_exit::<T>).in_set(StateTransitionSteps::ExitSchedules))
-                    .add_systems(should_run_transition::<T, OnTransition<T>>.pipe(run_transition::<T>).in_set(StateTransitionSteps::TransitionSchedules))
+                    .add_systems(should_run_transition::<T, OnEnter<T>>.pipe(run_enter::<T>).in_set(StateTransitionSteps::EnterSchedules).in_set(RunStateTransitionSchedules::<T>::new(StateTransitionSteps::EnterSchedules)))
+                    .add_systems(should_run_transition::<T, OnExit<T>>.pipe(run_exit::<T>).in_set(StateTransitionSteps::ExitSchedules).in_set(RunStateTransitionSchedules::<T>::new(StateTransitionSteps::ExitSchedules)))
+                    .add_systems(should_run_transition::<T, OnTransition<T>>.pipe(run_transition::<T>).in_set(StateTransitionSteps::TransitionSchedules).in_set(RunStateTransitionSchedules::<T>::new(StateTransitionSteps::TransitionSchedules)))
                     .configure_sets(
                         ApplyStateTransition::<T>::apply()
                         .in_set(StateTransitionSteps::DependentTransitions)
@@ -271,9 +289,9 @@ macro_rules! impl_state_set_sealed_tuples {
                 schedule
                     .add_systems(system.in_set(ApplyStateTransition::<T>::apply()))
                     .add_systems(apply_state_transition::<T>.in_set(StateTransitionSteps::ManualTransitions))
-                    .add_systems(should_run_transition::<T, OnEnter<T>>.pipe(run_enter::<T>).in_set(StateTransitionSteps::EnterSchedules))
-                    .add_systems(should_run_transition::<T, OnExit<T>>.pipe(run_exit::<T>).in_set(StateTransitionSteps::ExitSchedules))
-                    .add_systems(should_run_transition::<T, OnTransition<T>>.pipe(run_transition::<T>).in_set(StateTransitionSteps::TransitionSchedules))
+                    .add_systems(should_run_transition::<T, OnEnter<T>>.pipe(run_enter::<T>).in_set(StateTransitionSteps::EnterSchedules).in_set(RunStateTransitionSchedules::<T>::new(StateTransitionSteps::EnterSchedules)))
+                    .add_systems(should_run_transition::<T, OnExit<T>>.pipe(run_exit::<T>).in_set(StateTransitionSteps::ExitSchedules).in_set(RunStateTransitionSchedules::<T>::new(StateTransitionSteps::ExitSchedules)))
+                    .add_systems(should_run_transition::<T, OnTransition<T>>.pipe(run_transition::<T>).in_set(StateTransitionSteps::TransitionSchedules).in_set(RunStateTransitionSchedules::<T>::new(StateTransitionSteps::TransitionSchedules)))
                     .configure_sets(
                         ApplyStateTransition::<T>::apply()
                         .in_set(StateTransitionSteps::DependentTransitions)
@@ -159,7 +159,7 @@ pub trait SubStates: States + FreelyMutableState {
     fn should_exist(sources: Self::SourceStates) -> Option<Self>;
 
     /// This function sets up systems that compute the state whenever one of the [`SourceStates`](Self::SourceStates)
-    /// change. It is called by `App::add_computed_state`, but can be called manually if `App` is not
+    /// change. It is called by `App::add_sub_state`, but can be called manually if `App` is not
     /// used.
     fn register_sub_state_systems(schedule: &mut Schedule) {
         Self::SourceStates::register_sub_state_systems_in_schedule::<Self>(schedule);
@@ -1,17 +1,23 @@
-use std::{marker::PhantomData, mem, ops::DerefMut};
+use std::{
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    mem,
+    ops::{Deref, DerefMut},
+};
 
 use bevy_ecs::{
-    event::{Event, EventReader, EventWriter},
+    change_detection::DetectChangesMut,
+    event::{Event, EventReader, EventWriter, Events},
     schedule::{
         InternedScheduleLabel, IntoSystemSetConfigs, Schedule, ScheduleLabel, Schedules, SystemSet,
     },
-    system::{Commands, In, Local, Res, ResMut},
+    system::{Commands, In, Local, Res, ResMut, Resource},
     world::World,
 };
 
 use super::{
     freely_mutable_state::FreelyMutableState,
-    resources::{NextState, State},
+    resources::{NextState, NextStateConflict, NextStateConflictPolicy, State},
     states::States,
 };
 
@@ -37,12 +43,172 @@ pub struct OnTransition<S: States> {
     pub to: S,
 }
 
+/// The label of a [`Schedule`] that runs whenever [`State<S>`]
+/// enters a state for which the predicate returns `true`.
+///
+/// This is useful for states carrying data, where [`OnEnter`] would need a schedule per value:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_state::prelude::*;
+/// # use bevy_state::state::{MatchingStateSchedule, OnEnterMatching};
+/// #[derive(States, Clone, Copy, Default, Eq, PartialEq, Hash, Debug)]
+/// struct Level(u32);
+///
+/// # let mut world = World::new();
+/// # world.init_resource::<Schedules>();
+/// let boss_levels = OnEnterMatching::new("boss_levels", |level: &Level| level.0 >= 10);
+/// boss_levels.register(&mut world);
+/// world.resource_mut::<Schedules>().add_systems(boss_levels, spawn_boss);
+/// # fn spawn_boss() {}
+/// ```
+///
+/// Labels are told apart by their key, so the key must be unique among the matching schedules of
+/// `S`. The schedule only runs once it is registered in the [`MatchingStateSchedules`] of `S`, as
+/// `App::add_matching_state_systems` does.
+///
+/// Systems added to this schedule are always ran *after* [`OnEnter`], and the matching
+/// schedules run in the order they were registered.
+#[derive(ScheduleLabel, Debug)]
+pub struct OnEnterMatching<S: States> {
+    /// The key naming the predicate.
+    pub key: &'static str,
+    /// Whether the schedule runs when entering a state.
+    pub predicate: fn(&S) -> bool,
+}
+
+impl<S: States> OnEnterMatching<S> {
+    /// Creates the label of the schedule running when entering a state for which `predicate`
+    /// returns `true`.
+    pub fn new(key: &'static str, predicate: fn(&S) -> bool) -> Self {
+        Self { key, predicate }
+    }
+}
+
+// Not derived, as the states themselves don't need to be `Copy`.
+impl<S: States> Clone for OnEnterMatching<S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S: States> Copy for OnEnterMatching<S> {}
+
+impl<S: States> PartialEq for OnEnterMatching<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<S: States> Eq for OnEnterMatching<S> {}
+
+impl<S: States> Hash for OnEnterMatching<S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key.hash(state);
+    }
+}
+
+/// The label of a [`Schedule`] that runs whenever [`State<S>`]
+/// exits a state for which the predicate returns `true`.
+///
+/// Like [`OnEnterMatching`], labels are told apart by their key, and the schedule only runs once
+/// it is registered in the [`MatchingStateSchedules`] of `S`.
+///
+/// Systems added to this schedule are always ran *after* [`OnExit`], and the matching schedules
+/// run in the order they were registered.
+#[derive(ScheduleLabel, Debug)]
+pub struct OnExitMatching<S: States> {
+    /// The key naming the predicate.
+    pub key: &'static str,
+    /// Whether the schedule runs when exiting a state.
+    pub predicate: fn(&S) -> bool,
+}
+
+impl<S: States> OnExitMatching<S> {
+    /// Creates the label of the schedule running when exiting a state for which `predicate`
+    /// returns `true`.
+    pub fn new(key: &'static str, predicate: fn(&S) -> bool) -> Self {
+        Self { key, predicate }
+    }
+}
+
+// Not derived, as the states themselves don't need to be `Copy`.
+impl<S: States> Clone for OnExitMatching<S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S: States> Copy for OnExitMatching<S> {}
+
+impl<S: States> PartialEq for OnExitMatching<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<S: States> Eq for OnExitMatching<S> {}
+
+impl<S: States> Hash for OnExitMatching<S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key.hash(state);
+    }
+}
+
+/// Resource listing the [`OnEnterMatching`] and [`OnExitMatching`] schedules of `S`, so the
+/// transitions of `S` only check their predicates instead of looking through every schedule.
+#[derive(Resource)]
+pub struct MatchingStateSchedules<S: States> {
+    on_enter: Vec<OnEnterMatching<S>>,
+    on_exit: Vec<OnExitMatching<S>>,
+}
+
+impl<S: States> Default for MatchingStateSchedules<S> {
+    fn default() -> Self {
+        Self {
+            on_enter: Vec::new(),
+            on_exit: Vec::new(),
+        }
+    }
+}
+
+/// A schedule label running on the transitions of a state matching a predicate, see
+/// [`OnEnterMatching`] and [`OnExitMatching`].
+pub trait MatchingStateSchedule: ScheduleLabel + Copy {
+    /// Registers the schedule in the [`MatchingStateSchedules`] of its state, so the transitions
+    /// run it. Registering it again has no effect.
+    fn register(self, world: &mut World);
+}
+
+impl<S: States> MatchingStateSchedule for OnEnterMatching<S> {
+    fn register(self, world: &mut World) {
+        let mut schedules = world.get_resource_or_insert_with(MatchingStateSchedules::<S>::default);
+        if !schedules.on_enter.contains(&self) {
+            schedules.on_enter.push(self);
+        }
+    }
+}
+
+impl<S: States> MatchingStateSchedule for OnExitMatching<S> {
+    fn register(self, world: &mut World) {
+        let mut schedules = world.get_resource_or_insert_with(MatchingStateSchedules::<S>::default);
+        if !schedules.on_exit.contains(&self) {
+            schedules.on_exit.push(self);
+        }
+    }
+}
+
 /// Runs [state transitions](States).
 #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct StateTransition;
 
 /// Event sent when any state transition of `S` happens.
 ///
+/// A single event carries both the exited and the entered state, so consumers never need to pair
+/// up events or read [`State<S>`] mid-transition to learn where the state came from.
+/// `before` is `None` when the state is created (e.g. a sub-state starting to exist), and `after`
+/// is `None` when it is removed. Both are equal when the state is re-entered.
+///
 /// If you know exactly what state you want to respond to ahead of time, consider [`OnEnter`], [`OnTransition`], or [`OnExit`]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Event)]
 pub struct StateTransitionEvent<S: States> {
@@ -52,15 +218,90 @@ pub struct StateTransitionEvent<S: States> {
     pub after: Option<S>,
 }
 
-/// Applies manual state transitions using [`NextState<S>`].
+impl<S: States> StateTransitionEvent<S> {
+    /// Returns the state that was exited, if the state existed before the transition.
+    pub fn exited(&self) -> Option<&S> {
+        self.before.as_ref()
+    }
+
+    /// Returns the state that was entered, if the state exists after the transition.
+    pub fn entered(&self) -> Option<&S> {
+        self.after.as_ref()
+    }
+
+    /// Returns `true` if the state was exited and entered again with the same value.
+    pub fn is_reentry(&self) -> bool {
+        self.before.is_some() && self.before == self.after
+    }
+}
+
+/// Resource holding the transition of `S` being applied, available to the systems of the
+/// [`OnExit`], [`OnTransition`] and [`OnEnter`] schedules of `S` (and their matching variants)
+/// while they run.
 ///
-/// These system sets are run sequentially, in the order of the enum variants.
+/// This lets enter and exit logic branch on where the state came from or goes to, without
+/// reading the [`StateTransitionEvent`]s. It derefs to the [`StateTransitionEvent`] being
+/// applied. The resource is removed once the schedules have run, so systems outside of them
+/// should not access it.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_state::prelude::*;
+/// # use bevy_state::state::StateTransitionContext;
+/// # #[derive(States, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+/// # enum GameState { #[default] MainMenu, Paused, InGame }
+/// fn enter_game(context: Res<StateTransitionContext<GameState>>) {
+///     if context.exited() == Some(&GameState::Paused) {
+///         // Resume the game instead of starting a new one.
+///     }
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct StateTransitionContext<S: States>(StateTransitionEvent<S>);
+
+impl<S: States> Deref for StateTransitionContext<S> {
+    type Target = StateTransitionEvent<S>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Runs `run` with the [`StateTransitionContext<S>`] of `transition` inserted in `world`.
+fn with_transition_context<S: States>(
+    world: &mut World,
+    transition: StateTransitionEvent<S>,
+    run: impl FnOnce(&mut World),
+) {
+    // Keep the context of an outer transition, e.g. if a schedule applies transitions itself.
+    let outer = world.remove_resource::<StateTransitionContext<S>>();
+    world.insert_resource(StateTransitionContext(transition));
+    run(world);
+    match outer {
+        Some(outer) => world.insert_resource(outer),
+        None => {
+            world.remove_resource::<StateTransitionContext<S>>();
+        }
+    }
+}
+
+/// The steps of the [`StateTransition`] schedule.
+///
+/// These system sets are run sequentially, in the order of the enum variants. Within a step, the
+/// systems of different state types only run in a deterministic order if they depend on each
+/// other or have been ordered with [`order_state_transitions`].
 #[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
-pub(crate) enum StateTransitionSteps {
+pub enum StateTransitionSteps {
+    /// Applies manual state transitions using [`NextState<S>`].
     ManualTransitions,
+    /// Updates the [`ComputedStates`](crate::state::ComputedStates) and
+    /// [`SubStates`](crate::state::SubStates) whose sources changed.
     DependentTransitions,
+    /// Runs the [`OnExit`] schedules.
     ExitSchedules,
+    /// Runs the [`OnTransition`] schedules.
     TransitionSchedules,
+    /// Runs the [`OnEnter`] schedules.
     EnterSchedules,
 }
 
@@ -74,6 +315,55 @@ impl<S: States> ApplyStateTransition<S> {
     }
 }
 
+/// The system set running the [`OnExit`], [`OnTransition`] or [`OnEnter`] schedules of `S`,
+/// within the matching [`StateTransitionSteps`] set.
+#[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RunStateTransitionSchedules<S: States> {
+    step: StateTransitionSteps,
+    _marker: PhantomData<S>,
+}
+
+impl<S: States> RunStateTransitionSchedules<S> {
+    /// Returns the set running the schedules of `S` in `step`, which must be one of
+    /// [`ExitSchedules`](StateTransitionSteps::ExitSchedules),
+    /// [`TransitionSchedules`](StateTransitionSteps::TransitionSchedules) or
+    /// [`EnterSchedules`](StateTransitionSteps::EnterSchedules).
+    pub fn new(step: StateTransitionSteps) -> Self {
+        Self {
+            step,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Orders the transitions of the `Before` and `After` state types in `schedule`, which should be
+/// the [`StateTransition`] schedule.
+///
+/// When both states change during the same run of the schedule, `Before` is updated first, and
+/// in each of the [`StateTransitionSteps`] its schedules run before the ones of `After`. This
+/// makes the order of independent state types deterministic, e.g. so that an `AssetState` is
+/// always applied before a `GameState` reacting to it.
+///
+/// States depending on each other through [`ComputedStates`](crate::state::ComputedStates) or
+/// [`SubStates`](crate::state::SubStates) are already ordered. Since those are updated after the
+/// manual transitions, ordering one of them before a state changed through [`NextState<S>`], or
+/// before one of its sources, makes the schedule fail to build.
+pub fn order_state_transitions<Before: States, After: States>(schedule: &mut Schedule) {
+    schedule.configure_sets(
+        ApplyStateTransition::<Before>::apply().before(ApplyStateTransition::<After>::apply()),
+    );
+    for step in [
+        StateTransitionSteps::ExitSchedules,
+        StateTransitionSteps::TransitionSchedules,
+        StateTransitionSteps::EnterSchedules,
+    ] {
+        schedule.configure_sets(
+            RunStateTransitionSchedules::<Before>::new(step.clone())
+                .before(RunStateTransitionSchedules::<After>::new(step)),
+        );
+    }
+}
+
 /// This function actually applies a state change, and registers the required
 /// schedules for downstream computed states and transition schedules.
 ///
@@ -173,12 +463,25 @@ pub fn apply_state_transition<S: FreelyMutableState>(
     commands: Commands,
     current_state: Option<ResMut<State<S>>>,
     next_state: Option<ResMut<NextState<S>>>,
+    conflict_policy: Option<Res<NextStateConflictPolicy<S>>>,
+    conflict_events: Option<ResMut<Events<NextStateConflict<S>>>>,
 ) {
     // We want to check if the State and NextState resources exist
     let Some(mut next_state_resource) = next_state else {
         return;
     };
 
+    let writes = next_state_resource.take_writes();
+    if let Some(conflict_policy) = conflict_policy {
+        let (resolved, conflicts) = conflict_policy.resolve(writes);
+        if let Some(resolved) = resolved {
+            *next_state_resource.bypass_change_detection() = NextState::Pending(resolved);
+        }
+        if let Some(mut conflict_events) = conflict_events {
+            conflict_events.send_batch(conflicts);
+        }
+    }
+
     match next_state_resource.as_ref() {
         NextState::Pending(new_state) => {
             if let Some(current_state) = current_state {
@@ -233,11 +536,20 @@ pub(crate) fn run_enter<S: States>(
         return;
     };
 
-    let Some(after) = transition.after else {
+    let Some(after) = transition.after.clone() else {
         return;
     };
 
-    let _ = world.try_run_schedule(OnEnter(after));
+    with_transition_context(world, transition, |world| {
+        let _ = world.try_run_schedule(OnEnter(after.clone()));
+        let matching = world
+            .get_resource::<MatchingStateSchedules<S>>()
+            .map(|schedules| matching(&schedules.on_enter, |label| (label.predicate)(&after)))
+            .unwrap_or_default();
+        for label in matching {
+            let _ = world.try_run_schedule(label);
+        }
+    });
 }
 
 pub(crate) fn run_exit<S: States>(
@@ -248,11 +560,29 @@ pub(crate) fn run_exit<S: States>(
         return;
     };
 
-    let Some(before) = transition.before else {
+    let Some(before) = transition.before.clone() else {
         return;
     };
 
-    let _ = world.try_run_schedule(OnExit(before));
+    with_transition_context(world, transition, |world| {
+        let _ = world.try_run_schedule(OnExit(before.clone()));
+        let matching = world
+            .get_resource::<MatchingStateSchedules<S>>()
+            .map(|schedules| matching(&schedules.on_exit, |label| (label.predicate)(&before)))
+            .unwrap_or_default();
+        for label in matching {
+            let _ = world.try_run_schedule(label);
+        }
+    });
+}
+
+/// Returns the labels for which `predicate` returns `true`.
+fn matching<L: Copy>(labels: &[L], predicate: impl Fn(&L) -> bool) -> Vec<L> {
+    labels
+        .iter()
+        .filter(|label| predicate(label))
+        .copied()
+        .collect()
 }
 
 pub(crate) fn run_transition<S: States>(
@@ -265,12 +595,14 @@ pub(crate) fn run_transition<S: States>(
     let Some(transition) = transition else {
         return;
     };
-    let Some(from) = transition.before else {
+    let Some(from) = transition.before.clone() else {
         return;
     };
-    let Some(to) = transition.after else {
+    let Some(to) = transition.after.clone() else {
         return;
     };
 
-    let _ = world.try_run_schedule(OnTransition { from, to });
+    with_transition_context(world, transition, |world| {
+        let _ = world.try_run_schedule(OnTransition { from, to });
+    });
 }
@@ -0,0 +1,131 @@
+use bevy_ecs::{
+    event::{EventRegistry, Events},
+    schedule::Schedules,
+    world::World,
+};
+use bevy_utils::tracing::warn;
+
+use crate::state::{
+    setup_state_transitions_in_world, FreelyMutableState, NextState, NextStateConflict,
+    NextStateConflictPolicy, State, StateTransition, StateTransitionEvent,
+};
+
+/// Extension trait for [`World`] adding and removing state machines at runtime.
+///
+/// This lets optional subsystems (e.g. an editor mode or debug tooling) bring their states with
+/// them when they are enabled, instead of registering them when the app is built.
+pub trait WorldStatesExt {
+    /// Adds the state `S` with the initial value `state`, if it doesn't exist yet.
+    ///
+    /// Its [`OnEnter`](crate::state::OnEnter) schedule runs the next time the
+    /// [`StateTransition`] schedule runs.
+    ///
+    /// **Note:** The [`StateTransition`] schedule must not be running, as the systems applying
+    /// the transitions of `S` are added to it the first time `S` is added.
+    fn insert_state<S: FreelyMutableState>(&mut self, state: S);
+
+    /// Removes the state `S` and any pending transition of it.
+    ///
+    /// Its [`OnExit`](crate::state::OnExit) schedule runs the next time the [`StateTransition`]
+    /// schedule runs, and the computed and sub-states depending on it are removed as well.
+    fn remove_state<S: FreelyMutableState>(&mut self);
+}
+
+impl WorldStatesExt for World {
+    fn insert_state<S: FreelyMutableState>(&mut self, state: S) {
+        if self.contains_resource::<State<S>>() {
+            return;
+        }
+
+        // The transition systems are kept when the state is removed, so they are only added the
+        // first time the state is inserted.
+        if !self.contains_resource::<Events<StateTransitionEvent<S>>>() {
+            setup_state_transitions_in_world(self, None);
+            let mut schedules = self.resource_mut::<Schedules>();
+            let Some(schedule) = schedules.get_mut(StateTransition) else {
+                warn!(
+                    "Unable to insert the state {}: the StateTransition schedule is running",
+                    std::any::type_name::<S>()
+                );
+                return;
+            };
+            S::register_state(schedule);
+            EventRegistry::register_event::<StateTransitionEvent<S>>(self);
+            EventRegistry::register_event::<NextStateConflict<S>>(self);
+        }
+
+        self.insert_resource(State::new(state.clone()));
+        self.init_resource::<NextState<S>>();
+        self.init_resource::<NextStateConflictPolicy<S>>();
+        self.send_event(StateTransitionEvent {
+            before: None,
+            after: Some(state),
+        });
+    }
+
+    fn remove_state<S: FreelyMutableState>(&mut self) {
+        self.remove_resource::<NextState<S>>();
+        let Some(state) = self.remove_resource::<State<S>>() else {
+            return;
+        };
+        self.send_event(StateTransitionEvent {
+            before: Some(state.get().clone()),
+            after: None,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::prelude::*;
+    use bevy_state_macros::States;
+
+    use super::*;
+    use crate as bevy_state;
+    use crate::state::{OnEnter, OnExit};
+
+    #[derive(States, PartialEq, Eq, Debug, Default, Hash, Clone)]
+    enum EditorState {
+        #[default]
+        Idle,
+        Editing,
+    }
+
+    #[derive(Resource, Default)]
+    struct TransitionCounter {
+        enter: usize,
+        exit: usize,
+    }
+
+    #[test]
+    fn insert_and_remove_state_at_runtime() {
+        let mut world = World::new();
+        world.init_resource::<TransitionCounter>();
+        setup_state_transitions_in_world(&mut world, None);
+        let mut schedules = world.resource_mut::<Schedules>();
+        schedules.add_systems(
+            OnEnter(EditorState::Editing),
+            |mut counter: ResMut<TransitionCounter>| counter.enter += 1,
+        );
+        schedules.add_systems(
+            OnExit(EditorState::Editing),
+            |mut counter: ResMut<TransitionCounter>| counter.exit += 1,
+        );
+
+        for round in 1..=2 {
+            world.insert_state(EditorState::Editing);
+            world.run_schedule(StateTransition);
+            assert_eq!(world.resource::<TransitionCounter>().enter, round);
+            assert_eq!(
+                world.resource::<State<EditorState>>().get(),
+                &EditorState::Editing
+            );
+
+            world.remove_state::<EditorState>();
+            world.run_schedule(StateTransition);
+            assert_eq!(world.resource::<TransitionCounter>().exit, round);
+            assert!(!world.contains_resource::<State<EditorState>>());
+            assert!(!world.contains_resource::<NextState<EditorState>>());
+        }
+    }
+}
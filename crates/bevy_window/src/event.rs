@@ -10,7 +10,7 @@ use smol_str::SmolStr;
 #[cfg(feature = "serialize")]
 use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
 
-use crate::WindowTheme;
+use crate::{MonitorInfo, WindowTheme};
 
 /// A window event that is sent whenever a window's logical size has changed.
 #[derive(Event, Debug, Clone, PartialEq, Reflect)]
@@ -40,6 +40,23 @@ pub struct WindowResized {
 )]
 pub struct RequestRedraw;
 
+/// An event that is sent when the OS asks a window to redraw its content, e.g. because it was
+/// uncovered or resized, or in response to a [`RequestRedraw`].
+///
+/// Apps presenting frames manually or only on demand can use it to render when a window actually
+/// needs it.
+#[derive(Event, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct WindowRedrawRequested {
+    /// Window that should be redrawn.
+    pub window: Entity,
+}
+
 /// An event that is sent whenever a new window is created.
 ///
 /// To create a new window, spawn an entity with a [`crate::Window`] on it.
@@ -79,6 +96,8 @@ pub struct WindowCloseRequested {
 
 /// An event that is sent whenever a window is closed. This will be sent when
 /// the window entity loses its [`Window`](crate::window::Window) component or is despawned.
+///
+/// This is the last step of the lifecycle described on [`ClosingWindow`](crate::ClosingWindow).
 #[derive(Event, Debug, Clone, PartialEq, Eq, Reflect)]
 #[reflect(Debug, PartialEq)]
 #[cfg_attr(
@@ -96,6 +115,8 @@ pub struct WindowClosed {
 
 /// An event that is sent whenever a window is closing. This will be sent when
 /// after a [`WindowCloseRequested`] event is received and the window is in the process of closing.
+///
+/// The window stays alive for one more update, see [`ClosingWindow`](crate::ClosingWindow).
 #[derive(Event, Debug, Clone, PartialEq, Eq, Reflect)]
 #[reflect(Debug, PartialEq)]
 #[cfg_attr(
@@ -110,6 +131,9 @@ pub struct WindowClosing {
 
 /// An event that is sent whenever a window is destroyed by the underlying window system.
 ///
+/// For windows closed through [`ClosingWindow`](crate::ClosingWindow), the entity is only
+/// despawned at the end of the update after this event is sent, so it can still be queried.
+///
 /// Note that if your application only has a single window, this event may be your last chance to
 /// persist state before the application terminates.
 #[derive(Event, Debug, Clone, PartialEq, Eq, Reflect)]
@@ -122,8 +146,43 @@ pub struct WindowClosing {
 pub struct WindowDestroyed {
     /// Window that has been destroyed.
     ///
-    /// Note that this entity probably no longer exists
-    /// by the time this event is received.
+    /// Note that this entity no longer exists by the time this event is received if the window
+    /// was despawned directly.
+    pub window: Entity,
+}
+
+/// An event that is sent whenever the native surface of a window is destroyed while the window
+/// stays open, i.e. when its [`RawHandleWrapper`](crate::RawHandleWrapper) is removed.
+///
+/// This happens on platforms destroying the surfaces of suspended apps, e.g. Android after
+/// [`ApplicationLifetime::Suspended`]. Renderers and video players should release the GPU
+/// resources tied to the surface, and wait for a [`SurfaceRecreated`] event to reacquire them.
+///
+/// Closing windows send [`WindowDestroyed`] instead.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct SurfaceDestroyed {
+    /// Window whose surface has been destroyed.
+    pub window: Entity,
+}
+
+/// An event that is sent whenever the native surface of a window is created again after a
+/// [`SurfaceDestroyed`] event, i.e. when its [`RawHandleWrapper`](crate::RawHandleWrapper) is
+/// inserted back.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct SurfaceRecreated {
+    /// Window whose surface has been recreated.
     pub window: Entity,
 }
 
@@ -185,6 +244,75 @@ pub struct CursorLeft {
     pub window: Entity,
 }
 
+/// An event that is sent when the windowing backend failed to apply a change of a [`Window`]
+/// component to the actual window, e.g. because the platform refused it.
+///
+/// This lets apps surface window problems to their users, instead of relying on the logs. Only
+/// the fields that changed are applied, so the operation is not retried until the field changes
+/// again, except for cursor grabs: some platforms, notably Wayland and X11, refuse to grab the
+/// cursor until the window is mapped and focused, so the backend retries a few times while the
+/// window has focus before sending this event.
+///
+/// [`Window`]: crate::Window
+#[derive(Event, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct WindowError {
+    /// Window the operation was applied to.
+    pub window: Entity,
+    /// The operation that failed.
+    pub operation: WindowOperation,
+    /// The error reported by the platform.
+    pub error: String,
+}
+
+/// An operation applying a change of a [`Window`](crate::Window) component, see
+/// [`WindowError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Debug, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum WindowOperation {
+    /// Applying [`Window::mode`](crate::Window::mode).
+    SetFullscreen,
+    /// Applying [`Window::resolution`](crate::Window::resolution), when the platform resized the
+    /// window to another size than requested.
+    RequestInnerSize,
+    /// Applying [`Cursor::grab_mode`](crate::Cursor::grab_mode).
+    SetCursorGrab,
+    /// Applying the cursor position set with
+    /// [`Window::set_cursor_position`](crate::Window::set_cursor_position).
+    SetCursorPosition,
+    /// Applying [`Cursor::hit_test`](crate::Cursor::hit_test).
+    SetCursorHitTest,
+}
+
+/// An event that is sent when the cursor moves directly from one window of the app to another.
+///
+/// It is sent after the [`CursorLeft`] and [`CursorEntered`] events of the move, so multi-window
+/// interactions (e.g. dragging an item between windows) can tell the cursor stayed within the app
+/// rather than leaving it.
+#[derive(Event, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct CursorTransferred {
+    /// Window that the cursor left.
+    pub from: Entity,
+    /// Window that the cursor entered.
+    pub to: Entity,
+}
+
 /// An event that is sent whenever a window receives a character from the OS or underlying system.
 #[deprecated(since = "0.14.0", note = "Use `KeyboardInput` instead.")]
 #[derive(Event, Debug, Clone, PartialEq, Eq, Reflect)]
@@ -262,6 +390,22 @@ pub struct WindowFocused {
     pub focused: bool,
 }
 
+/// An event reporting whether the OS granted the focus to a window, after
+/// [`Window::request_focus`](crate::Window::request_focus) was called.
+#[derive(Event, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct WindowFocusRequestCompleted {
+    /// Window that requested the focus.
+    pub window: Entity,
+    /// Whether the window got the focus.
+    pub granted: bool,
+}
+
 /// The window has been occluded (completely hidden from view).
 ///
 /// This is different to window visibility as it depends on
@@ -347,6 +491,26 @@ pub enum FileDragAndDrop {
     },
 }
 
+/// An event that is sent when a window's logical size doesn't fit its
+/// [`resize_constraints`](crate::Window::resize_constraints), either because a size outside of
+/// them was requested or because the constraints changed. The OS clamps the window to the
+/// constraints, which will be reported by a [`WindowResized`] event.
+#[derive(Event, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct WindowSizeConstrained {
+    /// Window whose size was constrained.
+    pub window: Entity,
+    /// The logical size that doesn't fit the constraints.
+    pub requested: Vec2,
+    /// The logical size once clamped to the constraints.
+    pub constrained: Vec2,
+}
+
 /// An event that is sent when a window is repositioned in physical pixels.
 #[derive(Event, Debug, Clone, PartialEq, Eq, Reflect)]
 #[reflect(Debug, PartialEq)]
@@ -362,6 +526,59 @@ pub struct WindowMoved {
     pub position: IVec2,
 }
 
+/// An event sent when a move changes the monitor a window is on, or whether it straddles several
+/// monitors.
+///
+/// It follows the [`WindowMoved`] events, and is computed from the [`Monitors`](crate::Monitors)
+/// resource so placement-sensitive code doesn't need to compare the position of the window with
+/// the monitors itself. It is also sent for the first move of each window.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct WindowMonitorChanged {
+    /// Window that moved.
+    pub window: Entity,
+    /// The index in [`Monitors`](crate::Monitors) of the monitor showing the largest part of the
+    /// window, or `None` if the window is off-screen or the monitors aren't known.
+    pub monitor: Option<usize>,
+    /// Whether the window overlaps more than one monitor.
+    pub straddles_monitors: bool,
+}
+
+/// An event sent when a monitor is connected, once it is listed in the
+/// [`Monitors`](crate::Monitors) resource.
+///
+/// It is also sent for each monitor found when the backend first lists them.
+#[derive(Event, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct MonitorConnected {
+    /// The monitor that was connected.
+    pub monitor: MonitorInfo,
+}
+
+/// An event sent when a monitor is disconnected, once it is removed from the
+/// [`Monitors`](crate::Monitors) resource.
+#[derive(Event, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct MonitorDisconnected {
+    /// The monitor that was disconnected.
+    pub monitor: MonitorInfo,
+}
+
 /// An event sent when the system theme changes for a window.
 ///
 /// This event is only sent when the window is relying on the system theme to control its appearance.
@@ -398,3 +615,37 @@ pub enum ApplicationLifetime {
     /// The application was resumed.
     Resumed,
 }
+
+/// An event sent when the user asks to move to another window of the application, e.g. with the
+/// platform's window-cycling shortcut (`Ctrl+F6` on Windows and Linux, ``Cmd+` `` on macOS).
+///
+/// The focus is not moved automatically, so multi-window applications can implement accessible
+/// window switching in the order that makes sense to them.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct WindowCycleRequested {
+    /// Window that received the request.
+    pub window: Entity,
+    /// Which window the user wants to move to, relative to `window`.
+    pub direction: WindowCycleDirection,
+}
+
+/// The direction of a [`WindowCycleRequested`] event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Debug, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum WindowCycleDirection {
+    /// Move to the next window.
+    Next,
+    /// Move to the previous window.
+    Previous,
+}
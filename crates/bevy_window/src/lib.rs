@@ -15,6 +15,8 @@ use bevy_a11y::Focus;
 
 mod cursor;
 mod event;
+mod monitor;
+mod present_feedback;
 mod raw_handle;
 mod system;
 mod window;
@@ -23,6 +25,8 @@ pub use crate::raw_handle::*;
 
 pub use cursor::*;
 pub use event::*;
+pub use monitor::*;
+pub use present_feedback::*;
 pub use system::*;
 pub use window::*;
 
@@ -31,13 +35,14 @@ pub mod prelude {
     #[allow(deprecated)]
     #[doc(hidden)]
     pub use crate::{
-        CursorEntered, CursorIcon, CursorLeft, CursorMoved, FileDragAndDrop, Ime, MonitorSelection,
-        ReceivedCharacter, Window, WindowMoved, WindowPlugin, WindowPosition,
-        WindowResizeConstraints,
+        CursorEntered, CursorIcon, CursorLeft, CursorMoved, CursorTransferred, FileDragAndDrop,
+        Ime, MonitorSelection, ReceivedCharacter, SpawnWindowExt, Window, WindowMoved,
+        WindowPlugin, WindowPosition, WindowResizeConstraints,
     };
 }
 
 use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
 
 impl Default for WindowPlugin {
     fn default() -> Self {
@@ -70,8 +75,11 @@ pub struct WindowPlugin {
     /// surprise your users. It is recommended to leave this setting to
     /// either [`ExitCondition::OnAllClosed`] or [`ExitCondition::OnPrimaryClosed`].
     ///
-    /// [`ExitCondition::OnAllClosed`] will add [`exit_on_all_closed`] to [`Update`].
-    /// [`ExitCondition::OnPrimaryClosed`] will add [`exit_on_primary_closed`] to [`Update`].
+    /// [`ExitCondition::OnAllClosed`] will run [`exit_on_all_closed`] in [`PostUpdate`].
+    /// [`ExitCondition::OnPrimaryClosed`] will run [`exit_on_primary_closed`] in [`PostUpdate`].
+    ///
+    /// The condition is inserted as a resource, so it can be changed later on, e.g. by the
+    /// windowing backend's plugin.
     pub exit_condition: ExitCondition,
 
     /// Whether to close windows when they are requested to be closed (i.e.
@@ -93,19 +101,30 @@ impl Plugin for WindowPlugin {
             .add_event::<WindowClosed>()
             .add_event::<WindowCloseRequested>()
             .add_event::<WindowDestroyed>()
+            .add_event::<SurfaceDestroyed>()
+            .add_event::<SurfaceRecreated>()
             .add_event::<RequestRedraw>()
+            .add_event::<WindowRedrawRequested>()
             .add_event::<CursorMoved>()
             .add_event::<CursorEntered>()
             .add_event::<CursorLeft>()
+            .add_event::<CursorTransferred>()
+            .add_event::<WindowError>()
             .add_event::<ReceivedCharacter>()
             .add_event::<Ime>()
             .add_event::<WindowFocused>()
+            .add_event::<WindowFocusRequestCompleted>()
             .add_event::<WindowOccluded>()
             .add_event::<WindowScaleFactorChanged>()
             .add_event::<WindowBackendScaleFactorChanged>()
             .add_event::<FileDragAndDrop>()
             .add_event::<WindowMoved>()
+            .add_event::<WindowMonitorChanged>()
+            .add_event::<MonitorConnected>()
+            .add_event::<MonitorDisconnected>()
+            .add_event::<WindowSizeConstrained>()
             .add_event::<WindowThemeChanged>()
+            .add_event::<WindowCycleRequested>()
             .add_event::<ApplicationLifetime>();
 
         if let Some(primary_window) = &self.primary_window {
@@ -119,15 +138,19 @@ impl Plugin for WindowPlugin {
             }
         }
 
-        match self.exit_condition {
-            ExitCondition::OnPrimaryClosed => {
-                app.add_systems(PostUpdate, exit_on_primary_closed);
-            }
-            ExitCondition::OnAllClosed => {
-                app.add_systems(PostUpdate, exit_on_all_closed);
-            }
-            ExitCondition::DontExit => {}
-        }
+        app.init_resource::<PresentFeedback>()
+            .init_resource::<Monitors>()
+            .insert_resource(self.exit_condition.clone())
+            .add_systems(PreUpdate, report_window_monitors)
+            .add_systems(
+                PostUpdate,
+                (
+                    exit_on_primary_closed.run_if(resource_equals(ExitCondition::OnPrimaryClosed)),
+                    exit_on_all_closed.run_if(resource_equals(ExitCondition::OnAllClosed)),
+                ),
+            );
+
+        app.add_systems(Last, despawn_unmanaged_windows);
 
         if self.close_when_requested {
             // Need to run before `exit_on_*` systems
@@ -138,39 +161,56 @@ impl Plugin for WindowPlugin {
         #[allow(deprecated)]
         app.register_type::<WindowResized>()
             .register_type::<RequestRedraw>()
+            .register_type::<WindowRedrawRequested>()
             .register_type::<WindowCreated>()
             .register_type::<WindowCloseRequested>()
             .register_type::<WindowClosing>()
             .register_type::<WindowClosed>()
+            .register_type::<SurfaceDestroyed>()
+            .register_type::<SurfaceRecreated>()
             .register_type::<CursorMoved>()
             .register_type::<CursorEntered>()
             .register_type::<CursorLeft>()
+            .register_type::<CursorTransferred>()
+            .register_type::<WindowError>()
             .register_type::<ReceivedCharacter>()
             .register_type::<WindowFocused>()
+            .register_type::<WindowFocusRequestCompleted>()
             .register_type::<WindowOccluded>()
             .register_type::<WindowScaleFactorChanged>()
             .register_type::<WindowBackendScaleFactorChanged>()
             .register_type::<FileDragAndDrop>()
             .register_type::<WindowMoved>()
+            .register_type::<WindowMonitorChanged>()
+            .register_type::<MonitorConnected>()
+            .register_type::<MonitorDisconnected>()
+            .register_type::<WindowSizeConstrained>()
             .register_type::<WindowThemeChanged>()
+            .register_type::<WindowCycleRequested>()
             .register_type::<ApplicationLifetime>();
 
         // Register window descriptor and related types
         app.register_type::<Window>()
-            .register_type::<PrimaryWindow>();
+            .register_type::<PrimaryWindow>()
+            .register_type::<SplashWindow>()
+            .register_type::<SecondaryWindow>()
+            .register_type::<Monitors>();
     }
 }
 
 /// Defines the specific conditions the application should exit on
-#[derive(Clone)]
+///
+/// This is also a resource, inserted by the [`WindowPlugin`], that selects which `exit_on_*`
+/// system runs.
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
 pub enum ExitCondition {
     /// Close application when the primary window is closed
     ///
-    /// The plugin will add [`exit_on_primary_closed`] to [`Update`].
+    /// [`exit_on_primary_closed`] will run in [`PostUpdate`].
     OnPrimaryClosed,
     /// Close application when all windows are closed
     ///
-    /// The plugin will add [`exit_on_all_closed`] to [`Update`].
+    /// [`exit_on_all_closed`] will run in [`PostUpdate`].
     OnAllClosed,
     /// Keep application running headless even after closing all windows
     ///
@@ -0,0 +1,409 @@
+use bevy_ecs::{
+    entity::EntityHashMap,
+    event::{EventReader, EventWriter},
+    reflect::ReflectResource,
+    system::{Local, Query, Res, Resource, SystemParam},
+};
+use bevy_math::{IRect, IVec2, UVec2};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+
+#[cfg(feature = "serialize")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+use crate::{
+    MonitorInfo, MonitorSelection, Window, WindowMonitorChanged, WindowMoved, WindowPosition,
+};
+
+/// Resource listing the monitors connected to the system, as reported by the windowing backend.
+///
+/// The monitors are listed in the order of the backend, so the index of a monitor can be used
+/// with [`MonitorSelection::Index`](crate::MonitorSelection::Index), e.g. to offer a
+/// "fullscreen on monitor 2" setting. The backend keeps the list up to date, sending a
+/// [`MonitorConnected`](crate::MonitorConnected) or
+/// [`MonitorDisconnected`](crate::MonitorDisconnected) event when it changes.
+#[derive(Resource, Debug, Default, Clone, PartialEq, Reflect)]
+#[reflect(Resource, Debug, Default, PartialEq)]
+pub struct Monitors {
+    /// The monitors, in the order of the backend.
+    pub monitors: Vec<Monitor>,
+}
+
+impl Monitors {
+    /// Returns an iterator over the monitors, in the order of the backend.
+    pub fn iter(&self) -> impl Iterator<Item = &Monitor> {
+        self.monitors.iter()
+    }
+
+    /// Returns the monitor at `index`, as selected by
+    /// [`MonitorSelection::Index`](crate::MonitorSelection::Index).
+    pub fn get(&self, index: usize) -> Option<&Monitor> {
+        self.monitors.get(index)
+    }
+
+    /// Returns the primary monitor, if the backend reports one.
+    pub fn primary(&self) -> Option<&Monitor> {
+        self.monitors.iter().find(|monitor| monitor.primary)
+    }
+
+    /// Returns the number of monitors.
+    pub fn len(&self) -> usize {
+        self.monitors.len()
+    }
+
+    /// Returns `true` if no monitor is known, e.g. before the backend listed them.
+    pub fn is_empty(&self) -> bool {
+        self.monitors.is_empty()
+    }
+
+    /// Returns the monitor selected by `selection` for `window`, see [`MonitorSelection::select`].
+    pub fn select(&self, selection: MonitorSelection, window: &Window) -> Option<&Monitor> {
+        let monitors = self.iter().map(|monitor| (&monitor.info, monitor.primary));
+        selection
+            .select(window, monitors)
+            .and_then(|index| self.get(index))
+    }
+}
+
+impl MonitorSelection {
+    /// Returns the index of the monitor selected for `window` among `monitors`, listed with whether
+    /// they are the primary monitor.
+    ///
+    /// [`MonitorSelection::Current`] selects the monitor showing most of the window if its
+    /// position is known, like the windowing backend does, and the primary monitor otherwise.
+    /// The backend applies [`WindowMode::ExclusiveFullscreen`](crate::WindowMode::ExclusiveFullscreen)
+    /// on the monitor selected this way.
+    pub fn select<'a>(
+        self,
+        window: &Window,
+        monitors: impl IntoIterator<Item = (&'a MonitorInfo, bool)>,
+    ) -> Option<usize> {
+        let monitors = monitors.into_iter().collect::<Vec<_>>();
+        let primary = || monitors.iter().position(|&(_, primary)| primary);
+        match self {
+            MonitorSelection::Current => {
+                let current = match window.position {
+                    WindowPosition::At(position) => {
+                        let bounds = window_bounds(position, window.physical_size());
+                        monitors
+                            .iter()
+                            .enumerate()
+                            .map(|(index, (info, _))| (index, overlap_area(info, bounds)))
+                            .filter(|&(_, area)| area > 0)
+                            .max_by_key(|&(_, area)| area)
+                            .map(|(index, _)| index)
+                    }
+                    _ => None,
+                };
+                current.or_else(primary)
+            }
+            MonitorSelection::Primary => primary(),
+            MonitorSelection::Index(index) => (index < monitors.len()).then_some(index),
+        }
+    }
+}
+
+/// Returns the desktop area covered by a window at `position` of `size`, in physical pixels.
+fn window_bounds(position: IVec2, size: UVec2) -> IRect {
+    IRect::from_corners(position, position + size.as_ivec2())
+}
+
+/// Returns the area of `bounds` shown on the monitor, in physical pixels.
+fn overlap_area(monitor: &MonitorInfo, bounds: IRect) -> i32 {
+    let min = monitor.physical_position;
+    let max = min + monitor.physical_size.as_ivec2();
+    let overlap = bounds.intersect(IRect::from_corners(min, max));
+    if overlap.is_empty() {
+        0
+    } else {
+        overlap.width() * overlap.height()
+    }
+}
+
+/// Sends a [`WindowMonitorChanged`] event when a [`WindowMoved`] event changes the monitor a
+/// window is on, or whether it straddles several monitors.
+pub fn report_window_monitors(
+    mut moved: EventReader<WindowMoved>,
+    mut monitor_changed: EventWriter<WindowMonitorChanged>,
+    monitors: Res<Monitors>,
+    windows: Query<&Window>,
+    mut placements: Local<EntityHashMap<(Option<usize>, bool)>>,
+) {
+    placements.retain(|window, _| windows.contains(*window));
+    for &WindowMoved { window, position } in moved.read() {
+        let Ok(size) = windows.get(window).map(|window| window.physical_size()) else {
+            continue;
+        };
+        let bounds = window_bounds(position, size);
+        let overlaps = monitors
+            .iter()
+            .enumerate()
+            .map(|(index, monitor)| (index, overlap_area(&monitor.info, bounds)))
+            .filter(|&(_, area)| area > 0)
+            .collect::<Vec<_>>();
+        let straddles_monitors = overlaps.len() > 1;
+        let monitor = overlaps
+            .iter()
+            .max_by_key(|&&(_, area)| area)
+            .map(|&(index, _)| index);
+
+        if placements.insert(window, (monitor, straddles_monitors))
+            != Some((monitor, straddles_monitors))
+        {
+            monitor_changed.send(WindowMonitorChanged {
+                window,
+                monitor,
+                straddles_monitors,
+            });
+        }
+    }
+}
+
+/// A monitor listed in the [`Monitors`] resource.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+#[reflect(Debug, PartialEq)]
+pub struct Monitor {
+    /// The name, position, size, scale factor and refresh rate of the monitor.
+    pub info: MonitorInfo,
+    /// Whether this is the primary monitor of the system.
+    pub primary: bool,
+    /// The video modes supported by the monitor in exclusive fullscreen.
+    pub video_modes: Vec<VideoModeInfo>,
+}
+
+impl Monitor {
+    /// Returns `true` if `position`, in physical pixels on the desktop, is on this monitor.
+    pub fn contains(&self, position: IVec2) -> bool {
+        let min = self.info.physical_position;
+        let max = min + self.info.physical_size.as_ivec2();
+        position.cmpge(min).all() && position.cmplt(max).all()
+    }
+}
+
+/// System parameter listing the video modes a [`Window`] can use in
+/// [`WindowMode::ExclusiveFullscreen`](crate::WindowMode::ExclusiveFullscreen), backed by the
+/// [`Monitors`] resource.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_window::{MonitorSelection, PrimaryWindow, SupportedVideoModes, Window, WindowMode};
+/// fn fullscreen_on_second_monitor(
+///     mut window: Query<&mut Window, With<PrimaryWindow>>,
+///     video_modes: SupportedVideoModes,
+/// ) {
+///     let mut window = window.single_mut();
+///     let monitor = MonitorSelection::Index(1);
+///     if let Some(video_mode) = video_modes.best(monitor, &window) {
+///         window.mode = WindowMode::ExclusiveFullscreen {
+///             monitor,
+///             video_mode,
+///         };
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct SupportedVideoModes<'w> {
+    monitors: Res<'w, Monitors>,
+}
+
+impl SupportedVideoModes<'_> {
+    /// Returns the video modes of the monitor selected by `selection` for `window`, see
+    /// [`Monitors::select`].
+    pub fn get(&self, selection: MonitorSelection, window: &Window) -> &[VideoModeInfo] {
+        self.monitors
+            .select(selection, window)
+            .map_or(&[], |monitor| &monitor.video_modes)
+    }
+
+    /// Returns `true` if the monitor selected by `selection` for `window` supports `video_mode`.
+    pub fn supports(
+        &self,
+        selection: MonitorSelection,
+        window: &Window,
+        video_mode: &VideoModeInfo,
+    ) -> bool {
+        self.get(selection, window).contains(video_mode)
+    }
+
+    /// Returns the video mode of the monitor selected by `selection` for `window` with the
+    /// largest resolution, then the highest refresh rate and bit depth.
+    pub fn best(&self, selection: MonitorSelection, window: &Window) -> Option<VideoModeInfo> {
+        self.get(selection, window)
+            .iter()
+            .max_by_key(|video_mode| {
+                (
+                    video_mode.physical_size.x,
+                    video_mode.physical_size.y,
+                    video_mode.refresh_rate_millihertz,
+                    video_mode.bit_depth,
+                )
+            })
+            .copied()
+    }
+}
+
+/// A video mode supported by a monitor in exclusive fullscreen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+#[reflect(Debug, PartialEq, Hash)]
+pub struct VideoModeInfo {
+    /// The resolution of the video mode, in physical pixels.
+    pub physical_size: UVec2,
+    /// The number of bits per pixel.
+    pub bit_depth: u16,
+    /// The refresh rate of the video mode in millihertz.
+    pub refresh_rate_millihertz: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::{App, PreUpdate};
+    use bevy_ecs::{entity::Entity, event::Events, system::SystemState, world::World};
+    use bevy_math::{IVec2, UVec2};
+
+    use super::*;
+    use crate::WindowResolution;
+
+    fn monitor(x: i32) -> Monitor {
+        Monitor {
+            info: MonitorInfo {
+                name: None,
+                physical_position: IVec2::new(x, 0),
+                physical_size: UVec2::new(1920, 1080),
+                scale_factor: 1.0,
+                refresh_rate_millihertz: None,
+            },
+            primary: x == 0,
+            video_modes: Vec::new(),
+        }
+    }
+
+    fn move_window(app: &mut App, window: Entity, x: i32) -> Vec<WindowMonitorChanged> {
+        app.world_mut().send_event(WindowMoved {
+            window,
+            position: IVec2::new(x, 100),
+        });
+        app.update();
+        app.world_mut()
+            .resource_mut::<Events<WindowMonitorChanged>>()
+            .drain()
+            .collect()
+    }
+
+    #[test]
+    fn moves_report_the_monitor_showing_most_of_the_window() {
+        let mut app = App::new();
+        app.add_event::<WindowMoved>()
+            .add_event::<WindowMonitorChanged>()
+            .insert_resource(Monitors {
+                monitors: vec![monitor(0), monitor(1920)],
+            })
+            .add_systems(PreUpdate, report_window_monitors);
+        let window = app
+            .world_mut()
+            .spawn(Window {
+                resolution: WindowResolution::new(800.0, 600.0),
+                ..Default::default()
+            })
+            .id();
+
+        let changed = |monitor, straddles_monitors| {
+            vec![WindowMonitorChanged {
+                window,
+                monitor,
+                straddles_monitors,
+            }]
+        };
+        assert_eq!(move_window(&mut app, window, 100), changed(Some(0), false));
+        assert_eq!(move_window(&mut app, window, 1500), changed(Some(0), true));
+        assert_eq!(move_window(&mut app, window, 1600), changed(Some(1), true));
+        assert_eq!(move_window(&mut app, window, 1650), vec![]);
+        assert_eq!(move_window(&mut app, window, 4000), changed(None, false));
+    }
+
+    fn window_at(position: WindowPosition) -> Window {
+        Window {
+            position,
+            resolution: WindowResolution::new(800.0, 600.0),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn monitors_contain_their_top_left_corner_but_not_their_bottom_right() {
+        let monitor = monitor(1920);
+        assert!(monitor.contains(IVec2::new(1920, 0)));
+        assert!(monitor.contains(IVec2::new(3839, 1079)));
+        assert!(!monitor.contains(IVec2::new(3840, 1079)));
+        assert!(!monitor.contains(IVec2::new(1919, 500)));
+        assert!(!monitor.contains(IVec2::new(2000, -1)));
+    }
+
+    #[test]
+    fn current_selects_the_monitor_showing_most_of_the_window() {
+        let monitors = Monitors {
+            monitors: vec![monitor(0), monitor(1920)],
+        };
+        let selected = |selection, position| {
+            monitors
+                .select(selection, &window_at(position))
+                .map(|monitor| monitor.info.physical_position.x)
+        };
+        let at = |x| WindowPosition::At(IVec2::new(x, 100));
+
+        assert_eq!(selected(MonitorSelection::Current, at(100)), Some(0));
+        // The top-left corner is on the first monitor, most of the window on the second one.
+        assert_eq!(selected(MonitorSelection::Current, at(1600)), Some(1920));
+        // Off-screen and unknown positions fall back to the primary monitor.
+        assert_eq!(selected(MonitorSelection::Current, at(5000)), Some(0));
+        assert_eq!(
+            selected(MonitorSelection::Current, WindowPosition::Automatic),
+            Some(0)
+        );
+        assert_eq!(selected(MonitorSelection::Primary, at(2000)), Some(0));
+        assert_eq!(selected(MonitorSelection::Index(1), at(100)), Some(1920));
+        assert_eq!(selected(MonitorSelection::Index(2), at(100)), None);
+    }
+
+    #[test]
+    fn best_video_mode_has_the_largest_resolution_then_refresh_rate() {
+        let video_mode = |width, height, refresh_rate: u32, bit_depth| VideoModeInfo {
+            physical_size: UVec2::new(width, height),
+            bit_depth,
+            refresh_rate_millihertz: refresh_rate * 1000,
+        };
+        let mut second = monitor(1920);
+        second.video_modes = vec![
+            video_mode(2560, 1440, 60, 32),
+            video_mode(2560, 1440, 144, 24),
+            video_mode(2560, 1440, 144, 32),
+            video_mode(1920, 1080, 240, 32),
+        ];
+        let mut world = World::new();
+        world.insert_resource(Monitors {
+            monitors: vec![monitor(0), second],
+        });
+        let mut video_modes = SystemState::<SupportedVideoModes>::new(&mut world);
+        let video_modes = video_modes.get(&world);
+        let window = window_at(WindowPosition::At(IVec2::new(2000, 100)));
+
+        assert_eq!(
+            video_modes.best(MonitorSelection::Current, &window),
+            Some(video_mode(2560, 1440, 144, 32))
+        );
+        assert!(video_modes.supports(
+            MonitorSelection::Index(1),
+            &window,
+            &video_mode(1920, 1080, 240, 32)
+        ));
+        assert_eq!(video_modes.best(MonitorSelection::Primary, &window), None);
+    }
+}
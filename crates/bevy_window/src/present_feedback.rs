@@ -0,0 +1,27 @@
+use std::sync::{Arc, Mutex, PoisonError};
+
+use bevy_ecs::system::Resource;
+use bevy_utils::Instant;
+
+/// Resource through which the renderer reports when frames are actually presented.
+///
+/// The windowing backend uses the last presentation to pace its wakeups, scheduling the next
+/// update relative to it instead of to the start of the previous update, which avoids drift and
+/// jitter at display-matching update rates.
+///
+/// This is a cheap handle to shared state: clone it into the render world and call
+/// [`report_presented`](Self::report_presented) right after presenting a frame.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct PresentFeedback(Arc<Mutex<Option<Instant>>>);
+
+impl PresentFeedback {
+    /// Records that a frame was presented at `instant`.
+    pub fn report_presented(&self, instant: Instant) {
+        *self.0.lock().unwrap_or_else(PoisonError::into_inner) = Some(instant);
+    }
+
+    /// Returns when the last frame was presented, if any was reported.
+    pub fn last_presented(&self) -> Option<Instant> {
+        *self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
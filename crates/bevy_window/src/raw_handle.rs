@@ -30,6 +30,15 @@ impl<W: Send + Sync + 'static> WindowWrapper<W> {
     }
 }
 
+impl<W> Clone for WindowWrapper<W> {
+    fn clone(&self) -> Self {
+        WindowWrapper {
+            reference: self.reference.clone(),
+            ty: PhantomData,
+        }
+    }
+}
+
 impl<W: 'static> Deref for WindowWrapper<W> {
     type Target = W;
 
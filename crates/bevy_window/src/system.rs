@@ -1,4 +1,6 @@
-use crate::{ClosingWindow, PrimaryWindow, Window, WindowCloseRequested};
+use crate::{
+    ClosingWindow, ManagedWindow, PrimaryWindow, Window, WindowCloseRequested, WindowClosed,
+};
 
 use bevy_app::AppExit;
 use bevy_ecs::prelude::*;
@@ -34,6 +36,9 @@ pub fn exit_on_primary_closed(
 
 /// Close windows in response to [`WindowCloseRequested`] (e.g.  when the close button is pressed).
 ///
+/// This marks the windows with [`ClosingWindow`], starting their close lifecycle. Replace this
+/// system to ask for confirmation first, inserting [`ClosingWindow`] once the user confirmed.
+///
 /// This system is added by the [`WindowPlugin`] in the default configuration.
 /// To disable this behavior, set `close_when_requested` (on the [`WindowPlugin`]) to `false`.
 /// Ensure that you read the caveats documented on that field if doing so.
@@ -42,14 +47,76 @@ pub fn exit_on_primary_closed(
 pub fn close_when_requested(
     mut commands: Commands,
     mut closed: EventReader<WindowCloseRequested>,
-    closing: Query<Entity, With<ClosingWindow>>,
+    closing: Query<(), With<ClosingWindow>>,
 ) {
-    // This was inserted by us on the last frame so now we can despawn the window
-    for window in closing.iter() {
-        commands.entity(window).despawn();
-    }
-    // Mark the window as closing so we can despawn it on the next frame
     for event in closed.read() {
-        commands.entity(event.window).insert(ClosingWindow);
+        // The windowing backend, or `despawn_unmanaged_windows`, despawns the window.
+        if !closing.contains(event.window) {
+            commands.entity(event.window).insert(ClosingWindow);
+        }
+    }
+}
+
+/// Despawns the windows marked with [`ClosingWindow`] that no windowing backend manages, at the
+/// end of the update after the marker was inserted, and sends a [`WindowClosed`] event.
+///
+/// The windows created by a backend are marked with [`ManagedWindow`] and closed by the backend
+/// instead, see [`ClosingWindow`].
+///
+/// This system is added by the [`WindowPlugin`].
+///
+/// [`WindowPlugin`]: crate::WindowPlugin
+pub fn despawn_unmanaged_windows(
+    mut commands: Commands,
+    closing: Query<(Entity, Ref<ClosingWindow>), (With<Window>, Without<ManagedWindow>)>,
+    mut closed_events: EventWriter<WindowClosed>,
+) {
+    for (window, marker) in &closing {
+        if !marker.is_added() {
+            commands.entity(window).despawn();
+            closed_events.send(WindowClosed { window });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::{App, Last, Update};
+    use bevy_ecs::event::Events;
+
+    use super::*;
+
+    fn close_requested(app: &mut App, window: Entity) {
+        app.world_mut().send_event(WindowCloseRequested { window });
+        app.update();
+    }
+
+    #[test]
+    fn unmanaged_windows_are_despawned_the_update_after_closing() {
+        let mut app = App::new();
+        app.add_event::<WindowCloseRequested>()
+            .add_event::<WindowClosed>()
+            .add_systems(Update, close_when_requested)
+            .add_systems(Last, despawn_unmanaged_windows);
+        let window = app.world_mut().spawn(Window::default()).id();
+        let managed = app
+            .world_mut()
+            .spawn((Window::default(), ManagedWindow))
+            .id();
+
+        close_requested(&mut app, window);
+        close_requested(&mut app, managed);
+        assert!(app.world().get_entity(window).is_none());
+        assert_eq!(
+            app.world_mut()
+                .resource_mut::<Events<WindowClosed>>()
+                .drain()
+                .collect::<Vec<_>>(),
+            vec![WindowClosed { window }]
+        );
+
+        // The backend closes the windows it manages.
+        app.update();
+        assert!(app.world().get::<ClosingWindow>(managed).is_some());
     }
 }
@@ -2,7 +2,7 @@ use std::num::NonZeroU32;
 
 use bevy_ecs::{
     entity::{Entity, EntityMapper, MapEntities},
-    prelude::{Component, ReflectComponent},
+    prelude::{Commands, Component, ReflectComponent, World},
 };
 use bevy_math::{DVec2, IVec2, UVec2, Vec2};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
@@ -12,7 +12,7 @@ use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
 
 use bevy_utils::tracing::warn;
 
-use crate::CursorIcon;
+use crate::{CursorIcon, VideoModeInfo};
 
 /// Marker [`Component`] for the window considered the primary window.
 ///
@@ -25,6 +25,88 @@ use crate::CursorIcon;
 #[reflect(Component)]
 pub struct PrimaryWindow;
 
+/// Marker [`Component`] for a lightweight splash window, shown while a heavy app starts.
+///
+/// Splash windows are closed by the windowing backend once a [`PrimaryWindow`] that isn't a
+/// splash window has presented its first frame, as reported by the renderer through the
+/// [`PresentFeedback`](crate::PresentFeedback).
+/// Spawn the splash window before adding the backend's plugin so it is created immediately.
+///
+/// Use [`SplashWindow::window`] for a borderless, centered [`Window`].
+#[derive(Default, Debug, Component, PartialEq, Eq, Copy, Clone, Reflect)]
+#[reflect(Component)]
+pub struct SplashWindow;
+
+impl SplashWindow {
+    /// Returns a borderless [`Window`] centered on the primary monitor, suited for a splash screen.
+    pub fn window(title: impl Into<String>, resolution: impl Into<WindowResolution>) -> Window {
+        Window {
+            title: title.into(),
+            resolution: resolution.into(),
+            position: WindowPosition::Centered(MonitorSelection::Primary),
+            decorations: false,
+            resizable: false,
+            skip_taskbar: true,
+            enabled_buttons: EnabledButtons {
+                minimize: false,
+                maximize: false,
+                close: false,
+            },
+            ..Default::default()
+        }
+    }
+}
+
+/// Marker [`Component`] for the windows spawned with [`SpawnWindowExt::spawn_window`].
+///
+/// It sets windows opened at runtime apart from the [`PrimaryWindow`], e.g. to query the tool
+/// windows of an editor with `Query<&Window, With<SecondaryWindow>>`. An entity must not have both
+/// markers.
+#[derive(Default, Debug, Component, PartialEq, Eq, Copy, Clone, Reflect)]
+#[reflect(Component)]
+pub struct SecondaryWindow;
+
+/// Extension trait spawning secondary windows, see [`SpawnWindowExt::spawn_window`].
+pub trait SpawnWindowExt {
+    /// Spawns an entity with `window` and the [`SecondaryWindow`] marker, returning the entity.
+    ///
+    /// The window is never treated as the [`PrimaryWindow`], so [`WindowRef::Primary`] and
+    /// [`ExitCondition::OnPrimaryClosed`](crate::ExitCondition::OnPrimaryClosed) keep referring to
+    /// the window spawned by the [`WindowPlugin`](crate::WindowPlugin).
+    ///
+    /// The OS window and its surface are created by the windowing backend at the end of the
+    /// update, or the next time the event loop resumes, after which a
+    /// [`WindowCreated`](crate::WindowCreated) event is sent for the entity. Until then, the
+    /// entity has no [`RawHandleWrapper`](crate::RawHandleWrapper), so rendering to the window starts on the next update at
+    /// the earliest.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_window::{SpawnWindowExt, Window};
+    /// fn open_inspector(mut commands: Commands) {
+    ///     let inspector = commands.spawn_window(Window {
+    ///         title: "Inspector".to_owned(),
+    ///         ..Default::default()
+    ///     });
+    ///     // Point a camera at `inspector` with `WindowRef::Entity(inspector)`.
+    /// }
+    /// # bevy_ecs::system::assert_is_system(open_inspector);
+    /// ```
+    fn spawn_window(&mut self, window: Window) -> Entity;
+}
+
+impl SpawnWindowExt for Commands<'_, '_> {
+    fn spawn_window(&mut self, window: Window) -> Entity {
+        self.spawn((window, SecondaryWindow)).id()
+    }
+}
+
+impl SpawnWindowExt for World {
+    fn spawn_window(&mut self, window: Window) -> Entity {
+        self.spawn((window, SecondaryWindow)).id()
+    }
+}
+
 /// Reference to a [`Window`], whether it be a direct link to a specific entity or
 /// a more vague defaulting choice.
 #[repr(C)]
@@ -120,10 +202,18 @@ impl NormalizedWindowRef {
 ///     }
 /// }
 /// ```
+///
+/// # Scenes
+///
+/// The whole configuration of a window is reflected, so windows can also be defined in scene
+/// files and opened by spawning the scene, like any other entity. The fields left out of a scene
+/// file keep their default value, as they do when deserializing a window with the `serialize`
+/// feature.
 #[derive(Component, Debug, Clone, Reflect)]
 #[cfg_attr(
     feature = "serialize",
     derive(serde::Serialize, serde::Deserialize),
+    serde(default),
     reflect(Serialize, Deserialize)
 )]
 #[reflect(Component, Default)]
@@ -160,6 +250,12 @@ pub struct Window {
     /// The limits of the window's logical size
     /// (found in its [`resolution`](WindowResolution)) when resizing.
     pub resize_constraints: WindowResizeConstraints,
+    /// The ratio of width to height the window is kept at when resized, e.g. `Some(16.0 / 9.0)`.
+    ///
+    /// The backend corrects sizes that don't match it by requesting a new size, so the window
+    /// may briefly have another ratio during a live resize. `None` lets the window be resized
+    /// freely.
+    pub aspect_ratio: Option<f32>,
     /// Should the window be resizable?
     ///
     /// Note: This does not stop the program from fullscreening/setting
@@ -192,6 +288,16 @@ pub struct Window {
     /// macOS transparent works with winit out of the box, so this issue might be related to: <https://github.com/gfx-rs/wgpu/issues/687>.
     /// You should also set the window `composite_alpha_mode` to `CompositeAlphaMode::PostMultiplied`.
     pub transparent: bool,
+    /// The opacity of the whole window, from `0.0` (invisible) to `1.0` (opaque).
+    ///
+    /// Unlike [`transparent`](Self::transparent), this fades the window with its decorations, e.g.
+    /// to fade tool windows in and out.
+    ///
+    /// ## Platform-specific
+    /// - Web: Applied to the canvas.
+    /// - X11: Applied by compositing window managers. Requires the `x11` feature of `bevy_winit`.
+    /// - Other platforms: Unsupported.
+    pub opacity: f32,
     /// Get/set whether the window is focused.
     pub focused: bool,
     /// Where should the window appear relative to other overlapping window.
@@ -272,6 +378,18 @@ pub struct Window {
     ///
     /// - Only supported on Windows.
     pub skip_taskbar: bool,
+    /// Sets whether the window bypasses the window manager.
+    ///
+    /// If `true`, the window is neither managed nor decorated by the window manager, which suits
+    /// tooltips, dropdowns and context menus. Such a window isn't focused when created, and should
+    /// be given a [`WindowPosition::At`] position as the window manager won't place it.
+    ///
+    /// Note that this will only take effect on window creation.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only supported on X11.
+    pub override_redirect: bool,
     /// Optional hint given to the rendering API regarding the maximum number of queued frames admissible on the GPU.
     ///
     /// Given values are usually within the 1-3 range. If not provided, this will default to 2.
@@ -296,12 +414,14 @@ impl Default for Window {
             internal: Default::default(),
             composite_alpha_mode: Default::default(),
             resize_constraints: Default::default(),
+            aspect_ratio: None,
             ime_enabled: Default::default(),
             ime_position: Default::default(),
             resizable: true,
             enabled_buttons: Default::default(),
             decorations: true,
             transparent: false,
+            opacity: 1.0,
             focused: true,
             window_level: Default::default(),
             fit_canvas_to_parent: false,
@@ -310,6 +430,7 @@ impl Default for Window {
             window_theme: None,
             visible: true,
             skip_taskbar: false,
+            override_redirect: false,
             desired_maximum_frame_latency: None,
         }
     }
@@ -330,6 +451,15 @@ impl Window {
         self.internal.minimize_request = Some(minimized);
     }
 
+    /// Asks the OS to bring the window to the front and give it the focus.
+    ///
+    /// Most platforms prevent apps from stealing the focus from other apps, and may flash the
+    /// window in the taskbar instead, so a [`WindowFocusRequestCompleted`](crate::WindowFocusRequestCompleted)
+    /// event reports whether the focus was granted.
+    pub fn request_focus(&mut self) {
+        self.internal.focus_request = true;
+    }
+
     /// The window's client area width in logical pixels.
     ///
     /// See [`WindowResolution`] for an explanation about logical/physical sizes.
@@ -448,6 +578,7 @@ impl Window {
 #[cfg_attr(
     feature = "serialize",
     derive(serde::Serialize, serde::Deserialize),
+    serde(default),
     reflect(Serialize, Deserialize)
 )]
 #[reflect(Debug, PartialEq, Default)]
@@ -508,6 +639,14 @@ impl WindowResizeConstraints {
             max_height,
         }
     }
+
+    /// Clamps a logical `size` to these constraints.
+    ///
+    /// The constraints are expected to be valid, see [`check_constraints`](Self::check_constraints).
+    pub fn clamp(&self, size: Vec2) -> Vec2 {
+        size.max(Vec2::new(self.min_width, self.min_height))
+            .min(Vec2::new(self.max_width, self.max_height))
+    }
 }
 
 /// Cursor data for a [`Window`].
@@ -515,6 +654,7 @@ impl WindowResizeConstraints {
 #[cfg_attr(
     feature = "serialize",
     derive(serde::Serialize, serde::Deserialize),
+    serde(default),
     reflect(Serialize, Deserialize)
 )]
 #[reflect(Debug, Default)]
@@ -541,6 +681,8 @@ pub struct Cursor {
     /// - **`iOS/Android`** don't have cursors.
     ///
     /// Since `Windows` and `macOS` have different [`CursorGrabMode`] support, we first try to set the grab mode that was asked for. If it doesn't work then use the alternate grab mode.
+    ///
+    /// If the platform refuses the grab, e.g. because the window isn't focused yet, it is retried while the window has focus, and a [`WindowError`](crate::WindowError) event is sent if it keeps failing.
     pub grab_mode: CursorGrabMode,
 
     /// Set whether or not mouse events within *this* window are captured or fall through to the Window below.
@@ -656,6 +798,7 @@ impl WindowPosition {
 #[cfg_attr(
     feature = "serialize",
     derive(serde::Serialize, serde::Deserialize),
+    serde(default),
     reflect(Serialize, Deserialize)
 )]
 #[reflect(Debug, PartialEq, Default)]
@@ -672,6 +815,8 @@ pub struct WindowResolution {
     ///
     /// Set automatically depending on the pixel density of the screen.
     scale_factor: f32,
+    /// How the OS-provided scale factor is used when it isn't overridden.
+    scale_factor_preference: ScaleFactorPreference,
 }
 
 impl Default for WindowResolution {
@@ -681,6 +826,7 @@ impl Default for WindowResolution {
             physical_height: 720,
             scale_factor_override: None,
             scale_factor: 1.0,
+            scale_factor_preference: ScaleFactorPreference::default(),
         }
     }
 }
@@ -701,6 +847,12 @@ impl WindowResolution {
         self
     }
 
+    /// Builder method for setting how the scale factor reported by the window backend is used.
+    pub fn with_scale_factor_preference(mut self, preference: ScaleFactorPreference) -> Self {
+        self.set_scale_factor_preference(preference);
+        self
+    }
+
     /// The window's client area width in logical pixels.
     #[inline]
     pub fn width(&self) -> f32 {
@@ -742,7 +894,7 @@ impl WindowResolution {
     /// `physical_pixels = logical_pixels * scale_factor`
     pub fn scale_factor(&self) -> f32 {
         self.scale_factor_override
-            .unwrap_or_else(|| self.base_scale_factor())
+            .unwrap_or_else(|| self.scale_factor_preference.apply(self.base_scale_factor()))
     }
 
     /// The window scale factor as reported by the window backend.
@@ -753,6 +905,12 @@ impl WindowResolution {
         self.scale_factor
     }
 
+    /// How the scale factor reported by the window backend is used when it isn't overridden.
+    #[inline]
+    pub fn scale_factor_preference(&self) -> ScaleFactorPreference {
+        self.scale_factor_preference
+    }
+
     /// The scale factor set with [`WindowResolution::set_scale_factor_override`].
     ///
     /// This value may be different from the scale factor reported by the window backend.
@@ -798,6 +956,48 @@ impl WindowResolution {
         self.scale_factor_override = scale_factor_override;
         self.set(width, height);
     }
+
+    /// Set how the scale factor reported by the window backend is used.
+    ///
+    /// This can change the logical and physical sizes if the resulting physical
+    /// size is not within the limits.
+    #[inline]
+    pub fn set_scale_factor_preference(&mut self, preference: ScaleFactorPreference) {
+        let (width, height) = (self.width(), self.height());
+        self.scale_factor_preference = preference;
+        self.set(width, height);
+    }
+}
+
+/// How the scale factor reported by the window backend is used by a [`WindowResolution`]
+/// without a scale factor override.
+///
+/// Some backends report fractional scale factors (e.g. `1.25` with Wayland's fractional scaling
+/// or Windows' display scaling). Content drawn at a fractional scale can look blurry, so apps
+/// relying on pixel-exact rendering can prefer an integer scale factor instead.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+#[reflect(Debug, PartialEq, Default)]
+pub enum ScaleFactorPreference {
+    /// Use the scale factor as reported.
+    #[default]
+    Fractional,
+    /// Round the scale factor to the nearest integer, and at least `1`.
+    Integer,
+}
+
+impl ScaleFactorPreference {
+    /// Returns the scale factor to use for the scale factor reported by the window backend.
+    pub fn apply(self, scale_factor: f32) -> f32 {
+        match self {
+            ScaleFactorPreference::Fractional => scale_factor,
+            ScaleFactorPreference::Integer => scale_factor.round().max(1.0),
+        }
+    }
 }
 
 impl<I> From<(I, I)> for WindowResolution
@@ -861,6 +1061,7 @@ pub enum CursorGrabMode {
 #[cfg_attr(
     feature = "serialize",
     derive(serde::Serialize, serde::Deserialize),
+    serde(default),
     reflect(Serialize, Deserialize)
 )]
 #[reflect(Debug, PartialEq, Default)]
@@ -869,6 +1070,8 @@ pub struct InternalWindowState {
     minimize_request: Option<bool>,
     /// If this is true then next frame we will ask to maximize/un-maximize the window depending on `maximized`.
     maximize_request: Option<bool>,
+    /// If this is true then next frame we will ask to focus the window.
+    focus_request: bool,
     /// Unscaled cursor position.
     physical_cursor_position: Option<DVec2>,
 }
@@ -883,6 +1086,11 @@ impl InternalWindowState {
     pub fn take_minimize_request(&mut self) -> Option<bool> {
         self.minimize_request.take()
     }
+
+    /// Consumes the current focus request, if it exists. This should only be called by window backends.
+    pub fn take_focus_request(&mut self) -> bool {
+        std::mem::take(&mut self.focus_request)
+    }
 }
 
 /// References a screen monitor.
@@ -907,6 +1115,27 @@ pub enum MonitorSelection {
     Index(usize),
 }
 
+/// A screen monitor, as reported by the windowing backend.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+#[reflect(Debug, PartialEq)]
+pub struct MonitorInfo {
+    /// The human-readable name of the monitor, if available.
+    pub name: Option<String>,
+    /// The position of the top-left corner of the monitor on the desktop, in physical pixels.
+    pub physical_position: IVec2,
+    /// The size of the monitor, in physical pixels.
+    pub physical_size: UVec2,
+    /// The scale factor of the monitor.
+    pub scale_factor: f64,
+    /// The refresh rate of the monitor in millihertz, if available.
+    pub refresh_rate_millihertz: Option<u32>,
+}
+
 /// Presentation mode for a [`Window`].
 ///
 /// The presentation mode specifies when a frame is presented to the window. The [`Fifo`]
@@ -1085,6 +1314,23 @@ pub enum WindowMode {
     /// If you want to avoid that behavior, you can use the [`WindowResolution::set_scale_factor_override`] function
     /// or the [`WindowResolution::with_scale_factor_override`] builder method to set the scale factor to 1.0.
     Fullscreen,
+    /// The window should be in "true"/"legacy" Fullscreen mode, using `video_mode` on `monitor`.
+    ///
+    /// The video modes of each monitor are listed in the [`Monitors`](crate::Monitors) resource,
+    /// see [`SupportedVideoModes`](crate::SupportedVideoModes). If the monitor doesn't support
+    /// `video_mode`, the closest resolution is used, then the closest refresh rate. The monitor is
+    /// resolved by [`MonitorSelection::select`], like [`Monitors::select`](crate::Monitors::select)
+    /// does.
+    ///
+    /// After that, the window's physical size will be modified to match the resolution of the
+    /// video mode, and the logical size will follow based on the scale factor, see
+    /// [`WindowResolution`].
+    ExclusiveFullscreen {
+        /// The monitor to go fullscreen on.
+        monitor: MonitorSelection,
+        /// The video mode to use.
+        video_mode: VideoModeInfo,
+    },
 }
 
 /// Specifies where a [`Window`] should appear relative to other overlapping windows (on top or under) .
@@ -1143,6 +1389,7 @@ pub enum WindowTheme {
 #[cfg_attr(
     feature = "serialize",
     derive(serde::Serialize, serde::Deserialize),
+    serde(default),
     reflect(Serialize, Deserialize)
 )]
 #[reflect(Debug, PartialEq, Default)]
@@ -1172,10 +1419,38 @@ impl Default for EnabledButtons {
 }
 
 /// Marker component for a [`Window`] that has been requested to close and
-/// is in the process of closing (on the next frame).
+/// is in the process of closing.
+///
+/// Once this marker is inserted, the windowing backend closes the window in this order:
+///
+/// 1. At the end of the update in which the marker was inserted, a
+///    [`WindowClosing`](crate::WindowClosing) event is sent and the renderer releases the
+///    window's surface.
+/// 2. The next update is the final one with the window alive, e.g. to save its geometry.
+/// 3. At the end of that update, the OS window is destroyed and a
+///    [`WindowDestroyed`](crate::WindowDestroyed) event is sent, while the entity still exists.
+/// 4. At the end of the next update, the entity is despawned and a
+///    [`WindowClosed`](crate::WindowClosed) event is sent.
+///
+/// Per-window cleanup systems can thus read [`WindowDestroyed`](crate::WindowDestroyed) and
+/// still query the entity. Windows despawned directly skip these steps: the
+/// [`WindowDestroyed`](crate::WindowDestroyed) and [`WindowClosed`](crate::WindowClosed) events
+/// are sent together, after the entity is gone.
+///
+/// Windows no backend manages, see [`ManagedWindow`], are despawned at the end of the update
+/// after the marker was inserted instead, and a [`WindowClosed`](crate::WindowClosed) event is
+/// sent.
 #[derive(Component)]
 pub struct ClosingWindow;
 
+/// Marker component inserted by the windowing backend on the windows it created.
+///
+/// The backend closes these windows through the lifecycle described on [`ClosingWindow`]. The
+/// other closing windows, e.g. in apps without a backend, are despawned by
+/// [`despawn_unmanaged_windows`](crate::despawn_unmanaged_windows).
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct ManagedWindow;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1229,4 +1504,113 @@ mod tests {
         window.set_physical_cursor_position(Some(DVec2::new(400., 600.)));
         assert!(window.physical_cursor_position().is_none());
     }
+
+    // Checks that `spawn_window` tags the window as secondary and never as primary.
+    #[test]
+    fn spawn_secondary_window() {
+        let mut world = World::new();
+        let entity = world.spawn_window(Window {
+            title: "Inspector".to_owned(),
+            ..Default::default()
+        });
+
+        assert_eq!(world.get::<Window>(entity).unwrap().title, "Inspector");
+        assert!(world.get::<SecondaryWindow>(entity).is_some());
+        assert!(world.get::<PrimaryWindow>(entity).is_none());
+    }
+
+    // Checks that a window survives a round trip through the reflection serializer used by scenes.
+    #[test]
+    fn window_reflect_round_trip() {
+        use bevy_reflect::{
+            serde::{ReflectDeserializer, ReflectSerializer},
+            FromReflect, TypeRegistry,
+        };
+        use serde::de::DeserializeSeed;
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<Window>();
+
+        let window = Window {
+            title: "Inspector".to_owned(),
+            resolution: WindowResolution::new(400., 300.),
+            position: WindowPosition::At(IVec2::new(20, 40)),
+            window_level: WindowLevel::AlwaysOnTop,
+            resize_constraints: WindowResizeConstraints {
+                min_width: 200.,
+                min_height: 150.,
+                ..Default::default()
+            },
+            aspect_ratio: Some(4. / 3.),
+            desired_maximum_frame_latency: NonZeroU32::new(1),
+            ..Default::default()
+        };
+
+        let serialized = ron::to_string(&ReflectSerializer::new(&window, &registry)).unwrap();
+        let mut deserializer = ron::Deserializer::from_str(&serialized).unwrap();
+        let reflected = ReflectDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        let deserialized = Window::from_reflect(reflected.as_ref()).unwrap();
+
+        assert_eq!(deserialized.title, window.title);
+        assert_eq!(deserialized.resolution, window.resolution);
+        assert_eq!(deserialized.position, window.position);
+        assert_eq!(deserialized.window_level, window.window_level);
+        assert_eq!(deserialized.resize_constraints, window.resize_constraints);
+        assert_eq!(deserialized.aspect_ratio, window.aspect_ratio);
+        assert_eq!(
+            deserialized.desired_maximum_frame_latency,
+            window.desired_maximum_frame_latency
+        );
+    }
+
+    // Checks that the fields left out of a scene file keep their default value.
+    #[test]
+    fn window_partial_reflect_definition() {
+        use bevy_reflect::{serde::ReflectDeserializer, FromReflect, TypeRegistry};
+        use serde::de::DeserializeSeed;
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<Window>();
+
+        let mut deserializer = ron::Deserializer::from_str(
+            r#"{
+                "bevy_window::window::Window": (
+                    title: "Inspector",
+                    window_level: AlwaysOnTop,
+                ),
+            }"#,
+        )
+        .unwrap();
+        let reflected = ReflectDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        let window = Window::from_reflect(reflected.as_ref()).unwrap();
+
+        let default = Window::default();
+        assert_eq!(window.title, "Inspector");
+        assert_eq!(window.window_level, WindowLevel::AlwaysOnTop);
+        assert_eq!(window.resolution, default.resolution);
+        assert_eq!(window.position, default.position);
+        assert_eq!(window.mode, default.mode);
+        assert_eq!(window.name, default.name);
+    }
+
+    // Checks that an integer scale factor preference rounds the scale factor of the backend.
+    #[test]
+    fn integer_scale_factor_preference() {
+        let mut resolution = WindowResolution::new(800., 600.)
+            .with_scale_factor_preference(ScaleFactorPreference::Integer);
+
+        resolution.set_scale_factor(1.25);
+        assert_eq!(resolution.base_scale_factor(), 1.25);
+        assert_eq!(resolution.scale_factor(), 1.0);
+
+        resolution.set_scale_factor(1.75);
+        assert_eq!(resolution.scale_factor(), 2.0);
+
+        resolution.set_scale_factor_preference(ScaleFactorPreference::Fractional);
+        assert_eq!(resolution.scale_factor(), 1.75);
+    }
 }
@@ -8,9 +8,10 @@ use std::{
 use accesskit_winit::Adapter;
 use bevy_a11y::{
     accesskit::{
-        ActionHandler, ActionRequest, NodeBuilder, NodeClassSet, NodeId, Role, Tree, TreeUpdate,
+        Action, ActionHandler, ActionRequest, Node, NodeBuilder, NodeClassSet, NodeId, Role, Tree,
+        TreeUpdate,
     },
-    AccessibilityNode, AccessibilityRequested, AccessibilitySystem, Focus,
+    AccessibilityNode, AccessibilityRequested, AccessibilitySystem, Focus, OnAccessibilityAction,
 };
 use bevy_a11y::{ActionRequest as ActionRequestWrapper, ManageAccessibilityUpdates};
 use bevy_app::{App, Plugin, PostUpdate};
@@ -18,13 +19,46 @@ use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::entity::EntityHashMap;
 use bevy_ecs::{
     prelude::{DetectChanges, Entity, EventReader, EventWriter},
-    query::With,
+    query::{Changed, Or, With},
+    removal_detection::RemovedComponents,
     schedule::IntoSystemConfigs,
-    system::{NonSend, NonSendMut, Query, Res, ResMut, Resource},
+    system::{Commands, Local, NonSend, NonSendMut, Query, Res, ResMut, Resource},
 };
-use bevy_hierarchy::{Children, Parent};
+use bevy_hierarchy::{Children, HierarchyQueryExt, Parent};
 use bevy_window::{PrimaryWindow, Window, WindowClosed};
 
+/// Resource whose value determines whether the `AccessKit` integration is active.
+///
+/// When `false`, no `AccessKit` [`Adapter`] is created for new windows, the adapters of existing
+/// windows are dropped, [`AccessibilityRequested`] is cleared, and the window events and
+/// accessibility nodes are no longer processed for assistive technologies. This saves the per-event overhead of the integration on some
+/// platforms, e.g. for performance-critical shipped builds.
+///
+/// Adapters can only be created before a window is first shown, so enabling the integration
+/// again only applies to the windows created afterwards.
+///
+/// Its starting value is set by [`WinitPlugin::accessibility`](crate::WinitPlugin::accessibility).
+#[derive(Resource, Clone, Debug)]
+pub struct AccessKitEnabled(pub(crate) bool);
+
+impl Default for AccessKitEnabled {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+impl AccessKitEnabled {
+    /// Returns `true` if the `AccessKit` integration is active.
+    pub fn get(&self) -> bool {
+        self.0
+    }
+
+    /// Sets whether the `AccessKit` integration is active.
+    pub fn set(&mut self, value: bool) {
+        self.0 = value;
+    }
+}
+
 /// Maps window entities to their `AccessKit` [`Adapter`]s.
 #[derive(Default, Deref, DerefMut)]
 pub struct AccessKitAdapters(pub EntityHashMap<Adapter>);
@@ -86,6 +120,27 @@ fn window_closed(
     }
 }
 
+fn accesskit_enabled(enabled: Res<AccessKitEnabled>) -> bool {
+    enabled.get()
+}
+
+/// Drops the adapters of every window once the integration is disabled.
+///
+/// No assistive technology can be listening without an adapter, so [`AccessibilityRequested`] is
+/// cleared too, for the plugins checking it to stop their accessibility work.
+fn drop_disabled_adapters(
+    enabled: Res<AccessKitEnabled>,
+    accessibility_requested: Res<AccessibilityRequested>,
+    mut adapters: NonSendMut<AccessKitAdapters>,
+    mut handlers: ResMut<WinitActionHandlers>,
+) {
+    if enabled.is_changed() && !enabled.get() {
+        adapters.clear();
+        handlers.clear();
+        accessibility_requested.set(false);
+    }
+}
+
 fn poll_receivers(
     handlers: Res<WinitActionHandlers>,
     mut actions: EventWriter<ActionRequestWrapper>,
@@ -98,6 +153,45 @@ fn poll_receivers(
     }
 }
 
+/// Moves the [`Focus`] to the nodes that assistive technologies ask to focus, so the app and the
+/// screen reader agree on the focused entity.
+fn focus_from_action_requests(
+    mut requests: EventReader<ActionRequestWrapper>,
+    mut focus: ResMut<Focus>,
+    focusable: Query<(), Or<(With<AccessibilityNode>, With<Window>)>>,
+) {
+    for request in requests.read() {
+        let Ok(target) = Entity::try_from_bits(request.target.0) else {
+            continue;
+        };
+        match request.action {
+            Action::Focus if focusable.contains(target) && **focus != Some(target) => {
+                **focus = Some(target);
+            }
+            Action::Blur if **focus == Some(target) => {
+                **focus = None;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Dispatches the action requests to the [`OnAccessibilityAction`] handler of their target.
+fn dispatch_action_requests(
+    mut requests: EventReader<ActionRequestWrapper>,
+    mut handlers: Query<&mut OnAccessibilityAction>,
+    mut commands: Commands,
+) {
+    for request in requests.read() {
+        let Ok(target) = Entity::try_from_bits(request.target.0) else {
+            continue;
+        };
+        if let Ok(mut handler) = handlers.get_mut(target) {
+            handler.handle(target, request, &mut commands);
+        }
+    }
+}
+
 fn should_update_accessibility_nodes(
     accessibility_requested: Res<AccessibilityRequested>,
     manage_accessibility_updates: Res<ManageAccessibilityUpdates>,
@@ -105,73 +199,156 @@ fn should_update_accessibility_nodes(
     accessibility_requested.get() && manage_accessibility_updates.get()
 }
 
+/// The nodes of a single window that have to be pushed to its `AccessKit` adapter.
+#[derive(Default)]
+struct WindowTreeUpdate {
+    /// Nodes that are direct children of the window node.
+    children: Vec<NodeId>,
+    /// Nodes to send in the update.
+    nodes: Vec<(NodeId, Node)>,
+    /// The node that has focus, if it is part of this window.
+    focus: Option<NodeId>,
+    /// Whether a node of this window changed since the last update.
+    changed: bool,
+}
+
+type AccessibilityNodes<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static AccessibilityNode,
+        Option<&'static Children>,
+        Option<&'static Parent>,
+    ),
+>;
+
+type ChangedAccessibilityNodes<'w, 's> = Query<
+    'w,
+    's,
+    (),
+    (
+        With<AccessibilityNode>,
+        Or<(
+            Changed<AccessibilityNode>,
+            Changed<Children>,
+            Changed<Parent>,
+        )>,
+    ),
+>;
+
+/// Pushes the accessibility nodes that changed during the frame to the adapter of the window
+/// they belong to, with a single `AccessKit` update per window.
+///
+/// A node belongs to the closest ancestor that is a window, or to the primary window. Windows
+/// whose adapter has not received any update yet get their whole tree, and the window node is
+/// refreshed whenever its title changes.
+#[allow(clippy::too_many_arguments)]
 fn update_accessibility_nodes(
     adapters: NonSend<AccessKitAdapters>,
     focus: Res<Focus>,
-    primary_window: Query<(Entity, &Window), With<PrimaryWindow>>,
-    nodes: Query<(
-        Entity,
-        &AccessibilityNode,
-        Option<&Children>,
-        Option<&Parent>,
-    )>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    windows: Query<&Window>,
+    nodes: AccessibilityNodes,
+    changed_nodes: ChangedAccessibilityNodes,
     node_entities: Query<Entity, With<AccessibilityNode>>,
+    parents: Query<&Parent>,
+    mut removed_nodes: RemovedComponents<AccessibilityNode>,
+    mut announced_titles: Local<EntityHashMap<String>>,
 ) {
-    let Ok((primary_window_id, primary_window)) = primary_window.get_single() else {
-        return;
-    };
-    let Some(adapter) = adapters.get(&primary_window_id) else {
+    announced_titles.retain(|window, _| adapters.contains_key(window));
+    // Removed nodes can't be traced back to their window anymore, so every window is refreshed.
+    let nodes_removed = removed_nodes.read().count() > 0;
+    // Walking the nodes is only worth it once something changed, or a window wasn't updated yet.
+    let titles_announced = adapters.keys().all(|window_entity| {
+        windows.get(*window_entity).map_or(true, |window| {
+            announced_titles.get(window_entity) == Some(&window.title)
+        })
+    });
+    if !nodes_removed && !focus.is_changed() && changed_nodes.is_empty() && titles_announced {
         return;
-    };
-    if focus.is_changed() || !nodes.is_empty() {
+    }
+
+    let primary_window = primary_window.get_single().ok();
+    let mut updates = EntityHashMap::<WindowTreeUpdate>::default();
+    for (entity, node, children, parent) in &nodes {
+        let Some(window) = node_window(entity, &parents, &windows).or(primary_window) else {
+            continue;
+        };
+        let update = updates.entry(window).or_default();
+        queue_node_for_update(entity, parent, &node_entities, &mut update.children);
+
+        let node_id = NodeId(entity.to_bits());
+        if **focus == Some(entity) {
+            update.focus = Some(node_id);
+        }
+        let changed = changed_nodes.contains(entity);
+        update.changed |= changed;
+        if changed || !announced_titles.contains_key(&window) {
+            let mut node = (**node).clone();
+            add_children_nodes(children, &node_entities, &mut node);
+            update
+                .nodes
+                .push((node_id, node.build(&mut NodeClassSet::lock_global())));
+        }
+    }
+
+    for (&window_entity, adapter) in adapters.iter() {
+        let Ok(window) = windows.get(window_entity) else {
+            continue;
+        };
+        let update = updates.remove(&window_entity).unwrap_or_default();
+        let announced_title = announced_titles.get(&window_entity);
+        // Windows that were never updated need their whole tree, and title changes are pushed so
+        // screen readers announce the new context.
+        let title_changed = announced_title != Some(&window.title);
+        if !title_changed && !update.changed && !nodes_removed && !focus.is_changed() {
+            continue;
+        }
+
+        let mut sent = false;
         adapter.update_if_active(|| {
-            update_adapter(
-                nodes,
-                node_entities,
-                primary_window,
-                primary_window_id,
-                focus,
-            )
+            sent = true;
+            build_tree_update(window_entity, window, update)
         });
+        if sent {
+            announced_titles.insert(window_entity, window.title.clone());
+        }
     }
 }
 
-fn update_adapter(
-    nodes: Query<(
-        Entity,
-        &AccessibilityNode,
-        Option<&Children>,
-        Option<&Parent>,
-    )>,
-    node_entities: Query<Entity, With<AccessibilityNode>>,
-    primary_window: &Window,
-    primary_window_id: Entity,
-    focus: Res<Focus>,
+/// Returns the closest ancestor of `entity` that is a window.
+fn node_window(
+    entity: Entity,
+    parents: &Query<&Parent>,
+    windows: &Query<&Window>,
+) -> Option<Entity> {
+    parents
+        .iter_ancestors(entity)
+        .find(|ancestor| windows.contains(*ancestor))
+}
+
+fn build_tree_update(
+    window_entity: Entity,
+    window: &Window,
+    update: WindowTreeUpdate,
 ) -> TreeUpdate {
-    let mut to_update = vec![];
-    let mut window_children = vec![];
-    for (entity, node, children, parent) in &nodes {
-        let mut node = (**node).clone();
-        queue_node_for_update(entity, parent, &node_entities, &mut window_children);
-        add_children_nodes(children, &node_entities, &mut node);
-        let node_id = NodeId(entity.to_bits());
-        let node = node.build(&mut NodeClassSet::lock_global());
-        to_update.push((node_id, node));
-    }
+    let WindowTreeUpdate {
+        children,
+        mut nodes,
+        focus,
+        ..
+    } = update;
     let mut window_node = NodeBuilder::new(Role::Window);
-    if primary_window.focused {
-        let title = primary_window.title.clone();
-        window_node.set_name(title.into_boxed_str());
-    }
-    window_node.set_children(window_children);
+    window_node.set_name(window.title.clone().into_boxed_str());
+    window_node.set_children(children);
     let window_node = window_node.build(&mut NodeClassSet::lock_global());
-    let node_id = NodeId(primary_window_id.to_bits());
-    let window_update = (node_id, window_node);
-    to_update.insert(0, window_update);
+    let window_id = NodeId(window_entity.to_bits());
+    nodes.insert(0, (window_id, window_node));
     TreeUpdate {
-        nodes: to_update,
+        nodes,
         tree: None,
-        focus: NodeId(focus.unwrap_or(primary_window_id).to_bits()),
+        focus: focus.unwrap_or(window_id),
     }
 }
 
@@ -215,17 +392,53 @@ impl Plugin for AccessKitPlugin {
     fn build(&self, app: &mut App) {
         app.init_non_send_resource::<AccessKitAdapters>()
             .init_resource::<WinitActionHandlers>()
+            .init_resource::<AccessKitEnabled>()
             .add_event::<ActionRequestWrapper>()
+            .add_systems(
+                PostUpdate,
+                drop_disabled_adapters.before(AccessibilitySystem::Update),
+            )
             .add_systems(
                 PostUpdate,
                 (
                     poll_receivers,
+                    dispatch_action_requests.after(poll_receivers),
+                    focus_from_action_requests
+                        .after(poll_receivers)
+                        .before(update_accessibility_nodes),
                     update_accessibility_nodes.run_if(should_update_accessibility_nodes),
                     window_closed
                         .before(poll_receivers)
                         .before(update_accessibility_nodes),
                 )
+                    .run_if(accesskit_enabled)
                     .in_set(AccessibilitySystem::Update),
             );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+
+    use super::*;
+
+    #[test]
+    fn disabling_the_integration_clears_the_accessibility_request() {
+        let mut app = App::new();
+        app.init_resource::<AccessibilityRequested>()
+            .init_resource::<AccessKitEnabled>()
+            .init_non_send_resource::<AccessKitAdapters>()
+            .init_resource::<WinitActionHandlers>()
+            .add_systems(PostUpdate, drop_disabled_adapters);
+        app.world().resource::<AccessibilityRequested>().set(true);
+        app.update();
+        assert!(app.world().resource::<AccessibilityRequested>().get());
+
+        app.world_mut()
+            .resource_mut::<AccessKitEnabled>()
+            .set(false);
+        app.update();
+        assert!(!app.world().resource::<AccessibilityRequested>().get());
+    }
+}
@@ -0,0 +1,13 @@
+use bevy_ecs::prelude::*;
+use bevy_reflect::Reflect;
+
+/// Event notifying the app that the OS is terminating it, e.g. when the user picks "Quit" in the
+/// application menu of macOS or presses Cmd+Q.
+///
+/// This is a notification, not a request: the runner sends it along with an
+/// [`AppExit`](bevy_app::AppExit) event, and runs one last update so systems can react, e.g. to
+/// save the work of the user. The process ends once the runner returns to the OS, so the exit
+/// can't be held back: the [`ExitConfirmationPlugin`](crate::ExitConfirmationPlugin) only
+/// confirms the exits the app itself asks for.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub struct AppTerminating;
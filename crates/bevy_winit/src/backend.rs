@@ -0,0 +1,133 @@
+use bevy_ecs::{entity::Entity, system::EntityCommands};
+use bevy_math::Vec2;
+use bevy_window::{
+    CursorGrabMode, CursorIcon, EnabledButtons, Window, WindowLevel, WindowResizeConstraints,
+    WindowTheme,
+};
+use winit::dpi::PhysicalSize;
+
+/// The windows the window systems (`create_windows`, `changed_windows`, `despawn_windows`) manage,
+/// mapping the window entities to the windows of the platform.
+///
+/// [`WinitWindows`](crate::WinitWindows) is the backend of the [`WinitPlugin`](crate::WinitPlugin).
+/// [`HeadlessWindows`](crate::HeadlessWindows) simulates the windows, so the same systems can run
+/// without a display server.
+pub(crate) trait WindowBackend: 'static {
+    /// A window of the platform.
+    type Window: BackendWindow;
+    /// What the backend needs to create windows, e.g. the `winit` event loop.
+    type CreateContext<'a>;
+
+    /// Creates the window of `entity` from the properties of `window`, inserting the components
+    /// the backend adds to the window entities.
+    fn create_window(
+        &mut self,
+        context: &mut Self::CreateContext<'_>,
+        entity: Entity,
+        window: &Window,
+        commands: &mut EntityCommands,
+    ) -> &mut Self::Window;
+
+    /// Returns the window of `entity`, if it has one.
+    fn window(&self, entity: Entity) -> Option<&Self::Window>;
+
+    /// Returns the window of `entity` mutably, if it has one.
+    fn window_mut(&mut self, entity: Entity) -> Option<&mut Self::Window>;
+
+    /// Destroys the window of `entity`, returning `false` if it had none.
+    fn destroy_window(&mut self, entity: Entity) -> bool;
+
+    /// Called with the result of [`BackendWindow::set_cursor_grab`], so a refused grab can be
+    /// retried later.
+    fn cursor_grab_applied(&mut self, _entity: Entity, _granted: bool) {}
+}
+
+/// A window of a [`WindowBackend`], applying the properties of its [`Window`] component.
+///
+/// The setters do nothing by default, for the backends that can't change the property.
+pub(crate) trait BackendWindow {
+    /// The scale factor of the monitor the window is on.
+    fn scale_factor(&self) -> f32;
+
+    /// The theme of the window, if the platform reports it.
+    fn theme(&self) -> Option<WindowTheme>;
+
+    /// Returns `true` if the window has the input focus.
+    fn has_focus(&self) -> bool;
+
+    /// Requests the input focus for the window.
+    fn focus(&mut self);
+
+    /// Requests a new size for the client area, returning the size applied if it changed right
+    /// away.
+    fn request_inner_size(&mut self, size: PhysicalSize<u32>) -> Option<PhysicalSize<u32>>;
+
+    /// Applies [`Window::title`].
+    fn set_title(&mut self, _title: &str) {}
+
+    /// Applies [`Window::mode`], returning why it can't be applied.
+    fn set_mode(&mut self, _window: &Window) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Applies [`Window::position`].
+    fn set_position(&mut self, _window: &Window) {}
+
+    /// Moves the cursor to `position`, in physical pixels from the top-left of the window.
+    fn set_cursor_position(&mut self, _position: Vec2) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Applies [`Cursor::icon`](bevy_window::Cursor::icon).
+    fn set_cursor_icon(&mut self, _icon: CursorIcon) {}
+
+    /// Applies [`Cursor::grab_mode`](bevy_window::Cursor::grab_mode), returning `false` if the
+    /// platform refused it.
+    fn set_cursor_grab(&mut self, _grab_mode: CursorGrabMode) -> bool {
+        true
+    }
+
+    /// Applies [`Cursor::visible`](bevy_window::Cursor::visible).
+    fn set_cursor_visible(&mut self, _visible: bool) {}
+
+    /// Applies [`Cursor::hit_test`](bevy_window::Cursor::hit_test).
+    fn set_cursor_hit_test(&mut self, _hit_test: bool) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Applies [`Window::decorations`].
+    fn set_decorations(&mut self, _decorations: bool) {}
+
+    /// Applies [`Window::resizable`].
+    fn set_resizable(&mut self, _resizable: bool) {}
+
+    /// Applies [`Window::enabled_buttons`].
+    fn set_enabled_buttons(&mut self, _enabled_buttons: EnabledButtons) {}
+
+    /// Applies [`Window::resize_constraints`].
+    fn set_resize_constraints(&mut self, _constraints: WindowResizeConstraints) {}
+
+    /// Maximizes or restores the window.
+    fn set_maximized(&mut self, _maximized: bool) {}
+
+    /// Minimizes or restores the window.
+    fn set_minimized(&mut self, _minimized: bool) {}
+
+    /// Applies [`Window::window_level`].
+    fn set_window_level(&mut self, _window_level: WindowLevel) {}
+
+    /// Applies [`Window::ime_enabled`].
+    fn set_ime_allowed(&mut self, _allowed: bool) {}
+
+    /// Applies [`Window::ime_position`].
+    fn set_ime_position(&mut self, _position: Vec2) {}
+
+    /// Applies [`Window::window_theme`].
+    fn set_theme(&mut self, _theme: Option<WindowTheme>) {}
+
+    /// Applies [`Window::visible`].
+    fn set_visible(&mut self, _visible: bool) {}
+
+    /// Applies [`Window::opacity`].
+    fn set_opacity(&mut self, _opacity: f32) {}
+}
@@ -0,0 +1,224 @@
+use std::{
+    any::{Any, TypeId},
+    sync::{
+        mpsc::{channel, Receiver, SendError, Sender},
+        Arc, Mutex, PoisonError,
+    },
+};
+
+use bevy_app::App;
+use bevy_ecs::prelude::*;
+use bevy_utils::HashMap;
+
+use crate::{WinitEventSystem, WinitEvents, WinitWaker};
+
+/// Creates a sender whose messages are delivered to the [`App`] as Bevy events of type `T`.
+///
+/// The returned [`WinitEventSender`] is `Send + Sync` and can be cloned freely, so async tasks and
+/// other threads can feed events into the app. Every message wakes up the event loop, even if the
+/// app is sleeping in a [`Reactive`](crate::UpdateMode::Reactive) or
+/// [`ReactiveLowPower`](crate::UpdateMode::ReactiveLowPower) update mode. The events are sent
+/// during the [`WinitEvents`] schedule, so every system of the next update can read them.
+///
+/// Calling this several times with the same `T` returns senders feeding the same [`Events<T>`].
+/// The sender is also inserted as a resource, so systems can get one with
+/// `Res<WinitEventSender<T>>`.
+///
+/// # Panics
+///
+/// Panics if the [`WinitPlugin`](crate::WinitPlugin) has not been added to the app yet.
+///
+/// # Example
+///
+/// ```no_run
+/// # use bevy_app::App;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_winit::{winit_channel, WinitPlugin};
+/// #[derive(Event)]
+/// struct Downloaded(Vec<u8>);
+///
+/// let mut app = App::new();
+/// app.add_plugins(WinitPlugin::default());
+///
+/// let sender = winit_channel::<Downloaded>(&mut app);
+/// std::thread::spawn(move || {
+///     let _ = sender.send(Downloaded(Vec::new()));
+/// });
+/// ```
+pub fn winit_channel<T: Event>(app: &mut App) -> WinitEventSender<T> {
+    if let Some(sender) = app.world().get_resource::<WinitEventSender<T>>() {
+        return sender.clone();
+    }
+
+    let waker = app
+        .world()
+        .get_resource::<WinitWaker>()
+        .expect("The `WinitPlugin` must be added before creating a winit channel")
+        .clone();
+    let (sender, receiver) = channel();
+    let sender = WinitEventSender { sender, waker };
+    app.add_event::<T>()
+        .insert_resource(WinitChannel {
+            receiver: Mutex::new(receiver),
+        })
+        .insert_resource(sender.clone())
+        .add_systems(
+            WinitEvents,
+            forward_channel_events::<T>.in_set(WinitEventSystem::Forward),
+        );
+
+    sender
+}
+
+/// The sending half of a [`winit_channel`], also available as a resource.
+///
+/// Each message is sent to the app as a Bevy event of type `T`, waking up the event loop. The
+/// sender is `Send + Sync` and can be cloned freely, so systems can hand it to async tasks or
+/// other threads.
+///
+/// ```no_run
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_winit::WinitEventSender;
+/// #[derive(Event)]
+/// struct Downloaded(Vec<u8>);
+///
+/// fn download(sender: Res<WinitEventSender<Downloaded>>) {
+///     let sender = sender.clone();
+///     std::thread::spawn(move || {
+///         let _ = sender.send(Downloaded(Vec::new()));
+///     });
+/// }
+/// ```
+#[derive(Resource)]
+pub struct WinitEventSender<T: Event> {
+    sender: Sender<T>,
+    waker: WinitWaker,
+}
+
+/// A [`WinitEventSender`], waking up the event loop with [`wake`](WinitEventSender::wake) and
+/// sending events with [`send`](WinitEventSender::send).
+pub type WakeSender<T> = WinitEventSender<T>;
+
+impl<T: Event> WinitEventSender<T> {
+    /// Sends `event` to the app and wakes up the event loop.
+    ///
+    /// Returns an error containing `event` if the app is no longer running.
+    pub fn send(&self, event: T) -> Result<(), SendError<T>> {
+        self.sender.send(event)?;
+        // A closed event loop also drops the receiver, so the error above already covers it.
+        self.waker.wake();
+        Ok(())
+    }
+
+    /// Wakes up the event loop without sending an event, running an update.
+    ///
+    /// Returns `false` if the app is no longer running.
+    pub fn wake(&self) -> bool {
+        self.waker.wake()
+    }
+}
+
+impl<T: Event> Clone for WinitEventSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            waker: self.waker.clone(),
+        }
+    }
+}
+
+/// Resource registering the event types that can be sent to the [`App`] from other threads, so
+/// several plugins can each feed their own events through the event loop.
+///
+/// Each registered type gets its own [`winit_channel`]: the events are delivered as Bevy events
+/// of that type, waking up the event loop. The registry is `Send + Sync` and can be cloned freely,
+/// e.g. into async tasks, a clone seeing the types registered when it was made.
+///
+/// ```no_run
+/// # use bevy_app::App;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_winit::{WinitPlugin, WinitUserEvents};
+/// #[derive(Event)]
+/// struct Downloaded(Vec<u8>);
+///
+/// #[derive(Event)]
+/// struct MidiMessage([u8; 3]);
+///
+/// let mut app = App::new();
+/// app.add_plugins(WinitPlugin::default());
+/// WinitUserEvents::register::<Downloaded>(&mut app);
+/// WinitUserEvents::register::<MidiMessage>(&mut app);
+///
+/// fn download(events: Res<WinitUserEvents>) {
+///     let events = events.clone();
+///     std::thread::spawn(move || {
+///         let _ = events.send(Downloaded(Vec::new()));
+///     });
+/// }
+/// ```
+#[derive(Resource, Clone, Default)]
+pub struct WinitUserEvents {
+    /// The [`WinitEventSender`] of each registered type.
+    senders: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+impl WinitUserEvents {
+    /// Registers `T`, so it can be sent to the app with [`send`](Self::send), and returns a
+    /// sender for it.
+    ///
+    /// Registering a type again returns a sender feeding the same [`Events<T>`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`WinitPlugin`](crate::WinitPlugin) has not been added to the app yet.
+    pub fn register<T: Event>(app: &mut App) -> WinitEventSender<T> {
+        let sender = winit_channel::<T>(app);
+        app.world_mut()
+            .get_resource_or_insert_with(WinitUserEvents::default)
+            .senders
+            .insert(TypeId::of::<T>(), Arc::new(sender.clone()));
+        sender
+    }
+
+    /// Returns `true` if `T` has been registered.
+    pub fn is_registered<T: Event>(&self) -> bool {
+        self.senders.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Returns a sender for `T`, or `None` if `T` hasn't been registered.
+    pub fn sender<T: Event>(&self) -> Option<WinitEventSender<T>> {
+        self.get::<T>().cloned()
+    }
+
+    /// Sends `event` to the app and wakes up the event loop.
+    ///
+    /// Returns an error containing `event` if `T` hasn't been registered, or if the app is no
+    /// longer running.
+    pub fn send<T: Event>(&self, event: T) -> Result<(), SendError<T>> {
+        match self.get::<T>() {
+            Some(sender) => sender.send(event),
+            None => Err(SendError(event)),
+        }
+    }
+
+    fn get<T: Event>(&self) -> Option<&WinitEventSender<T>> {
+        self.senders
+            .get(&TypeId::of::<T>())
+            .and_then(|sender| sender.downcast_ref())
+    }
+}
+
+/// Keeps the receiving half of a [`winit_channel`].
+#[derive(Resource)]
+struct WinitChannel<T> {
+    // `Receiver` is `Send` but not `Sync`.
+    receiver: Mutex<Receiver<T>>,
+}
+
+fn forward_channel_events<T: Event>(channel: Res<WinitChannel<T>>, mut events: EventWriter<T>) {
+    let receiver = channel
+        .receiver
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    events.send_batch(receiver.try_iter());
+}
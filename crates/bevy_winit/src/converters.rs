@@ -5,8 +5,10 @@ use bevy_input::{
     touch::{ForceTouch, TouchInput, TouchPhase},
     ButtonState,
 };
-use bevy_math::Vec2;
-use bevy_window::{CursorIcon, EnabledButtons, WindowLevel, WindowTheme};
+use bevy_math::{IVec2, UVec2, Vec2};
+use bevy_window::{
+    CursorIcon, EnabledButtons, MonitorInfo, VideoModeInfo, WindowLevel, WindowTheme,
+};
 use winit::keyboard::{Key, NamedKey, NativeKey};
 
 pub fn convert_keyboard_input(
@@ -701,3 +703,24 @@ pub fn convert_enabled_buttons(enabled_buttons: EnabledButtons) -> winit::window
     }
     window_buttons
 }
+
+pub fn convert_monitor(monitor: &winit::monitor::MonitorHandle) -> MonitorInfo {
+    let position = monitor.position();
+    let size = monitor.size();
+    MonitorInfo {
+        name: monitor.name(),
+        physical_position: IVec2::new(position.x, position.y),
+        physical_size: UVec2::new(size.width, size.height),
+        scale_factor: monitor.scale_factor(),
+        refresh_rate_millihertz: monitor.refresh_rate_millihertz(),
+    }
+}
+
+pub fn convert_video_mode(video_mode: &winit::monitor::VideoMode) -> VideoModeInfo {
+    let size = video_mode.size();
+    VideoModeInfo {
+        physical_size: UVec2::new(size.width, size.height),
+        bit_depth: video_mode.bit_depth(),
+        refresh_rate_millihertz: video_mode.refresh_rate_millihertz(),
+    }
+}
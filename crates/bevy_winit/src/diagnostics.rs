@@ -0,0 +1,138 @@
+use bevy_app::{App, Plugin, Update};
+use bevy_diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+use bevy_ecs::prelude::*;
+use bevy_time::{Real, Time};
+use bevy_utils::{Duration, Instant};
+use winit::event::{Event, WindowEvent};
+
+use crate::{RawWinitEvent, WinitEventSystem, WinitEvents, WinitStartCause};
+
+/// Adds diagnostics counting the [`winit`] events received by each update and measuring how long
+/// the event loop waits between updates, to find out why the loop is busy.
+///
+/// The latency of input events, from the time the runner receives them to the update they are
+/// readable in, is measured as well, to quantify the cost of the reactive update modes.
+///
+/// The events are counted from the [`RawWinitEvent`]s, which this plugin enables.
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](bevy_diagnostic::LogDiagnosticsPlugin) to output diagnostics to the
+/// console.
+#[derive(Default)]
+pub struct WinitDiagnosticsPlugin;
+
+impl Plugin for WinitDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(Diagnostic::new(Self::WINDOW_EVENTS))
+            .register_diagnostic(Diagnostic::new(Self::INPUT_EVENTS))
+            .register_diagnostic(Diagnostic::new(Self::REDRAW_REQUESTS))
+            .register_diagnostic(Diagnostic::new(Self::DEVICE_EVENTS))
+            .register_diagnostic(Diagnostic::new(Self::USER_EVENTS))
+            .register_diagnostic(Diagnostic::new(Self::WAIT_TIME).with_suffix("ms"))
+            .register_diagnostic(Diagnostic::new(Self::BUSY_TIME).with_suffix("ms"))
+            .register_diagnostic(Diagnostic::new(Self::INPUT_LATENCY).with_suffix("ms"))
+            .add_event::<RawWinitEvent>()
+            .add_systems(Update, Self::diagnostic_system)
+            .add_systems(
+                WinitEvents,
+                Self::input_latency_system.after(WinitEventSystem::Forward),
+            );
+    }
+}
+
+impl WinitDiagnosticsPlugin {
+    /// Window events received since the previous update, including input and redraw requests.
+    pub const WINDOW_EVENTS: DiagnosticPath = DiagnosticPath::const_new("winit/window_events");
+    /// Keyboard, mouse and touch window events received since the previous update.
+    pub const INPUT_EVENTS: DiagnosticPath = DiagnosticPath::const_new("winit/input_events");
+    /// Redraw requests received since the previous update.
+    pub const REDRAW_REQUESTS: DiagnosticPath = DiagnosticPath::const_new("winit/redraw_requests");
+    /// Device events received since the previous update.
+    pub const DEVICE_EVENTS: DiagnosticPath = DiagnosticPath::const_new("winit/device_events");
+    /// User events (e.g. wakeups from other threads) received since the previous update.
+    pub const USER_EVENTS: DiagnosticPath = DiagnosticPath::const_new("winit/user_events");
+    /// The longest time an input event received since the previous update waited before it was
+    /// readable by the app, i.e. the latency added by the runner and the [`UpdateMode`].
+    ///
+    /// Only measured on updates receiving input events.
+    ///
+    /// [`UpdateMode`]: crate::UpdateMode
+    pub const INPUT_LATENCY: DiagnosticPath = DiagnosticPath::const_new("winit/input_latency");
+    /// Time the event loop spent waiting for events since the previous update.
+    pub const WAIT_TIME: DiagnosticPath = DiagnosticPath::const_new("winit/wait_time");
+    /// Time spent since the previous update other than waiting, mostly running the update.
+    pub const BUSY_TIME: DiagnosticPath = DiagnosticPath::const_new("winit/busy_time");
+
+    /// Updates the winit diagnostics.
+    pub fn diagnostic_system(
+        mut diagnostics: Diagnostics,
+        mut raw_events: EventReader<RawWinitEvent>,
+        mut start_causes: EventReader<WinitStartCause>,
+        time: Res<Time<Real>>,
+    ) {
+        let (mut window, mut input, mut redraw, mut device, mut user) = (0, 0, 0, 0, 0);
+        for RawWinitEvent { event, .. } in raw_events.read() {
+            match event {
+                Event::WindowEvent { event, .. } => {
+                    window += 1;
+                    match event {
+                        WindowEvent::KeyboardInput { .. }
+                        | WindowEvent::MouseInput { .. }
+                        | WindowEvent::MouseWheel { .. }
+                        | WindowEvent::CursorMoved { .. }
+                        | WindowEvent::Touch(_) => input += 1,
+                        WindowEvent::RedrawRequested => redraw += 1,
+                        _ => {}
+                    }
+                }
+                Event::DeviceEvent { .. } => device += 1,
+                Event::UserEvent(_) => user += 1,
+                _ => {}
+            }
+        }
+        diagnostics.add_measurement(&Self::WINDOW_EVENTS, || window as f64);
+        diagnostics.add_measurement(&Self::INPUT_EVENTS, || input as f64);
+        diagnostics.add_measurement(&Self::REDRAW_REQUESTS, || redraw as f64);
+        diagnostics.add_measurement(&Self::DEVICE_EVENTS, || device as f64);
+        diagnostics.add_measurement(&Self::USER_EVENTS, || user as f64);
+
+        let wait = start_causes
+            .read()
+            .filter_map(WinitStartCause::wait_duration)
+            .sum::<Duration>();
+        diagnostics.add_measurement(&Self::WAIT_TIME, || wait.as_secs_f64() * 1000.0);
+        diagnostics.add_measurement(&Self::BUSY_TIME, || {
+            time.delta().saturating_sub(wait).as_secs_f64() * 1000.0
+        });
+    }
+
+    /// Updates the [`INPUT_LATENCY`](Self::INPUT_LATENCY) diagnostic, once the events of the
+    /// update are readable.
+    pub fn input_latency_system(
+        mut diagnostics: Diagnostics,
+        mut raw_events: EventReader<RawWinitEvent>,
+    ) {
+        let now = Instant::now();
+        let latency = raw_events
+            .read()
+            .filter(|RawWinitEvent { event, .. }| {
+                matches!(
+                    event,
+                    Event::WindowEvent {
+                        event: WindowEvent::KeyboardInput { .. }
+                            | WindowEvent::MouseInput { .. }
+                            | WindowEvent::MouseWheel { .. }
+                            | WindowEvent::CursorMoved { .. }
+                            | WindowEvent::Touch(_),
+                        ..
+                    }
+                )
+            })
+            .map(|raw_event| now.saturating_duration_since(raw_event.received))
+            .max();
+        if let Some(latency) = latency {
+            diagnostics.add_measurement(&Self::INPUT_LATENCY, || latency.as_secs_f64() * 1000.0);
+        }
+    }
+}
@@ -0,0 +1,312 @@
+use bevy_app::{App, AppExit, Plugin, PostUpdate};
+use bevy_ecs::prelude::*;
+use bevy_utils::tracing::warn;
+use bevy_window::{
+    ClosingWindow, ExitCondition, PrimaryWindow, Window, WindowCloseRequested, WindowPlugin,
+};
+
+/// Opt-in plugin asking the app to confirm before windows close or the app exits, e.g. to offer
+/// saving unsaved work.
+///
+/// The requests to close a window and the [`AppExit`] events, including those sent for a
+/// [`WinitControlFlow::Exit`](crate::WinitControlFlow::Exit), are held back, and an
+/// [`ExitRequested`] event is sent instead. The request then proceeds once the app sends
+/// [`ConfirmExit`], and is dropped once it sends [`CancelExit`]. Which requests are held back is
+/// set by the [`ExitConfirmation`] resource.
+///
+/// The [`AppExit`] events are held back by the `winit` runner, once the update sending them
+/// ended. Those following the windows being closed, as set by the [`ExitCondition`], are let
+/// through: closing the windows was already confirmed. So is the exit of an app the OS
+/// terminates, which sends [`AppTerminating`](crate::AppTerminating) as it can't be held back.
+///
+/// The [`WindowPlugin`] must be added with
+/// [`close_when_requested`](WindowPlugin::close_when_requested) set to `false`, otherwise windows
+/// close without waiting for the answer.
+///
+/// ```no_run
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_winit::{ConfirmExit, ExitRequested};
+/// fn confirm_exit(mut requests: EventReader<ExitRequested>, mut confirm: EventWriter<ConfirmExit>) {
+///     for ExitRequested { request } in requests.read() {
+///         // Show a dialog here, and answer once the user made their choice.
+///         confirm.send(ConfirmExit {
+///             request: request.clone(),
+///         });
+///     }
+/// }
+/// ```
+#[derive(Default)]
+pub struct ExitConfirmationPlugin;
+
+impl Plugin for ExitConfirmationPlugin {
+    fn build(&self, app: &mut App) {
+        if app
+            .get_added_plugins::<WindowPlugin>()
+            .iter()
+            .any(|plugin| plugin.close_when_requested)
+        {
+            warn!(
+                "`ExitConfirmationPlugin` needs `WindowPlugin::close_when_requested` to be \
+                 `false`, windows will close without confirmation"
+            );
+        }
+
+        app.init_resource::<ExitConfirmation>()
+            .add_event::<ExitRequested>()
+            .add_event::<ConfirmExit>()
+            .add_event::<CancelExit>()
+            .add_systems(PostUpdate, request_exit_confirmation);
+    }
+}
+
+/// Resource setting which requests the [`ExitConfirmationPlugin`] holds back.
+#[derive(Resource, Debug, Clone)]
+pub struct ExitConfirmation {
+    /// Hold back [`WindowCloseRequested`] events.
+    pub window_close: bool,
+    /// Hold back [`AppExit`] events.
+    pub app_exit: bool,
+    pending: Vec<ExitRequest>,
+    confirmed_exit: bool,
+}
+
+impl Default for ExitConfirmation {
+    fn default() -> Self {
+        Self {
+            window_close: true,
+            app_exit: true,
+            pending: Vec::new(),
+            confirmed_exit: false,
+        }
+    }
+}
+
+impl ExitConfirmation {
+    /// Returns the requests waiting for an answer, in the order they were made.
+    pub fn pending(&self) -> &[ExitRequest] {
+        &self.pending
+    }
+
+    /// Called by the runner with the `exit` the app is about to make, returning `true` if the
+    /// [`ExitConfirmationPlugin`] holds it back.
+    ///
+    /// The [`AppExit`] events are then cleared, so the app keeps running until the exit is
+    /// confirmed.
+    pub(crate) fn hold_back_exit(world: &mut World, exit: &AppExit) -> bool {
+        let Some(mut confirmation) = world.get_resource_mut::<ExitConfirmation>() else {
+            return false;
+        };
+        if std::mem::take(&mut confirmation.confirmed_exit) || !confirmation.app_exit {
+            return false;
+        }
+        let windows_closed = match world.get_resource::<ExitCondition>() {
+            Some(ExitCondition::OnPrimaryClosed) => world
+                .query_filtered::<(), (With<Window>, With<PrimaryWindow>)>()
+                .iter(world)
+                .next()
+                .is_none(),
+            _ => world
+                .query_filtered::<(), With<Window>>()
+                .iter(world)
+                .next()
+                .is_none(),
+        };
+        if windows_closed {
+            return false;
+        }
+
+        world.resource_mut::<Events<AppExit>>().clear();
+        let request = ExitRequest::AppExit(exit.clone());
+        if world
+            .resource_mut::<ExitConfirmation>()
+            .hold_back(request.clone())
+        {
+            world.send_event(ExitRequested { request });
+        }
+        true
+    }
+
+    /// Holds `request` back, returning `true` if it wasn't already pending.
+    fn hold_back(&mut self, request: ExitRequest) -> bool {
+        if self.pending.contains(&request) {
+            return false;
+        }
+        self.pending.push(request);
+        true
+    }
+
+    /// Forgets `request`, returning `true` if it was pending.
+    fn answer(&mut self, request: &ExitRequest) -> bool {
+        let len = self.pending.len();
+        self.pending.retain(|pending| pending != request);
+        self.pending.len() != len
+    }
+}
+
+/// A request held back by the [`ExitConfirmationPlugin`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExitRequest {
+    /// The user asked to close this window.
+    WindowClose(Entity),
+    /// The app sent this [`AppExit`] event.
+    AppExit(AppExit),
+}
+
+/// Sent when the [`ExitConfirmationPlugin`] holds a request back, waiting for a [`ConfirmExit`]
+/// or [`CancelExit`] answer.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct ExitRequested {
+    /// The request held back.
+    pub request: ExitRequest,
+}
+
+/// Send this event to let a request held back by the [`ExitConfirmationPlugin`] proceed.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct ConfirmExit {
+    /// The request to proceed with, as sent in [`ExitRequested`].
+    pub request: ExitRequest,
+}
+
+/// Send this event to drop a request held back by the [`ExitConfirmationPlugin`].
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct CancelExit {
+    /// The request to drop, as sent in [`ExitRequested`].
+    pub request: ExitRequest,
+}
+
+/// Holds back the close requests, and applies the answers.
+#[allow(clippy::too_many_arguments)]
+fn request_exit_confirmation(
+    mut commands: Commands,
+    mut confirmation: ResMut<ExitConfirmation>,
+    mut close_requested: EventReader<WindowCloseRequested>,
+    mut confirmed: EventReader<ConfirmExit>,
+    mut cancelled: EventReader<CancelExit>,
+    mut exit_requested: EventWriter<ExitRequested>,
+    mut app_exit: EventWriter<AppExit>,
+    windows: Query<(), (With<Window>, Without<ClosingWindow>)>,
+) {
+    for event in close_requested.read() {
+        let request = ExitRequest::WindowClose(event.window);
+        if confirmation.window_close && confirmation.hold_back(request.clone()) {
+            exit_requested.send(ExitRequested { request });
+        }
+    }
+
+    for ConfirmExit { request } in confirmed.read() {
+        if !confirmation.answer(request) {
+            continue;
+        }
+        match request {
+            ExitRequest::WindowClose(window) => {
+                if windows.contains(*window) {
+                    commands.entity(*window).insert(ClosingWindow);
+                }
+            }
+            ExitRequest::AppExit(exit) => {
+                confirmation.confirmed_exit = true;
+                app_exit.send(exit.clone());
+            }
+        }
+    }
+    for CancelExit { request } in cancelled.read() {
+        confirmation.answer(request);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::event::Events;
+
+    use super::*;
+    use crate::{HeadlessWindows, HeadlessWindowsPlugin};
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            WindowPlugin {
+                close_when_requested: false,
+                ..Default::default()
+            },
+            HeadlessWindowsPlugin::default(),
+            ExitConfirmationPlugin,
+        ));
+        app.update();
+        app
+    }
+
+    fn requests(app: &mut App) -> Vec<ExitRequest> {
+        app.world_mut()
+            .resource_mut::<Events<ExitRequested>>()
+            .drain()
+            .map(|event| event.request)
+            .collect()
+    }
+
+    /// Returns the exit the runner would make after this update.
+    fn runner_exit(app: &mut App) -> Option<AppExit> {
+        app.should_exit()
+            .filter(|exit| !ExitConfirmation::hold_back_exit(app.world_mut(), exit))
+    }
+
+    #[test]
+    fn closing_the_primary_window_asks_once() {
+        let mut app = app();
+        let window = app
+            .world_mut()
+            .query_filtered::<Entity, With<PrimaryWindow>>()
+            .single(app.world());
+
+        app.world_mut()
+            .non_send_resource_mut::<HeadlessWindows>()
+            .request_close(window);
+        app.update();
+        let request = ExitRequest::WindowClose(window);
+        assert_eq!(requests(&mut app), vec![request.clone()]);
+
+        app.world_mut().send_event(ConfirmExit { request });
+        for _ in 0..3 {
+            app.update();
+            assert_eq!(runner_exit(&mut app), None);
+        }
+        app.update();
+        assert!(app.world().get_entity(window).is_none());
+        assert_eq!(runner_exit(&mut app), Some(AppExit::Success));
+        assert!(requests(&mut app).is_empty());
+    }
+
+    #[test]
+    fn app_exit_waits_for_the_answer() {
+        let mut app = app();
+        let request = ExitRequest::AppExit(AppExit::Success);
+
+        app.world_mut().send_event(AppExit::Success);
+        app.update();
+        assert_eq!(runner_exit(&mut app), None);
+        app.update();
+        assert_eq!(requests(&mut app), vec![request.clone()]);
+
+        app.world_mut().send_event(CancelExit {
+            request: request.clone(),
+        });
+        app.update();
+        assert_eq!(runner_exit(&mut app), None);
+        assert!(app
+            .world()
+            .resource::<ExitConfirmation>()
+            .pending()
+            .is_empty());
+
+        // The answer only applies to the request it was given for.
+        app.world_mut().send_event(AppExit::Success);
+        app.update();
+        assert_eq!(runner_exit(&mut app), None);
+        app.update();
+        assert_eq!(requests(&mut app), vec![request.clone()]);
+
+        app.world_mut().send_event(ConfirmExit { request });
+        app.update();
+        assert_eq!(runner_exit(&mut app), Some(AppExit::Success));
+        assert!(!app.world().resource::<ExitConfirmation>().confirmed_exit);
+    }
+}
@@ -0,0 +1,470 @@
+use bevy_app::{App, First, Last, Plugin};
+use bevy_ecs::{entity::EntityHashMap, prelude::*, system::EntityCommands};
+use bevy_math::{UVec2, Vec2};
+use bevy_utils::tracing::warn;
+use bevy_window::{
+    Window, WindowBackendScaleFactorChanged, WindowCloseRequested, WindowCreated, WindowFocused,
+    WindowResized, WindowScaleFactorChanged, WindowTheme,
+};
+use winit::dpi::PhysicalSize;
+
+use crate::{
+    backend::{BackendWindow, WindowBackend},
+    system::{
+        changed_windows, create_backend_windows, despawn_windows, report_focus_requests,
+        CachedWindow, PendingFocusRequests,
+    },
+};
+
+/// A stand-in for the [`WinitPlugin`](crate::WinitPlugin) simulating the windows, so plugins
+/// managing windows can be tested without a display server, e.g. in CI.
+///
+/// `winit` can't create windows without a display server, so this plugin runs the systems the
+/// [`WinitPlugin`](crate::WinitPlugin) uses to create, update and close the windows on simulated
+/// windows instead: windows spawned are given an id and a scale factor and a [`WindowCreated`]
+/// event is sent, size and focus requests are granted, and closed windows go through the same
+/// lifecycle as real ones, see [`ClosingWindow`](bevy_window::ClosingWindow). The events the OS
+/// would send can be simulated with the [`HeadlessWindows`] resource.
+///
+/// The app is updated manually, and the [`WinitPlugin`](crate::WinitPlugin) must not be added.
+///
+/// ```
+/// # use bevy_app::App;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::UVec2;
+/// # use bevy_window::{Window, WindowPlugin, WindowResolution};
+/// # use bevy_winit::{HeadlessWindows, HeadlessWindowsPlugin};
+/// let mut app = App::new();
+/// app.add_plugins((
+///     WindowPlugin::default(),
+///     HeadlessWindowsPlugin { scale_factor: 2.0 },
+/// ));
+/// let window = app
+///     .world_mut()
+///     .spawn(Window {
+///         resolution: WindowResolution::new(400.0, 300.0),
+///         ..Default::default()
+///     })
+///     .id();
+/// app.update();
+/// assert_eq!(app.world().non_send_resource::<HeadlessWindows>().len(), 2);
+///
+/// // Simulate moving the window to a monitor with a lower scale factor.
+/// app.world_mut()
+///     .non_send_resource_mut::<HeadlessWindows>()
+///     .set_scale_factor(window, 1.0);
+/// app.update();
+/// let resolution = &app.world().get::<Window>(window).unwrap().resolution;
+/// assert_eq!(resolution.physical_size(), UVec2::new(400, 300));
+/// ```
+pub struct HeadlessWindowsPlugin {
+    /// The scale factor of the simulated windows when they are created.
+    pub scale_factor: f32,
+}
+
+impl Default for HeadlessWindowsPlugin {
+    fn default() -> Self {
+        Self { scale_factor: 1.0 }
+    }
+}
+
+impl Plugin for HeadlessWindowsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_non_send_resource(HeadlessWindows {
+            scale_factor: self.scale_factor,
+            ..Default::default()
+        })
+        .init_resource::<PendingFocusRequests>()
+        .add_systems(First, apply_simulated_events)
+        .add_systems(
+            Last,
+            (
+                create_headless_windows,
+                changed_windows::<HeadlessWindows>,
+                report_focus_requests::<HeadlessWindows>,
+                despawn_windows::<HeadlessWindows>,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Non-send resource holding the windows simulated by the [`HeadlessWindowsPlugin`], and the OS
+/// events to simulate for them.
+///
+/// Simulated events are applied at the start of the next update.
+///
+/// Use `NonSendMut<HeadlessWindows>` to access this resource.
+#[derive(Debug, Default)]
+pub struct HeadlessWindows {
+    scale_factor: f32,
+    windows: EntityHashMap<HeadlessWindow>,
+    next_id: u64,
+    pending: Vec<(Entity, SimulatedEvent)>,
+}
+
+/// A window simulated by the [`HeadlessWindowsPlugin`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeadlessWindow {
+    /// A unique id, standing in for the window id of `winit`.
+    pub id: u64,
+    /// The scale factor of the monitor the window is on.
+    pub scale_factor: f32,
+    /// The size of the window's client area, in physical pixels.
+    pub physical_size: UVec2,
+    /// Whether the window has the input focus.
+    pub focused: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum SimulatedEvent {
+    ScaleFactorChanged(f32),
+    Resized(UVec2),
+    CloseRequested,
+}
+
+impl HeadlessWindows {
+    /// Returns the simulated window of the `entity`.
+    pub fn get(&self, entity: Entity) -> Option<&HeadlessWindow> {
+        self.windows.get(&entity)
+    }
+
+    /// Returns an iterator over the simulated windows and their entities.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &HeadlessWindow)> {
+        self.windows
+            .iter()
+            .map(|(&entity, window)| (entity, window))
+    }
+
+    /// Returns the number of simulated windows.
+    pub fn len(&self) -> usize {
+        self.windows.len()
+    }
+
+    /// Returns `true` if no window is simulated.
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    /// Simulates the window being moved to a monitor with another scale factor.
+    pub fn set_scale_factor(&mut self, entity: Entity, scale_factor: f32) {
+        self.pending
+            .push((entity, SimulatedEvent::ScaleFactorChanged(scale_factor)));
+    }
+
+    /// Simulates the user resizing the window to `physical_size`.
+    pub fn resize(&mut self, entity: Entity, physical_size: UVec2) {
+        self.pending
+            .push((entity, SimulatedEvent::Resized(physical_size)));
+    }
+
+    /// Simulates the user clicking the close button of the window.
+    pub fn request_close(&mut self, entity: Entity) {
+        self.pending.push((entity, SimulatedEvent::CloseRequested));
+    }
+}
+
+/// Applies the simulated events to the windows, like the runner does with the events of `winit`.
+#[allow(clippy::too_many_arguments)]
+fn apply_simulated_events(
+    mut headless_windows: NonSendMut<HeadlessWindows>,
+    mut windows: Query<(Entity, &mut Window, &mut CachedWindow)>,
+    mut resized: EventWriter<WindowResized>,
+    mut backend_scale_factor_changed: EventWriter<WindowBackendScaleFactorChanged>,
+    mut scale_factor_changed: EventWriter<WindowScaleFactorChanged>,
+    mut close_requested: EventWriter<WindowCloseRequested>,
+    mut focused: EventWriter<WindowFocused>,
+) {
+    let headless_windows = &mut *headless_windows;
+    for (entity, event) in headless_windows.pending.drain(..) {
+        let (Some(headless_window), Ok((_, mut window, mut cache))) = (
+            headless_windows.windows.get_mut(&entity),
+            windows.get_mut(entity),
+        ) else {
+            warn!("Skipped simulated event {event:?} for unknown window {entity:?}");
+            continue;
+        };
+
+        match event {
+            SimulatedEvent::ScaleFactorChanged(scale_factor) => {
+                let prior_factor = window.resolution.scale_factor();
+                let prior_base_factor = window.resolution.base_scale_factor();
+                let prior_size = Vec2::new(window.width(), window.height());
+                let prior_physical_size = window.physical_size().as_vec2();
+                window.resolution.set_scale_factor(scale_factor);
+                let new_factor = window.resolution.scale_factor();
+
+                // Like `winit`, keep the logical size of the window.
+                let physical_size = if window.resolution.scale_factor_override().is_some() {
+                    prior_size * new_factor
+                } else {
+                    prior_physical_size / prior_base_factor * scale_factor
+                }
+                .round()
+                .as_uvec2();
+                window
+                    .resolution
+                    .set_physical_resolution(physical_size.x, physical_size.y);
+                headless_window.scale_factor = scale_factor;
+                headless_window.physical_size = physical_size;
+
+                backend_scale_factor_changed.send(WindowBackendScaleFactorChanged {
+                    window: entity,
+                    scale_factor: scale_factor as f64,
+                });
+                if window.resolution.scale_factor_override().is_none() && new_factor != prior_factor
+                {
+                    scale_factor_changed.send(WindowScaleFactorChanged {
+                        window: entity,
+                        scale_factor: new_factor as f64,
+                    });
+                }
+                if Vec2::new(window.width(), window.height()) != prior_size {
+                    resized.send(WindowResized {
+                        window: entity,
+                        width: window.width(),
+                        height: window.height(),
+                    });
+                }
+            }
+            SimulatedEvent::Resized(physical_size) => {
+                window
+                    .resolution
+                    .set_physical_resolution(physical_size.x, physical_size.y);
+                headless_window.physical_size = physical_size;
+                resized.send(WindowResized {
+                    window: entity,
+                    width: window.width(),
+                    height: window.height(),
+                });
+            }
+            SimulatedEvent::CloseRequested => {
+                close_requested.send(WindowCloseRequested { window: entity });
+            }
+        }
+        // The window now matches its simulated window, there is nothing to apply to it.
+        cache.window = window.clone();
+    }
+
+    // Report the focus granted to the windows.
+    for (entity, mut window, mut cache) in &mut windows {
+        let Some(headless_window) = headless_windows.windows.get(&entity) else {
+            continue;
+        };
+        if window.focused != headless_window.focused {
+            window.focused = headless_window.focused;
+            cache.window.focused = headless_window.focused;
+            focused.send(WindowFocused {
+                window: entity,
+                focused: headless_window.focused,
+            });
+        }
+    }
+}
+
+fn create_headless_windows(
+    mut commands: Commands,
+    mut headless_windows: NonSendMut<HeadlessWindows>,
+    mut created_windows: Query<(Entity, &mut Window), Added<Window>>,
+    mut window_created: EventWriter<WindowCreated>,
+) {
+    create_backend_windows(
+        &mut *headless_windows,
+        &mut (),
+        &mut commands,
+        &mut created_windows,
+        &mut window_created,
+    );
+}
+
+impl WindowBackend for HeadlessWindows {
+    type Window = HeadlessWindow;
+    type CreateContext<'a> = ();
+
+    fn create_window(
+        &mut self,
+        _context: &mut (),
+        entity: Entity,
+        window: &Window,
+        _commands: &mut EntityCommands,
+    ) -> &mut HeadlessWindow {
+        // Windows are created with their logical size.
+        let mut resolution = window.resolution.clone();
+        resolution.set_scale_factor(self.scale_factor);
+        let headless_window = HeadlessWindow {
+            id: self.next_id,
+            scale_factor: self.scale_factor,
+            physical_size: resolution.physical_size(),
+            focused: window.focused,
+        };
+        self.next_id += 1;
+        self.windows
+            .entry(entity)
+            .insert(headless_window)
+            .into_mut()
+    }
+
+    fn window(&self, entity: Entity) -> Option<&HeadlessWindow> {
+        self.windows.get(&entity)
+    }
+
+    fn window_mut(&mut self, entity: Entity) -> Option<&mut HeadlessWindow> {
+        self.windows.get_mut(&entity)
+    }
+
+    fn destroy_window(&mut self, entity: Entity) -> bool {
+        self.windows.remove(&entity).is_some()
+    }
+}
+
+impl BackendWindow for HeadlessWindow {
+    fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    fn theme(&self) -> Option<WindowTheme> {
+        None
+    }
+
+    fn has_focus(&self) -> bool {
+        self.focused
+    }
+
+    // The simulated windows are always granted the focus.
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    // The simulated windows accept any size requested.
+    fn request_inner_size(&mut self, size: PhysicalSize<u32>) -> Option<PhysicalSize<u32>> {
+        self.physical_size = UVec2::new(size.width, size.height);
+        Some(size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_ecs::event::{Event, Events};
+    use bevy_window::{
+        ScaleFactorPreference, WindowClosed, WindowClosing, WindowDestroyed,
+        WindowFocusRequestCompleted, WindowPlugin, WindowResizeConstraints, WindowResolution,
+        WindowSizeConstrained,
+    };
+
+    use super::*;
+
+    fn app(scale_factor: f32) -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            WindowPlugin {
+                primary_window: None,
+                ..Default::default()
+            },
+            HeadlessWindowsPlugin { scale_factor },
+        ));
+        app
+    }
+
+    fn drain<E: Event>(app: &mut App) -> Vec<E> {
+        app.world_mut()
+            .resource_mut::<Events<E>>()
+            .drain()
+            .collect()
+    }
+
+    #[test]
+    fn window_changes_are_applied() {
+        let mut app = app(1.0);
+        let window = app
+            .world_mut()
+            .spawn(Window {
+                resolution: WindowResolution::new(400.0, 300.0),
+                resize_constraints: WindowResizeConstraints {
+                    max_width: 500.0,
+                    ..Default::default()
+                },
+                focused: false,
+                ..Default::default()
+            })
+            .id();
+        app.update();
+        assert_eq!(drain::<WindowCreated>(&mut app).len(), 1);
+
+        let mut window_mut = app.world_mut().get_mut::<Window>(window).unwrap();
+        window_mut.resolution.set(600.0, 300.0);
+        window_mut.request_focus();
+        app.update();
+
+        let resized = drain::<WindowResized>(&mut app);
+        assert_eq!(resized.len(), 1);
+        assert_eq!((resized[0].width, resized[0].height), (600.0, 300.0));
+        let constrained = drain::<WindowSizeConstrained>(&mut app);
+        assert_eq!(constrained.len(), 1);
+        assert_eq!(constrained[0].constrained, Vec2::new(500.0, 300.0));
+        let headless_windows = app.world().non_send_resource::<HeadlessWindows>();
+        assert_eq!(
+            headless_windows.get(window).unwrap().physical_size,
+            UVec2::new(600, 300)
+        );
+        let completed = drain::<WindowFocusRequestCompleted>(&mut app);
+        assert_eq!(completed.len(), 1);
+        assert!(completed[0].granted);
+
+        // The focus is reported at the start of the next update, like the events of `winit`.
+        app.update();
+        assert_eq!(drain::<WindowFocused>(&mut app).len(), 1);
+        assert!(app.world().get::<Window>(window).unwrap().focused);
+        assert!(drain::<WindowResized>(&mut app).is_empty());
+    }
+
+    #[test]
+    fn closed_windows_go_through_the_lifecycle() {
+        let mut app = app(1.0);
+        let window = app.world_mut().spawn(Window::default()).id();
+        app.update();
+
+        app.world_mut()
+            .non_send_resource_mut::<HeadlessWindows>()
+            .request_close(window);
+        app.update();
+        assert_eq!(drain::<WindowCloseRequested>(&mut app).len(), 1);
+        assert_eq!(drain::<WindowClosing>(&mut app).len(), 1);
+
+        app.update();
+        assert_eq!(drain::<WindowDestroyed>(&mut app).len(), 1);
+        assert!(app
+            .world()
+            .non_send_resource::<HeadlessWindows>()
+            .is_empty());
+
+        app.update();
+        assert_eq!(drain::<WindowClosed>(&mut app).len(), 1);
+        assert!(app.world().get_entity(window).is_none());
+    }
+
+    #[test]
+    fn scale_factor_changes_report_the_effective_factor() {
+        let mut app = app(1.0);
+        let mut resolution = WindowResolution::new(400.0, 300.0);
+        resolution.set_scale_factor_preference(ScaleFactorPreference::Integer);
+        let window = app
+            .world_mut()
+            .spawn(Window {
+                resolution,
+                ..Default::default()
+            })
+            .id();
+        app.update();
+
+        app.world_mut()
+            .non_send_resource_mut::<HeadlessWindows>()
+            .set_scale_factor(window, 1.6);
+        app.update();
+        let changed = drain::<WindowScaleFactorChanged>(&mut app);
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].scale_factor, 2.0);
+        let backend_changed = drain::<WindowBackendScaleFactorChanged>(&mut app);
+        assert_eq!(backend_changed[0].scale_factor, 1.6f32 as f64);
+    }
+}
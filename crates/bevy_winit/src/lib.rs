@@ -13,59 +13,122 @@
 //! See `winit_runner` for details.
 
 pub mod accessibility;
+mod app_terminating;
+mod backend;
+mod channel;
 mod converters;
+mod diagnostics;
+mod exit_confirmation;
+mod headless;
+mod main_thread;
+mod monitors;
+#[cfg(feature = "panic_dialog")]
+mod panic_dialog;
+mod replay;
+#[cfg(all(feature = "settings_file", not(target_arch = "wasm32")))]
+mod settings_file;
+mod shutdown;
+#[cfg(feature = "bevy_state")]
+mod state_overrides;
+#[cfg(feature = "bevy_state")]
+mod state_update_modes;
+#[cfg(feature = "bevy_state")]
+mod state_window_overrides;
 mod system;
+mod system_idle;
+mod waker;
+mod window_drag;
+mod window_handle;
+mod window_hit_test;
+mod window_snapping;
+mod window_state_log;
 mod winit_config;
 pub mod winit_event;
 mod winit_windows;
 
+use std::borrow::Cow;
+use std::ffi::c_void;
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
 use std::sync::mpsc::{sync_channel, SyncSender};
+use std::sync::Arc;
 
-use approx::relative_eq;
+pub use app_terminating::AppTerminating;
 use bevy_a11y::AccessibilityRequested;
-use bevy_utils::Instant;
+use bevy_utils::{Duration, Instant};
+pub use channel::*;
+pub use diagnostics::WinitDiagnosticsPlugin;
+pub use exit_confirmation::{
+    CancelExit, ConfirmExit, ExitConfirmation, ExitConfirmationPlugin, ExitRequest, ExitRequested,
+};
+pub use headless::{HeadlessWindow, HeadlessWindows, HeadlessWindowsPlugin};
+pub use main_thread::MainThreadExecutor;
+use main_thread::MainThreadTasks;
+use monitors::MonitorTracker;
+#[cfg(feature = "panic_dialog")]
+pub use panic_dialog::PanicDialogPlugin;
+pub use replay::{RecordedFrame, WinitRecording, WinitReplay, WinitReplayPlugin};
+#[cfg(all(feature = "settings_file", not(target_arch = "wasm32")))]
+pub use settings_file::WinitSettingsFilePlugin;
+pub use shutdown::{ShutdownCause, ShutdownHook, WinitShutdownHooks};
+#[cfg(feature = "bevy_state")]
+pub use state_overrides::{StateOverride, StateOverrides, StateOverridesPlugin};
+#[cfg(feature = "bevy_state")]
+pub use state_update_modes::{StateUpdateModes, StateUpdateModesPlugin};
+#[cfg(feature = "bevy_state")]
+pub use state_window_overrides::{
+    StateWindowOverrides, StateWindowOverridesPlugin, WindowOverrides,
+};
 pub use system::create_windows;
-use system::{changed_windows, despawn_windows, CachedWindow};
-use winit::dpi::{LogicalSize, PhysicalSize};
+use system::{
+    changed_windows, close_splash_windows, despawn_windows, report_focus_requests,
+    report_surface_changes, retry_cursor_grabs, translate_winit_events, update_any_window_focused,
+    PendingFocusRequests,
+};
+pub use system_idle::*;
+pub use waker::WinitWaker;
+pub use window_drag::*;
+use window_handle::update_window_handles;
+pub use window_handle::WinitWindowHandle;
+pub use window_hit_test::*;
+pub use window_snapping::*;
+pub use window_state_log::*;
+use winit::dpi::LogicalSize;
 pub use winit_config::*;
 pub use winit_event::*;
 pub use winit_windows::*;
 
-use bevy_app::{App, AppExit, Last, Plugin, PluginsState};
+use bevy_app::{App, AppExit, First, Last, MainScheduleOrder, Plugin, PluginsState};
+use bevy_ecs::entity::EntityHashSet;
 use bevy_ecs::event::ManualEventReader;
 use bevy_ecs::prelude::*;
 use bevy_ecs::system::SystemState;
-use bevy_input::{
-    mouse::{MouseButtonInput, MouseMotion, MouseScrollUnit, MouseWheel},
-    touchpad::{TouchpadMagnify, TouchpadRotate},
-};
-use bevy_math::{ivec2, DVec2, Vec2};
+use bevy_input::Timestamped;
 #[cfg(not(target_arch = "wasm32"))]
 use bevy_tasks::tick_global_task_pools_on_main_thread;
+use bevy_time::{Fixed, Real, Time, Virtual};
 use bevy_utils::tracing::{error, trace, warn};
-#[allow(deprecated)]
 use bevy_window::{
-    exit_on_all_closed, ApplicationLifetime, CursorEntered, CursorLeft, CursorMoved,
-    FileDragAndDrop, Ime, ReceivedCharacter, RequestRedraw, Window,
-    WindowBackendScaleFactorChanged, WindowCloseRequested, WindowCreated, WindowDestroyed,
-    WindowFocused, WindowMoved, WindowOccluded, WindowResized, WindowScaleFactorChanged,
-    WindowThemeChanged,
+    exit_on_all_closed, CursorMoved, ExitCondition, PresentFeedback, RequestRedraw, Window,
+    WindowCreated, WindowResized,
 };
 #[cfg(target_os = "android")]
 use bevy_window::{PrimaryWindow, RawHandleWrapper};
+#[cfg(target_os = "android")]
+use system::CachedWindow;
 
 #[cfg(target_os = "android")]
 pub use winit::platform::android::activity as android_activity;
 
 use winit::event::StartCause;
 use winit::{
-    event::{self, DeviceEvent, Event, WindowEvent},
+    event::{Event, WindowEvent},
     event_loop::{ControlFlow, EventLoop, EventLoopBuilder, EventLoopWindowTarget},
 };
 
-use crate::accessibility::{AccessKitAdapters, AccessKitPlugin, WinitActionHandlers};
-
-use crate::converters::convert_winit_theme;
+use crate::accessibility::{
+    AccessKitAdapters, AccessKitEnabled, AccessKitPlugin, WinitActionHandlers,
+};
 
 /// [`AndroidApp`] provides an interface to query the application state as well as monitor events
 /// (for example lifecycle and input events).
@@ -79,7 +142,6 @@ pub static ANDROID_APP: std::sync::OnceLock<android_activity::AndroidApp> =
 /// This plugin will add systems and resources that sync with the `winit` backend and also
 /// replace the existing [`App`] runner with one that constructs an [event loop](EventLoop) to
 /// receive window and input events from the OS.
-#[derive(Default)]
 pub struct WinitPlugin {
     /// Allows the window (and the event loop) to be created on any thread
     /// instead of only the main thread.
@@ -91,6 +153,174 @@ pub struct WinitPlugin {
     /// Only works on Linux (X11/Wayland) and Windows.
     /// This field is ignored on other platforms.
     pub run_on_any_thread: bool,
+    /// Whether the process should be marked as DPI aware, letting the OS report the real scale
+    /// factor of each monitor instead of scaling the window's contents itself.
+    ///
+    /// Disable this for apps that handle DPI entirely by themselves, or that are embedded in a
+    /// host process whose DPI awareness must not be changed.
+    ///
+    /// See `winit::platform::windows::EventLoopBuilderExtWindows::with_dpi_aware` for more
+    /// information on this.
+    ///
+    /// # Supported platforms
+    ///
+    /// Only works on Windows.
+    /// This field is ignored on other platforms.
+    pub dpi_aware: bool,
+    /// When the app should exit as its windows are closed.
+    ///
+    /// `Some` replaces the [`ExitCondition`] set by the [`WindowPlugin`](bevy_window::WindowPlugin),
+    /// which must be added before this plugin. `None` keeps it unchanged.
+    pub exit_condition: Option<ExitCondition>,
+    /// A hook called with every raw `MSG` received by the event loop, before `winit` handles it.
+    ///
+    /// The hook receives a pointer to the `MSG`, and returns `true` to prevent `winit` from
+    /// handling the message. This lets advanced users observe messages `winit` doesn't translate
+    /// (e.g. `WM_POWERBROADCAST` or custom shell messages).
+    ///
+    /// See `winit::platform::windows::EventLoopBuilderExtWindows::with_msg_hook` for more
+    /// information on this.
+    ///
+    /// # Supported platforms
+    ///
+    /// Only works on Windows.
+    /// This field is ignored on other platforms.
+    pub windows_message_hook: Option<WindowsMessageHook>,
+    /// The [`WinitSettings`] to start with.
+    ///
+    /// `None` keeps the [`WinitSettings`] resource inserted before this plugin, or the default
+    /// settings if there is none.
+    pub settings: Option<WinitSettings>,
+    /// Filters added to the [`WinitEventFilters`], with their label.
+    pub event_filters: Vec<(Cow<'static, str>, WinitPluginEventFilter)>,
+    /// Whether the `AccessKit` integration starts active, see [`AccessKitEnabled`].
+    ///
+    /// Disable this for performance-critical builds that don't need to support assistive
+    /// technologies. The integration can be toggled at runtime with the [`AccessKitEnabled`]
+    /// resource.
+    pub accessibility: bool,
+    /// Whether to send every raw [`winit`] event received by the runner as a [`RawWinitEvent`].
+    ///
+    /// This is off by default, as cloning every event has a cost that only apps using events Bevy
+    /// doesn't translate should pay.
+    pub raw_events: bool,
+}
+
+/// A hook observing the raw messages of the Windows event loop, see
+/// [`WinitPlugin::windows_message_hook`].
+pub type WindowsMessageHook = Arc<dyn Fn(*const c_void) -> bool + Send + Sync>;
+
+/// A filter of the raw [`winit`] events added by the [`WinitPlugin`], see
+/// [`WinitPlugin::event_filters`].
+pub type WinitPluginEventFilter = Arc<dyn Fn(&Event<UserEvent>, &World) -> bool + Send + Sync>;
+
+impl Default for WinitPlugin {
+    fn default() -> Self {
+        Self {
+            run_on_any_thread: false,
+            dpi_aware: true,
+            exit_condition: None,
+            windows_message_hook: None,
+            settings: None,
+            event_filters: Vec::new(),
+            accessibility: true,
+            raw_events: false,
+        }
+    }
+}
+
+impl WinitPlugin {
+    /// Creates the plugin with the default configuration, to be customized with the `with_*`
+    /// methods.
+    ///
+    /// Apps starting without a window (e.g. tray apps, or apps restoring their windows
+    /// themselves) don't spawn the primary window of the [`WindowPlugin`](bevy_window::WindowPlugin),
+    /// and should also change the [`exit_condition`](Self::exit_condition), as the app exits as soon
+    /// as no window is open by default.
+    ///
+    /// ```no_run
+    /// # use bevy_app::App;
+    /// # use bevy_window::{ExitCondition, WindowPlugin};
+    /// # use bevy_winit::{WinitPlugin, WinitSettings};
+    /// # use winit::event::{DeviceEvent, Event};
+    /// App::new().add_plugins((
+    ///     WindowPlugin {
+    ///         primary_window: None,
+    ///         ..Default::default()
+    ///     },
+    ///     WinitPlugin::new()
+    ///         .with_settings(WinitSettings::desktop_app())
+    ///         .with_filter("no_mouse_motion", |event, _world| {
+    ///             !matches!(
+    ///                 event,
+    ///                 Event::DeviceEvent {
+    ///                     event: DeviceEvent::MouseMotion { .. },
+    ///                     ..
+    ///                 }
+    ///             )
+    ///         })
+    ///         .with_exit_condition(ExitCondition::DontExit),
+    /// ));
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets [`run_on_any_thread`](Self::run_on_any_thread).
+    pub fn with_any_thread(mut self, run_on_any_thread: bool) -> Self {
+        self.run_on_any_thread = run_on_any_thread;
+        self
+    }
+
+    /// Sets [`dpi_aware`](Self::dpi_aware).
+    pub fn with_dpi_aware(mut self, dpi_aware: bool) -> Self {
+        self.dpi_aware = dpi_aware;
+        self
+    }
+
+    /// Sets the [`exit_condition`](Self::exit_condition).
+    pub fn with_exit_condition(mut self, exit_condition: ExitCondition) -> Self {
+        self.exit_condition = Some(exit_condition);
+        self
+    }
+
+    /// Sets the [`windows_message_hook`](Self::windows_message_hook).
+    pub fn with_windows_message_hook(
+        mut self,
+        hook: impl Fn(*const c_void) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.windows_message_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Sets [`accessibility`](Self::accessibility).
+    pub fn with_accessibility(mut self, accessibility: bool) -> Self {
+        self.accessibility = accessibility;
+        self
+    }
+
+    /// Sets [`raw_events`](Self::raw_events).
+    pub fn with_raw_events(mut self, raw_events: bool) -> Self {
+        self.raw_events = raw_events;
+        self
+    }
+
+    /// Sets the [`WinitSettings`] to start with.
+    pub fn with_settings(mut self, settings: WinitSettings) -> Self {
+        self.settings = Some(settings);
+        self
+    }
+
+    /// Adds `filter` to the [`WinitEventFilters`] under `label`, see
+    /// [`event_filters`](Self::event_filters).
+    pub fn with_filter(
+        mut self,
+        label: impl Into<Cow<'static, str>>,
+        filter: impl Fn(&Event<UserEvent>, &World) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.event_filters.push((label.into(), Arc::new(filter)));
+        self
+    }
 }
 
 impl Plugin for WinitPlugin {
@@ -119,7 +349,12 @@ impl Plugin for WinitPlugin {
         #[cfg(target_os = "windows")]
         {
             use winit::platform::windows::EventLoopBuilderExtWindows;
-            event_loop_builder.with_any_thread(self.run_on_any_thread);
+            event_loop_builder
+                .with_any_thread(self.run_on_any_thread)
+                .with_dpi_aware(self.dpi_aware);
+            if let Some(hook) = self.windows_message_hook.clone() {
+                event_loop_builder.with_msg_hook(move |msg| hook(msg));
+            }
         }
 
         #[cfg(target_os = "android")]
@@ -129,22 +364,83 @@ impl Plugin for WinitPlugin {
             event_loop_builder.with_android_app(ANDROID_APP.get().expect(msg).clone());
         }
 
+        if let Some(exit_condition) = &self.exit_condition {
+            app.insert_resource(exit_condition.clone());
+        }
+        if let Some(settings) = &self.settings {
+            app.insert_resource(settings.clone());
+        }
+        for (label, filter) in &self.event_filters {
+            let filter = filter.clone();
+            app.add_winit_event_filter(label.clone(), move |event, world| filter(event, world));
+        }
+
         app.init_non_send_resource::<WinitWindows>()
+            .init_non_send_resource::<WindowBuilderHook>()
+            .init_non_send_resource::<WinitEventBuffer>()
+            .init_non_send_resource::<WinitEventHooks>()
+            .init_non_send_resource::<WinitShutdownHooks>()
+            .init_resource::<WinitEventInjector>()
+            .init_resource::<WinitEventFilters>()
+            .init_resource::<WinitUserEvents>()
             .init_resource::<WinitSettings>()
+            .init_resource::<WakeReason>()
+            .init_resource::<AnyWindowFocused>()
+            .init_resource::<PendingFocusRequests>()
+            .register_type::<AnyWindowFocused>()
+            .register_type::<AppTerminating>()
             .add_event::<WinitEvent>()
+            .add_event::<SequencedInput>()
+            .add_event::<Timestamped<CursorMoved>>()
+            .add_event::<WakeReason>()
+            .add_event::<UpdateModeChanged>()
+            .add_event::<WinitStartCause>()
+            .add_event::<MissingWindowEvent>()
+            .add_event::<AppWillExit>()
+            .add_event::<AppTerminating>()
             .set_runner(winit_runner)
+            .configure_sets(
+                WinitEvents,
+                (WinitEventSystem::Translate, WinitEventSystem::Forward).chain(),
+            )
+            .add_systems(
+                WinitEvents,
+                (
+                    (
+                        translate_winit_events,
+                        update_any_window_focused,
+                        update_window_handles,
+                    )
+                        .chain()
+                        .in_set(WinitEventSystem::Translate),
+                    forward_winit_events.in_set(WinitEventSystem::Forward),
+                ),
+            )
             .add_systems(
                 Last,
                 (
                     // `exit_on_all_closed` only checks if windows exist but doesn't access data,
                     // so we don't need to care about its ordering relative to `changed_windows`
-                    changed_windows.ambiguous_with(exit_on_all_closed),
-                    despawn_windows,
+                    changed_windows::<WinitWindows>.ambiguous_with(exit_on_all_closed),
+                    report_focus_requests::<WinitWindows>,
+                    retry_cursor_grabs,
+                    close_splash_windows,
+                    despawn_windows::<WinitWindows>,
+                    report_surface_changes,
                 )
                     .chain(),
             );
 
-        app.add_plugins(AccessKitPlugin);
+        if self.raw_events {
+            app.add_event::<RawWinitEvent>();
+        }
+
+        app.world_mut()
+            .resource_mut::<MainScheduleOrder>()
+            .insert_before(First, WinitEvents);
+
+        app.insert_resource(AccessKitEnabled(self.accessibility))
+            .add_plugins(AccessKitPlugin);
 
         let event_loop = event_loop_builder
             .build()
@@ -166,44 +462,68 @@ impl Plugin for WinitPlugin {
             create_window.apply(app.world_mut());
         }
 
+        // The proxy and the waker sharing it with other threads (see [`winit_channel`]) are
+        // available from the start.
+        app.insert_non_send_resource(event_loop.create_proxy());
+        let waker = WinitWaker::new(event_loop.create_proxy());
+        MainThreadTasks::init(app.world_mut(), waker.clone());
+        app.world_mut()
+            .resource_mut::<WinitEventInjector>()
+            .set_waker(waker.clone());
+        app.insert_resource(waker);
+
         // `winit`'s windows are bound to the event loop that created them, so the event loop must
         // be inserted as a resource here to pass it onto the runner.
         app.insert_non_send_resource(event_loop);
     }
 }
 
-trait AppSendEvent {
-    fn send(&mut self, event: impl Into<WinitEvent>);
-}
-impl AppSendEvent for Vec<WinitEvent> {
-    fn send(&mut self, event: impl Into<WinitEvent>) {
-        self.push(Into::<WinitEvent>::into(event));
-    }
-}
-
 /// Persistent state that is used to run the [`App`] according to the current
 /// [`UpdateMode`].
 struct WinitAppRunnerState {
     /// Current activity state of the app.
     activity_state: UpdateState,
-    /// Current update mode of the app.
-    update_mode: UpdateMode,
+    /// Current update mode of the app, `None` until the event loop first waits.
+    update_mode: Option<UpdateMode>,
     /// Is `true` if a new [`WindowEvent`] has been received since the last update.
     window_event_received: bool,
     /// Is `true` if a new [`DeviceEvent`] has been received since the last update.
     device_event_received: bool,
     /// Is `true` if the app has requested a redraw since the last update.
     redraw_requested: bool,
+    /// Is `true` if a [`RequestRedraw`] has been received since the last redraw broadcast.
+    redraw_event_received: bool,
+    /// Is `true` if the [`WinitWaker`] has woken up the event loop since the last update.
+    wake_requested: bool,
     /// Is `true` if enough time has elapsed since `last_update` to run another update.
     wait_elapsed: bool,
     /// Number of "forced" updates to trigger on application start
     startup_forced_updates: u32,
+    /// Reasons for the next update that are not tracked by the fields above.
+    wake_reason: WakeReason,
+    /// The last frame presentation used to pace the updates.
+    last_presented: Option<Instant>,
+    /// The instant to busy-wait for once the event loop wakes up early, as requested by
+    /// [`WaitStrategy::SleepAndSpin`].
+    spin_until: Option<Instant>,
+    /// When the last update started, and how long it took.
+    last_update: Option<(Instant, Duration)>,
+    /// When the next frame is due according to the [`FrameLimiter`].
+    next_frame: Option<Instant>,
+    /// Keeps the [`Monitors`](bevy_window::Monitors) resource up to date.
+    monitors: MonitorTracker,
+    /// The windows the OS reported as occluded.
+    occluded_windows: EntityHashSet,
+    /// The frame time pacing the updates while every window is paused, see
+    /// [`WinitSettings::pause_occluded_windows`].
+    paused_frame_time: Option<Duration>,
 }
 
 impl WinitAppRunnerState {
     fn reset_on_update(&mut self) {
         self.window_event_received = false;
         self.device_event_received = false;
+        self.wake_requested = false;
     }
 }
 
@@ -211,13 +531,23 @@ impl Default for WinitAppRunnerState {
     fn default() -> Self {
         Self {
             activity_state: UpdateState::NotYetStarted,
-            update_mode: UpdateMode::Continuous,
+            update_mode: None,
             window_event_received: false,
             device_event_received: false,
             redraw_requested: false,
+            redraw_event_received: false,
+            wake_requested: false,
             wait_elapsed: false,
             // 3 seems to be enough, 5 is a safe margin
             startup_forced_updates: 5,
+            wake_reason: WakeReason::default(),
+            last_presented: None,
+            spin_until: None,
+            last_update: None,
+            next_frame: None,
+            monitors: MonitorTracker::default(),
+            occluded_windows: EntityHashSet::default(),
+            paused_frame_time: None,
         }
     }
 }
@@ -250,6 +580,8 @@ pub type CreateWindowParams<'w, 's, F = ()> = (
     NonSendMut<'w, AccessKitAdapters>,
     ResMut<'w, WinitActionHandlers>,
     Res<'w, AccessibilityRequested>,
+    Res<'w, AccessKitEnabled>,
+    NonSend<'w, WindowBuilderHook>,
 );
 
 /// The [`winit::event_loop::EventLoopProxy`] with the specific [`winit::event::Event::UserEvent`] used in the [`winit_runner`].
@@ -276,9 +608,6 @@ pub fn winit_runner(mut app: App) -> AppExit {
         .remove_non_send_resource::<EventLoop<UserEvent>>()
         .unwrap();
 
-    app.world_mut()
-        .insert_non_send_resource(event_loop.create_proxy());
-
     let mut runner_state = WinitAppRunnerState::default();
 
     // Create a channel with a size of 1, since ideally only one exit code will be sent before exiting the app.
@@ -287,19 +616,19 @@ pub fn winit_runner(mut app: App) -> AppExit {
     // prepare structures to access data in the world
     let mut redraw_event_reader = ManualEventReader::<RequestRedraw>::default();
 
-    let mut focused_windows_state: SystemState<(Res<WinitSettings>, Query<(Entity, &Window)>)> =
-        SystemState::new(app.world_mut());
+    let mut focused_windows_state: SystemState<(
+        Res<WinitSettings>,
+        Query<(Entity, &Window, Option<&WindowUpdateMode>)>,
+    )> = SystemState::new(app.world_mut());
 
-    let mut event_writer_system_state: SystemState<(
-        EventWriter<WindowResized>,
+    let mut window_event_state: SystemState<(
         NonSend<WinitWindows>,
-        Query<(&mut Window, &mut CachedWindow)>,
+        Query<&Window>,
         NonSend<AccessKitAdapters>,
     )> = SystemState::new(app.world_mut());
 
     let mut create_window =
         SystemState::<CreateWindowParams<Added<Window>>>::from_world(app.world_mut());
-    let mut winit_events = Vec::default();
     // set up the event loop
     let event_handler = move |event, event_loop: &EventLoopWindowTarget<UserEvent>| {
         // The event loop is in the process of exiting, so don't deliver any new events
@@ -307,18 +636,50 @@ pub fn winit_runner(mut app: App) -> AppExit {
             return;
         }
 
-        handle_winit_event(
-            &mut app,
-            &mut runner_state,
-            &mut create_window,
-            &mut event_writer_system_state,
-            &mut focused_windows_state,
-            &mut redraw_event_reader,
-            &mut winit_events,
-            &exit_sender,
-            event,
-            event_loop,
-        );
+        let mut handle_event = |app: &mut App, event: Event<UserEvent>| {
+            if !WinitEventFilters::accepts(app.world(), &event) {
+                return;
+            }
+            WinitEventHooks::run_pre_hooks(app.world_mut(), &event);
+            let post_hook_event =
+                WinitEventHooks::has_post_hooks(app.world()).then(|| event.clone());
+
+            handle_winit_event(
+                app,
+                &mut runner_state,
+                &mut create_window,
+                &mut window_event_state,
+                &mut focused_windows_state,
+                &mut redraw_event_reader,
+                &exit_sender,
+                event,
+                event_loop,
+            );
+
+            if let Some(event) = post_hook_event {
+                WinitEventHooks::run_post_hooks(app.world_mut(), &event);
+            }
+        };
+
+        // Give the shutdown hooks a chance to flush state before the panic kills the app.
+        if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| {
+            handle_event(&mut app, event);
+        })) {
+            WinitShutdownHooks::run(app.world_mut(), &ShutdownCause::Panic);
+            #[cfg(feature = "panic_dialog")]
+            panic_dialog::PanicDialog::show(app.world(), &*payload);
+            panic::resume_unwind(payload);
+        }
+
+        // Events injected while handling this one are handled as if the OS just sent them.
+        // Events injected while handling those wait for the wake-up sent by the injector, so that
+        // a system injecting events every update can't keep the runner from returning to the OS.
+        for injected in WinitEventInjector::take(app.world_mut()) {
+            if event_loop.exiting() {
+                break;
+            }
+            handle_event(&mut app, injected);
+        }
     };
 
     trace!("starting winit event loop");
@@ -339,15 +700,16 @@ fn handle_winit_event(
     app: &mut App,
     runner_state: &mut WinitAppRunnerState,
     create_window: &mut SystemState<CreateWindowParams<Added<Window>>>,
-    event_writer_system_state: &mut SystemState<(
-        EventWriter<WindowResized>,
+    window_event_state: &mut SystemState<(
         NonSend<WinitWindows>,
-        Query<(&mut Window, &mut CachedWindow)>,
+        Query<&Window>,
         NonSend<AccessKitAdapters>,
     )>,
-    focused_windows_state: &mut SystemState<(Res<WinitSettings>, Query<(Entity, &Window)>)>,
+    focused_windows_state: &mut SystemState<(
+        Res<WinitSettings>,
+        Query<(Entity, &Window, Option<&WindowUpdateMode>)>,
+    )>,
     redraw_event_reader: &mut ManualEventReader<RequestRedraw>,
-    winit_events: &mut Vec<WinitEvent>,
     exit_notify: &SyncSender<AppExit>,
     event: Event<UserEvent>,
     event_loop: &EventLoopWindowTarget<UserEvent>,
@@ -371,30 +733,42 @@ fn handle_winit_event(
     create_windows(event_loop, create_window.get_mut(app.world_mut()));
     create_window.apply(app.world_mut());
 
+    let terminating = matches!(event, Event::LoopExiting);
     match event {
         Event::AboutToWait => {
+            // Marks the boundary between update cycles, so profiler captures can be aligned with
+            // them. The `tracy.frame_mark` field is what `tracing-tracy` turns into a frame mark,
+            // and what the formatted logs of the `LogPlugin` omit.
+            #[cfg(feature = "trace")]
+            bevy_utils::tracing::event!(
+                bevy_utils::tracing::Level::INFO,
+                message = "winit update cycle",
+                tracy.frame_mark = true
+            );
+
             if let Some(app_redraw_events) = app.world().get_resource::<Events<RequestRedraw>>() {
                 if redraw_event_reader.read(app_redraw_events).last().is_some() {
-                    runner_state.redraw_requested = true;
+                    runner_state.redraw_event_received = true;
                 }
             }
 
-            let (config, windows) = focused_windows_state.get(app.world());
-            let focused = windows.iter().any(|(_, window)| window.focused);
+            runner_state.monitors.poll(app.world_mut(), event_loop);
 
-            let mut update_mode = config.update_mode(focused);
+            let mut update_mode = current_update_mode(app.world(), focused_windows_state);
             let mut should_update = should_update(runner_state, update_mode);
 
             if runner_state.startup_forced_updates > 0 {
                 runner_state.startup_forced_updates -= 1;
                 // Ensure that an update is triggered on the first iterations for app initialization
                 should_update = true;
+                runner_state.wake_reason.startup = true;
             }
 
             if runner_state.activity_state == UpdateState::WillSuspend {
                 runner_state.activity_state = UpdateState::Suspended;
                 // Trigger one last update to enter the suspended state
                 should_update = true;
+                runner_state.wake_reason.lifecycle = true;
 
                 #[cfg(target_os = "android")]
                 {
@@ -414,6 +788,7 @@ fn handle_winit_event(
                 runner_state.activity_state = UpdateState::Active;
                 // Trigger the update to enter the active state
                 should_update = true;
+                runner_state.wake_reason.lifecycle = true;
                 // Trigger the next redraw ro refresh the screen immediately
                 runner_state.redraw_requested = true;
 
@@ -433,6 +808,8 @@ fn handle_winit_event(
                             mut adapters,
                             mut handlers,
                             accessibility_requested,
+                            accesskit_enabled,
+                            builder_hook,
                         ) = create_window.get_mut(app.world_mut());
 
                         let winit_window = winit_windows.create_window(
@@ -442,6 +819,8 @@ fn handle_winit_event(
                             &mut adapters,
                             &mut handlers,
                             &accessibility_requested,
+                            &accesskit_enabled,
+                            &builder_hook,
                         );
 
                         let wrapper = RawHandleWrapper::new(winit_window).unwrap();
@@ -455,15 +834,29 @@ fn handle_winit_event(
             // If the cycle takes more than the wait timeout, it will be re-executed immediately.
             let begin_frame_time = Instant::now();
 
+            // Hold the update back until the next frame is due, the events waking the app up stay
+            // pending until then.
+            let frame_limiter = app.world().get_resource::<FrameLimiter>().copied();
+            if frame_limiter.is_none() {
+                runner_state.next_frame = None;
+            } else if runner_state
+                .next_frame
+                .is_some_and(|next_frame| next_frame > begin_frame_time)
+            {
+                should_update = false;
+            }
+
             if should_update {
+                if let Some(frame_limiter) = frame_limiter {
+                    runner_state.next_frame =
+                        frame_limiter.next_frame(runner_state.next_frame, begin_frame_time);
+                }
+
                 // Not redrawing, but the timeout elapsed.
-                run_app_update(runner_state, app, winit_events);
+                run_app_update(runner_state, app);
 
                 // Running the app may have changed the WinitSettings resource, so we have to re-extract it.
-                let (config, windows) = focused_windows_state.get(app.world());
-                let focused = windows.iter().any(|(_, window)| window.focused);
-
-                update_mode = config.update_mode(focused);
+                update_mode = current_update_mode(app.world(), focused_windows_state);
             }
 
             match update_mode {
@@ -495,38 +888,187 @@ fn handle_winit_event(
                         }
                     }
 
+                    // Without redraws, waiting would stop the updates once every window is paused,
+                    // and polling would spin them as fast as possible.
+                    let winit_windows = app.world().non_send_resource::<WinitWindows>();
+                    runner_state
+                        .occluded_windows
+                        .retain(|entity| winit_windows.get_window(*entity).is_some());
+                    let all_paused = app
+                        .world()
+                        .resource::<WinitSettings>()
+                        .pause_occluded_windows
+                        && !winit_windows.is_empty()
+                        && winit_windows.len() == runner_state.occluded_windows.len();
+                    if !all_paused {
+                        runner_state.paused_frame_time = None;
+                    }
+                    let frame_time = all_paused.then(|| {
+                        *runner_state
+                            .paused_frame_time
+                            .get_or_insert_with(|| last_frame_time(app.world()))
+                    });
+                    if let Some(control_flow) =
+                        paused_windows_control_flow(begin_frame_time, frame_time)
+                    {
+                        event_loop.set_control_flow(control_flow);
+                    }
+
                     // Trigger the next redraw to refresh the screen immediately if waiting
                     if let ControlFlow::Wait = event_loop.control_flow() {
                         runner_state.redraw_requested = true;
                     }
                 }
+                UpdateMode::BusyPoll => {
+                    event_loop.set_control_flow(ControlFlow::Poll);
+                }
                 UpdateMode::Reactive { wait } | UpdateMode::ReactiveLowPower { wait } => {
-                    // Set the next timeout, starting from the instant before running app.update() to avoid frame delays
-                    if let Some(next) = begin_frame_time.checked_add(wait) {
+                    // Set the next timeout, starting from the last frame presentation if the
+                    // renderer reported a new one, or else from the instant before running
+                    // app.update(), to avoid frame delays
+                    let presented = app
+                        .world()
+                        .get_resource::<PresentFeedback>()
+                        .and_then(PresentFeedback::last_presented)
+                        .filter(|presented| Some(*presented) != runner_state.last_presented);
+                    let pacing_start = match presented {
+                        Some(presented) => {
+                            runner_state.last_presented = Some(presented);
+                            presented
+                        }
+                        None => begin_frame_time,
+                    };
+                    if let Some(next) = pacing_start.checked_add(wait) {
                         if runner_state.wait_elapsed {
-                            event_loop.set_control_flow(ControlFlow::WaitUntil(next));
+                            let wait_strategy = app
+                                .world()
+                                .get_resource::<WinitSettings>()
+                                .map_or_else(Default::default, |settings| settings.wait_strategy);
+                            let wake_up = match wait_strategy {
+                                WaitStrategy::SleepAndSpin { spin }
+                                    if cfg!(not(target_arch = "wasm32")) =>
+                                {
+                                    runner_state.spin_until = Some(next);
+                                    next.checked_sub(spin).unwrap_or(next)
+                                }
+                                _ => {
+                                    runner_state.spin_until = None;
+                                    next
+                                }
+                            };
+                            event_loop.set_control_flow(ControlFlow::WaitUntil(wake_up));
                         }
                     }
                 }
+                UpdateMode::FixedTimestep => {
+                    event_loop.set_control_flow(match next_fixed_timestep(app.world()) {
+                        Some(next) => ControlFlow::WaitUntil(next),
+                        None => ControlFlow::Wait,
+                    });
+                }
+            }
+
+            // Don't wake up before the next frame is due, and hold the redraws back until then.
+            let mut frame_pending = false;
+            if let (Some(frame_limiter), Some(next_frame)) =
+                (frame_limiter, runner_state.next_frame)
+            {
+                let wakes_early = match event_loop.control_flow() {
+                    ControlFlow::Poll => true,
+                    ControlFlow::Wait => {
+                        runner_state.redraw_requested || runner_state.redraw_event_received
+                    }
+                    ControlFlow::WaitUntil(wake_up) => wake_up < next_frame,
+                };
+                if wakes_early && next_frame > Instant::now() {
+                    let wake_up = if cfg!(target_arch = "wasm32") {
+                        next_frame
+                    } else {
+                        runner_state.spin_until = Some(next_frame);
+                        next_frame
+                            .checked_sub(frame_limiter.spin)
+                            .unwrap_or(next_frame)
+                    };
+                    event_loop.set_control_flow(ControlFlow::WaitUntil(wake_up));
+                    frame_pending = true;
+                }
+            }
+
+            // Systems can take over the control flow computed above.
+            match app.world().get_resource::<WinitControlFlow>().copied() {
+                Some(WinitControlFlow::Poll) => {
+                    runner_state.spin_until = None;
+                    event_loop.set_control_flow(ControlFlow::Poll);
+                }
+                Some(WinitControlFlow::Wait) => {
+                    runner_state.spin_until = None;
+                    event_loop.set_control_flow(ControlFlow::Wait);
+                }
+                Some(WinitControlFlow::WaitUntil(wake_up)) => {
+                    runner_state.spin_until = None;
+                    event_loop.set_control_flow(ControlFlow::WaitUntil(wake_up));
+                }
+                Some(WinitControlFlow::Exit) => {
+                    app.world_mut().remove_resource::<WinitControlFlow>();
+                    app.world_mut().send_event(AppExit::Success);
+                }
+                None => {}
             }
 
-            if update_mode != runner_state.update_mode {
+            if Some(update_mode) != runner_state.update_mode {
                 // Trigger the next redraw since we're changing the update mode
                 runner_state.redraw_requested = true;
-                runner_state.update_mode = update_mode;
+                if let Some(previous) = runner_state.update_mode.replace(update_mode) {
+                    app.world_mut().send_event(UpdateModeChanged {
+                        previous,
+                        current: update_mode,
+                    });
+                }
             }
 
-            if runner_state.redraw_requested
+            if (runner_state.redraw_requested || runner_state.redraw_event_received)
                 && runner_state.activity_state != UpdateState::Suspended
+                && !frame_pending
             {
+                let (config, windows) = focused_windows_state.get(app.world());
                 let winit_windows = app.world().non_send_resource::<WinitWindows>();
-                for window in winit_windows.windows.values() {
-                    window.request_redraw();
+                // Explicit redraw requests are meant for every window.
+                let broadcast = if runner_state.redraw_event_received {
+                    RedrawBroadcast::AllWindows
+                } else {
+                    config.redraw_broadcast
+                };
+                let paused = |entity: &Entity| {
+                    config.pause_occluded_windows && runner_state.occluded_windows.contains(entity)
+                };
+                match broadcast {
+                    RedrawBroadcast::AllWindows => {
+                        for (_, window) in winit_windows
+                            .iter_windows()
+                            .filter(|(entity, _)| !paused(entity))
+                        {
+                            window.request_redraw();
+                        }
+                    }
+                    RedrawBroadcast::FocusedWindows => {
+                        for (entity, ..) in windows
+                            .iter()
+                            .filter(|(entity, window, _)| window.focused && !paused(entity))
+                        {
+                            if let Some(window) = winit_windows.get_window(entity) {
+                                window.request_redraw();
+                            }
+                        }
+                    }
+                    RedrawBroadcast::Disabled => {}
                 }
                 runner_state.redraw_requested = false;
+                runner_state.redraw_event_received = false;
             }
         }
         Event::NewEvents(cause) => {
+            app.world_mut()
+                .send_event(WinitStartCause::new(cause, Instant::now()));
             runner_state.wait_elapsed = match cause {
                 StartCause::WaitCancelled {
                     requested_resume: Some(resume),
@@ -538,20 +1080,37 @@ fn handle_winit_event(
                 }
                 _ => true,
             };
+
+            if runner_state.wait_elapsed {
+                if let Some(deadline) = runner_state.spin_until.take() {
+                    while Instant::now() < deadline {
+                        std::hint::spin_loop();
+                    }
+                }
+            }
         }
         Event::WindowEvent {
-            event, window_id, ..
+            mut event,
+            window_id,
         } => {
-            let (mut window_resized, winit_windows, mut windows, access_kit_adapters) =
-                event_writer_system_state.get_mut(app.world_mut());
+            let (winit_windows, windows, access_kit_adapters) = window_event_state.get(app.world());
 
             let Some(window) = winit_windows.get_window_entity(window_id) else {
-                warn!("Skipped event {event:?} for unknown winit Window Id {window_id:?}");
+                // Windows closed by the app are forgotten before the OS confirms their destruction.
+                if matches!(event, WindowEvent::Destroyed) {
+                    return;
+                }
+                missing_window_policy(app.world()).report(app.world_mut(), window_id, None, &event);
                 return;
             };
 
-            let Ok((mut win, _)) = windows.get_mut(window) else {
-                warn!("Window {window:?} is missing `Window` component, skipping event {event:?}");
+            let Ok(win) = windows.get(window) else {
+                missing_window_policy(app.world()).report(
+                    app.world_mut(),
+                    window_id,
+                    Some(window),
+                    &event,
+                );
                 return;
             };
 
@@ -563,263 +1122,263 @@ fn handle_winit_event(
                 }
             }
 
-            runner_state.window_event_received = true;
-
-            match event {
-                WindowEvent::Resized(size) => {
-                    react_to_resize(&mut win, size, &mut window_resized, window);
-                }
-                WindowEvent::CloseRequested => winit_events.send(WindowCloseRequested { window }),
-                WindowEvent::KeyboardInput { ref event, .. } => {
-                    if event.state.is_pressed() {
-                        if let Some(char) = &event.text {
-                            let char = char.clone();
-                            #[allow(deprecated)]
-                            winit_events.send(ReceivedCharacter { window, char });
-                        }
-                    }
-                    winit_events.send(converters::convert_keyboard_input(event, window));
-                }
-                WindowEvent::CursorMoved { position, .. } => {
-                    let physical_position = DVec2::new(position.x, position.y);
-
-                    let last_position = win.physical_cursor_position();
-                    let delta = last_position.map(|last_pos| {
-                        (physical_position.as_vec2() - last_pos) / win.resolution.scale_factor()
-                    });
-
-                    win.set_physical_cursor_position(Some(physical_position));
-                    let position =
-                        (physical_position / win.resolution.scale_factor() as f64).as_vec2();
-                    winit_events.send(CursorMoved {
-                        window,
-                        position,
-                        delta,
-                    });
-                }
-                WindowEvent::CursorEntered { .. } => {
-                    winit_events.send(CursorEntered { window });
-                }
-                WindowEvent::CursorLeft { .. } => {
-                    win.set_physical_cursor_position(None);
-                    winit_events.send(CursorLeft { window });
-                }
-                WindowEvent::MouseInput { state, button, .. } => {
-                    winit_events.send(MouseButtonInput {
-                        button: converters::convert_mouse_button(button),
-                        state: converters::convert_element_state(state),
-                        window,
-                    });
-                }
-                WindowEvent::TouchpadMagnify { delta, .. } => {
-                    winit_events.send(TouchpadMagnify(delta as f32));
-                }
-                WindowEvent::TouchpadRotate { delta, .. } => {
-                    winit_events.send(TouchpadRotate(delta));
-                }
-                WindowEvent::MouseWheel { delta, .. } => match delta {
-                    event::MouseScrollDelta::LineDelta(x, y) => {
-                        winit_events.send(MouseWheel {
-                            unit: MouseScrollUnit::Line,
-                            x,
-                            y,
-                            window,
-                        });
-                    }
-                    event::MouseScrollDelta::PixelDelta(p) => {
-                        winit_events.send(MouseWheel {
-                            unit: MouseScrollUnit::Pixel,
-                            x: p.x as f32,
-                            y: p.y as f32,
-                            window,
-                        });
+            // `winit` only accepts a new size for the window while the scale factor change is
+            // being handled, so it can't be deferred to the `WinitEvents` schedule.
+            if let WindowEvent::ScaleFactorChanged {
+                inner_size_writer, ..
+            } = &mut event
+            {
+                if let Some(forced_factor) = win.resolution.scale_factor_override() {
+                    // This window is overriding the OS-suggested DPI, so its physical size
+                    // should be set based on the overriding value. Its logical size already
+                    // incorporates any resize constraints.
+                    let new_inner_size = LogicalSize::new(win.width(), win.height())
+                        .to_physical::<u32>(forced_factor as f64);
+                    if let Err(err) = inner_size_writer.request_inner_size(new_inner_size) {
+                        warn!("Winit Failed to resize the window: {err}");
                     }
-                },
-                WindowEvent::Touch(touch) => {
-                    let location = touch
-                        .location
-                        .to_logical(win.resolution.scale_factor() as f64);
-                    winit_events.send(converters::convert_touch_input(touch, location, window));
                 }
-                WindowEvent::ScaleFactorChanged {
-                    scale_factor,
-                    mut inner_size_writer,
-                } => {
-                    let prior_factor = win.resolution.scale_factor();
-                    win.resolution.set_scale_factor(scale_factor as f32);
-                    // Note: this may be different from new_scale_factor if
-                    // `scale_factor_override` is set to Some(thing)
-                    let new_factor = win.resolution.scale_factor();
-
-                    let mut new_inner_size =
-                        PhysicalSize::new(win.physical_width(), win.physical_height());
-                    let scale_factor_override = win.resolution.scale_factor_override();
-                    if let Some(forced_factor) = scale_factor_override {
-                        // This window is overriding the OS-suggested DPI, so its physical size
-                        // should be set based on the overriding value. Its logical size already
-                        // incorporates any resize constraints.
-                        let maybe_new_inner_size = LogicalSize::new(win.width(), win.height())
-                            .to_physical::<u32>(forced_factor as f64);
-                        if let Err(err) = inner_size_writer.request_inner_size(new_inner_size) {
-                            warn!("Winit Failed to resize the window: {err}");
-                        } else {
-                            new_inner_size = maybe_new_inner_size;
-                        }
-                    }
-                    let new_logical_width = new_inner_size.width as f32 / new_factor;
-                    let new_logical_height = new_inner_size.height as f32 / new_factor;
-
-                    let width_equal = relative_eq!(win.width(), new_logical_width);
-                    let height_equal = relative_eq!(win.height(), new_logical_height);
-                    win.resolution
-                        .set_physical_resolution(new_inner_size.width, new_inner_size.height);
+            }
 
-                    winit_events.send(WindowBackendScaleFactorChanged {
-                        window,
-                        scale_factor,
-                    });
-                    if scale_factor_override.is_none() && !relative_eq!(new_factor, prior_factor) {
-                        winit_events.send(WindowScaleFactorChanged {
-                            window,
-                            scale_factor,
-                        });
-                    }
+            runner_state.window_event_received = true;
 
-                    if !width_equal || !height_equal {
-                        winit_events.send(WindowResized {
-                            window,
-                            width: new_logical_width,
-                            height: new_logical_height,
-                        });
-                    }
-                }
-                WindowEvent::Focused(focused) => {
-                    win.focused = focused;
-                    winit_events.send(WindowFocused { window, focused });
-                }
-                WindowEvent::Occluded(occluded) => {
-                    winit_events.send(WindowOccluded { window, occluded });
-                }
-                WindowEvent::DroppedFile(path_buf) => {
-                    winit_events.send(FileDragAndDrop::DroppedFile { window, path_buf });
-                }
-                WindowEvent::HoveredFile(path_buf) => {
-                    winit_events.send(FileDragAndDrop::HoveredFile { window, path_buf });
-                }
-                WindowEvent::HoveredFileCancelled => {
-                    winit_events.send(FileDragAndDrop::HoveredFileCanceled { window });
-                }
-                WindowEvent::Moved(position) => {
-                    let position = ivec2(position.x, position.y);
-                    win.position.set(position);
-                    winit_events.send(WindowMoved { window, position });
-                }
-                WindowEvent::Ime(event) => match event {
-                    event::Ime::Preedit(value, cursor) => {
-                        winit_events.send(Ime::Preedit {
-                            window,
-                            value,
-                            cursor,
-                        });
-                    }
-                    event::Ime::Commit(value) => {
-                        winit_events.send(Ime::Commit { window, value });
-                    }
-                    event::Ime::Enabled => {
-                        winit_events.send(Ime::Enabled { window });
-                    }
-                    event::Ime::Disabled => {
-                        winit_events.send(Ime::Disabled { window });
-                    }
-                },
-                WindowEvent::ThemeChanged(theme) => {
-                    winit_events.send(WindowThemeChanged {
-                        window,
-                        theme: convert_winit_theme(theme),
-                    });
-                }
-                WindowEvent::Destroyed => {
-                    winit_events.send(WindowDestroyed { window });
-                }
-                WindowEvent::RedrawRequested => {
-                    run_app_update(runner_state, app, winit_events);
+            if let WindowEvent::Occluded(occluded) = event {
+                if occluded {
+                    runner_state.occluded_windows.insert(window);
+                } else if runner_state.occluded_windows.remove(&window) {
+                    // Redraw right away, in case redraws were paused while the window was covered.
+                    runner_state.redraw_requested = true;
                 }
-                _ => {}
             }
 
-            let mut windows = app.world_mut().query::<(&mut Window, &mut CachedWindow)>();
-            if let Ok((window_component, mut cache)) = windows.get_mut(app.world_mut(), window) {
-                if window_component.is_changed() {
-                    cache.window = window_component.clone();
-                }
+            let redraw_requested = matches!(event, WindowEvent::RedrawRequested);
+            let input = matches!(
+                event,
+                WindowEvent::KeyboardInput { .. }
+                    | WindowEvent::MouseInput { .. }
+                    | WindowEvent::MouseWheel { .. }
+                    | WindowEvent::Touch(_)
+            );
+            app.world_mut()
+                .non_send_resource_mut::<WinitEventBuffer>()
+                .push(Event::WindowEvent { window_id, event });
+
+            if redraw_requested {
+                runner_state.wake_reason.redraw_requested = true;
+                run_app_update(runner_state, app);
+            } else if input
+                && runner_state.activity_state.is_active()
+                && !matches!(event_loop.control_flow(), ControlFlow::Poll)
+                && app
+                    .world()
+                    .get_resource::<WinitSettings>()
+                    .is_some_and(|settings| settings.low_latency_input)
+            {
+                // Handle the input right away instead of waiting for the next redraw.
+                run_app_update(runner_state, app);
             }
         }
-        Event::DeviceEvent { event, .. } => {
+        Event::DeviceEvent { device_id, event } => {
             runner_state.device_event_received = true;
-            if let DeviceEvent::MouseMotion { delta: (x, y) } = event {
-                let delta = Vec2::new(x as f32, y as f32);
-                winit_events.send(MouseMotion { delta });
-            }
+            app.world_mut()
+                .non_send_resource_mut::<WinitEventBuffer>()
+                .push(Event::DeviceEvent { device_id, event });
         }
         Event::Suspended => {
-            winit_events.send(ApplicationLifetime::Suspended);
+            app.world_mut()
+                .non_send_resource_mut::<WinitEventBuffer>()
+                .push(Event::Suspended);
             // Mark the state as `WillSuspend`. This will let the schedule run one last time
             // before actually suspending to let the application react
             runner_state.activity_state = UpdateState::WillSuspend;
         }
         Event::Resumed => {
-            match runner_state.activity_state {
-                UpdateState::NotYetStarted => winit_events.send(ApplicationLifetime::Started),
-                _ => winit_events.send(ApplicationLifetime::Resumed),
-            }
+            // The monitors can only be listed once the app is resumed, and the update following
+            // the resume may already need them, e.g. to place the windows.
+            runner_state.monitors.refresh(app.world_mut(), event_loop);
+            app.world_mut()
+                .non_send_resource_mut::<WinitEventBuffer>()
+                .push(Event::Resumed);
             runner_state.activity_state = UpdateState::WillResume;
         }
         Event::UserEvent(RequestRedraw) => {
-            runner_state.redraw_requested = true;
+            MainThreadTasks::run(app.world_mut());
+            // Wakeups only ask for an update, the other user events for a redraw of every window.
+            if app
+                .world()
+                .get_resource::<WinitWaker>()
+                .is_some_and(WinitWaker::take_wake)
+            {
+                runner_state.wake_requested = true;
+            } else {
+                runner_state.redraw_event_received = true;
+            }
+            runner_state.wake_reason.user_event = true;
+        }
+        Event::LoopExiting => {
+            // The exits of the app are handled below, so the loop only exits without them when
+            // the OS terminates the app, e.g. from the "Quit" item of the macOS application menu.
+            app.world_mut().send_event(AppTerminating);
+            app.world_mut().send_event(AppExit::Success);
+            run_app_update(runner_state, app);
         }
         _ => (),
     }
 
-    if let Some(app_exit) = app.should_exit() {
+    // The process ends once a terminating OS returns, so its exit can't wait for a confirmation.
+    if let Some(app_exit) = app.should_exit().filter(|app_exit| {
+        terminating || !ExitConfirmation::hold_back_exit(app.world_mut(), app_exit)
+    }) {
+        let exit_updates = app
+            .world()
+            .get_resource::<WinitSettings>()
+            .map_or(0, |settings| settings.exit_updates);
+        for remaining_updates in (0..exit_updates).rev() {
+            app.world_mut().send_event(AppWillExit {
+                exit: app_exit.clone(),
+                remaining_updates,
+            });
+            run_app_update(runner_state, app);
+        }
+        WinitShutdownHooks::run(app.world_mut(), &ShutdownCause::Exit(app_exit.clone()));
+
         if let Err(err) = exit_notify.try_send(app_exit) {
             error!("Failed to send a app exit notification! This is a bug. Reason: {err}");
         };
         event_loop.exit();
-        return;
     }
+}
+
+fn missing_window_policy(world: &World) -> MissingWindowPolicy {
+    world
+        .get_resource::<WinitSettings>()
+        .map_or_else(Default::default, |settings| settings.missing_window_policy)
+}
 
-    // We drain events after every received winit event in addition to on app update to ensure
-    // the work of pushing events into event queues is spread out over time in case the app becomes
-    // dormant for a long stretch.
-    forward_winit_events(winit_events, app);
+fn is_any_window_focused(world: &World) -> bool {
+    world
+        .get_resource::<AnyWindowFocused>()
+        .is_some_and(AnyWindowFocused::is_focused)
+}
+
+/// Returns the [`UpdateMode`] to run with: the one updating the most often among the visible
+/// windows, using their [`WindowUpdateMode`] if they have one and the [`WinitSettings`] otherwise.
+fn current_update_mode(
+    world: &World,
+    focused_windows_state: &mut SystemState<(
+        Res<WinitSettings>,
+        Query<(Entity, &Window, Option<&WindowUpdateMode>)>,
+    )>,
+) -> UpdateMode {
+    let focused = is_any_window_focused(world);
+    let fixed_timestep = fixed_timestep(world);
+    let (config, windows) = focused_windows_state.get(world);
+    let default_mode = config.update_mode(focused);
+    windows
+        .iter()
+        .filter(|(_, window, _)| window.visible)
+        .map(|(_, _, mode)| mode.map_or(default_mode, |mode| mode.update_mode(focused)))
+        .reduce(|mode, other| mode.most_aggressive(other, fixed_timestep))
+        .unwrap_or(default_mode)
+}
+
+/// Returns the real time between two [`FixedUpdate`](bevy_app::FixedUpdate) steps, or `None`
+/// while they don't run.
+fn fixed_timestep(world: &World) -> Option<Duration> {
+    let fixed = world.get_resource::<Time<Fixed>>()?;
+    let virtual_time = world.get_resource::<Time<Virtual>>()?;
+    let speed = virtual_time.relative_speed_f64();
+    (!virtual_time.is_paused() && speed > 0.0).then(|| fixed.timestep().div_f64(speed))
 }
 
 fn should_update(runner_state: &WinitAppRunnerState, update_mode: UpdateMode) -> bool {
     let handle_event = match update_mode {
-        UpdateMode::Continuous | UpdateMode::Reactive { .. } => {
+        UpdateMode::Continuous | UpdateMode::Reactive { .. } | UpdateMode::FixedTimestep => {
             runner_state.wait_elapsed
                 || runner_state.window_event_received
                 || runner_state.device_event_received
         }
+        UpdateMode::BusyPoll => true,
         UpdateMode::ReactiveLowPower { .. } => {
             runner_state.wait_elapsed || runner_state.window_event_received
         }
     };
 
-    handle_event && runner_state.activity_state.is_active()
+    (handle_event || runner_state.wake_requested) && runner_state.activity_state.is_active()
 }
 
-fn run_app_update(
-    runner_state: &mut WinitAppRunnerState,
-    app: &mut App,
-    winit_events: &mut Vec<WinitEvent>,
-) {
-    runner_state.reset_on_update();
+/// Returns the duration of the last frame, or 60 FPS if it isn't known yet.
+fn last_frame_time(world: &World) -> Duration {
+    world
+        .get_resource::<Time<Real>>()
+        .map(Time::delta)
+        .filter(|delta| !delta.is_zero())
+        .unwrap_or(Duration::from_secs_f64(1.0 / 60.0))
+}
+
+/// Returns the control flow of a [`UpdateMode::Continuous`] app while every window is paused,
+/// i.e. when `frame_time` is `Some`: the next update is due one frame after the start of this one.
+fn paused_windows_control_flow(
+    frame_start: Instant,
+    frame_time: Option<Duration>,
+) -> Option<ControlFlow> {
+    let wake_up = frame_start.checked_add(frame_time?)?;
+    Some(ControlFlow::WaitUntil(wake_up))
+}
+
+/// Returns when the next [`FixedUpdate`](bevy_app::FixedUpdate) step is due, or `None` if the
+/// fixed timestep isn't advancing.
+fn next_fixed_timestep(world: &World) -> Option<Instant> {
+    let fixed = world.get_resource::<Time<Fixed>>()?;
+    let virtual_time = world.get_resource::<Time<Virtual>>()?;
+    let real = world.get_resource::<Time<Real>>()?;
+    let speed = virtual_time.relative_speed_f64();
+    if virtual_time.is_paused() || speed <= 0.0 {
+        return None;
+    }
+
+    // The overstep was accumulated up to the start of the last update.
+    let remaining = fixed.timestep().saturating_sub(fixed.overstep());
+    real.last_update()?.checked_add(remaining.div_f64(speed))
+}
 
-    forward_winit_events(winit_events, app);
+fn run_app_update(runner_state: &mut WinitAppRunnerState, app: &mut App) {
+    let wake_reason = WakeReason {
+        timer_elapsed: runner_state.wait_elapsed,
+        window_event: runner_state.window_event_received,
+        device_event: runner_state.device_event_received,
+        ..mem::take(&mut runner_state.wake_reason)
+    };
+    runner_state.reset_on_update();
 
     if app.plugins_state() == PluginsState::Cleaned {
+        app.world_mut().insert_resource(wake_reason);
+        app.world_mut().send_event(wake_reason);
+        let start = Instant::now();
         app.update();
+        let duration = start.elapsed();
+
+        let watchdog = app
+            .world()
+            .get_resource::<WinitSettings>()
+            .and_then(|settings| settings.update_watchdog);
+        if let Some(threshold) = watchdog.filter(|&threshold| duration > threshold) {
+            let (previous_update, previous_wait) = match runner_state.last_update {
+                Some((previous_start, previous_duration)) => (
+                    Some(previous_duration),
+                    Some(start.saturating_duration_since(previous_start + previous_duration)),
+                ),
+                None => (None, None),
+            };
+            warn!(
+                ?duration,
+                ?threshold,
+                ?previous_update,
+                ?previous_wait,
+                "Update took longer than the watchdog threshold, blocking the event loop"
+            );
+        }
+        runner_state.last_update = Some((start, duration));
     }
 }
 
@@ -838,3 +1397,50 @@ fn react_to_resize(
         height: win.height(),
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paused_windows_wait_for_the_next_frame() {
+        let frame_start = Instant::now();
+        let frame_time = Duration::from_millis(16);
+
+        assert_eq!(paused_windows_control_flow(frame_start, None), None);
+        assert_eq!(
+            paused_windows_control_flow(frame_start, Some(frame_time)),
+            Some(ControlFlow::WaitUntil(frame_start + frame_time))
+        );
+    }
+
+    #[test]
+    fn wakeups_run_an_update_in_reactive_modes() {
+        let mut runner_state = WinitAppRunnerState {
+            activity_state: UpdateState::Active,
+            ..Default::default()
+        };
+        let update_mode = UpdateMode::ReactiveLowPower {
+            wait: Duration::from_secs(60),
+        };
+        assert!(!should_update(&runner_state, update_mode));
+
+        runner_state.wake_requested = true;
+        assert!(should_update(&runner_state, update_mode));
+
+        runner_state.reset_on_update();
+        assert!(!should_update(&runner_state, update_mode));
+    }
+
+    #[test]
+    fn last_frame_time_defaults_to_60_fps() {
+        let mut world = World::new();
+        assert_eq!(last_frame_time(&world), Duration::from_secs_f64(1.0 / 60.0));
+
+        let mut time = Time::<Real>::new(Instant::now());
+        time.update_with_duration(Duration::ZERO);
+        time.update_with_duration(Duration::from_millis(8));
+        world.insert_resource(time);
+        assert_eq!(last_frame_time(&world), Duration::from_millis(8));
+    }
+}
@@ -0,0 +1,70 @@
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+use bevy_ecs::prelude::*;
+
+use crate::WinitWaker;
+
+type MainThreadTask = Box<dyn FnOnce(&mut World) + Send>;
+
+/// Resource running closures on the main thread, from any thread.
+///
+/// Some platform APIs (e.g. native dialogs, the clipboard on some platforms, or most `AppKit`
+/// calls on macOS) must be called from the main thread, which runs the `winit` event loop. The
+/// closures are run by the runner as soon as it receives the user event waking it up, even if the
+/// app is sleeping in a [`Reactive`](crate::UpdateMode::Reactive) or
+/// [`ReactiveLowPower`](crate::UpdateMode::ReactiveLowPower) update mode, with exclusive access
+/// to the [`World`].
+///
+/// The executor is `Send + Sync` and can be cloned freely, e.g. into async tasks.
+///
+/// ```no_run
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_winit::MainThreadExecutor;
+/// fn show_dialog(executor: Res<MainThreadExecutor>) {
+///     let executor = executor.clone();
+///     std::thread::spawn(move || {
+///         executor.spawn(|_world| {
+///             // Call the main-thread-only API here.
+///         });
+///     });
+/// }
+/// ```
+#[derive(Resource, Clone)]
+pub struct MainThreadExecutor {
+    sender: Sender<MainThreadTask>,
+    waker: WinitWaker,
+}
+
+impl MainThreadExecutor {
+    /// Queues `task` to run on the main thread, waking up the event loop.
+    ///
+    /// Returns `false` if the app is no longer running, in which case `task` is dropped.
+    pub fn spawn(&self, task: impl FnOnce(&mut World) + Send + 'static) -> bool {
+        self.sender.send(Box::new(task)).is_ok() && self.waker.wake()
+    }
+}
+
+/// Non-send resource holding the tasks queued by the [`MainThreadExecutor`].
+pub(crate) struct MainThreadTasks {
+    receiver: Receiver<MainThreadTask>,
+}
+
+impl MainThreadTasks {
+    /// Inserts the [`MainThreadExecutor`] and the queue it feeds into `world`.
+    pub(crate) fn init(world: &mut World, waker: WinitWaker) {
+        let (sender, receiver) = channel();
+        world.insert_resource(MainThreadExecutor { sender, waker });
+        world.insert_non_send_resource(MainThreadTasks { receiver });
+    }
+
+    /// Runs the tasks queued in `world`, in the order they were queued.
+    pub(crate) fn run(world: &mut World) {
+        let Some(tasks) = world.get_non_send_resource::<Self>() else {
+            return;
+        };
+        let tasks = tasks.receiver.try_iter().collect::<Vec<_>>();
+        for task in tasks {
+            task(world);
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use bevy_ecs::{change_detection::DetectChangesMut, world::World};
+use bevy_utils::{Duration, Instant};
+use bevy_window::{
+    Monitor, MonitorConnected, MonitorDisconnected, MonitorInfo, Monitors, VideoModeInfo,
+};
+use winit::{event_loop::EventLoopWindowTarget, monitor::MonitorHandle};
+
+use crate::{
+    converters::{convert_monitor, convert_video_mode},
+    UserEvent,
+};
+
+/// How often the runner checks the monitors, as `winit` doesn't report their changes.
+const MONITOR_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Keeps the [`Monitors`] resource up to date with the monitors of the event loop.
+pub(crate) struct MonitorTracker<H = MonitorHandle> {
+    /// The monitors listed by the last poll, with their video modes.
+    monitors: Vec<(H, Monitor)>,
+    last_poll: Option<Instant>,
+}
+
+impl<H> Default for MonitorTracker<H> {
+    fn default() -> Self {
+        Self {
+            monitors: Vec::new(),
+            last_poll: None,
+        }
+    }
+}
+
+impl MonitorTracker {
+    /// Lists the monitors again if the poll interval elapsed, updating the [`Monitors`] resource
+    /// and sending the [`MonitorConnected`] and [`MonitorDisconnected`] events.
+    pub(crate) fn poll(
+        &mut self,
+        world: &mut World,
+        event_loop: &EventLoopWindowTarget<UserEvent>,
+    ) {
+        let now = Instant::now();
+        if self.last_poll.is_some_and(|last_poll| {
+            now.saturating_duration_since(last_poll) < MONITOR_POLL_INTERVAL
+        }) {
+            return;
+        }
+        self.refresh(world, event_loop);
+    }
+
+    /// Lists the monitors right away, e.g. when the app resumes, so the [`Monitors`] resource is
+    /// filled before the next update.
+    pub(crate) fn refresh(
+        &mut self,
+        world: &mut World,
+        event_loop: &EventLoopWindowTarget<UserEvent>,
+    ) {
+        if !world.contains_resource::<Monitors>() {
+            return;
+        }
+        self.last_poll = Some(Instant::now());
+
+        let primary = event_loop.primary_monitor();
+        let listed = event_loop.available_monitors().map(|handle| {
+            let primary = primary.as_ref() == Some(&handle);
+            (convert_monitor(&handle), primary, handle)
+        });
+        self.update(world, listed, |handle| {
+            handle
+                .video_modes()
+                .map(|video_mode| convert_video_mode(&video_mode))
+                .collect()
+        });
+    }
+}
+
+impl<H: PartialEq> MonitorTracker<H> {
+    /// Replaces the tracked monitors with the `listed` ones, updating the [`Monitors`] resource and
+    /// sending the [`MonitorConnected`] and [`MonitorDisconnected`] events.
+    fn update(
+        &mut self,
+        world: &mut World,
+        listed: impl IntoIterator<Item = (MonitorInfo, bool, H)>,
+        video_modes: impl Fn(&H) -> Vec<VideoModeInfo>,
+    ) {
+        let previous = std::mem::take(&mut self.monitors);
+        for (info, primary, handle) in listed {
+            // Listing the video modes can be slow, they are only listed once per monitor.
+            let video_modes = match previous.iter().find(|(other, _)| *other == handle) {
+                Some((_, monitor)) => monitor.video_modes.clone(),
+                None => video_modes(&handle),
+            };
+            let monitor = Monitor {
+                info,
+                primary,
+                video_modes,
+            };
+            self.monitors.push((handle, monitor));
+        }
+
+        let disconnected = previous
+            .iter()
+            .filter(|(handle, _)| !self.monitors.iter().any(|(other, _)| other == handle))
+            .map(|(_, monitor)| monitor.info.clone())
+            .collect::<Vec<MonitorInfo>>();
+        let connected = self
+            .monitors
+            .iter()
+            .filter(|(handle, _)| !previous.iter().any(|(other, _)| other == handle))
+            .map(|(_, monitor)| monitor.info.clone())
+            .collect::<Vec<MonitorInfo>>();
+
+        world.resource_mut::<Monitors>().set_if_neq(Monitors {
+            monitors: self
+                .monitors
+                .iter()
+                .map(|(_, monitor)| monitor.clone())
+                .collect(),
+        });
+        for monitor in disconnected {
+            world.send_event(MonitorDisconnected { monitor });
+        }
+        for monitor in connected {
+            world.send_event(MonitorConnected { monitor });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use bevy_ecs::event::Events;
+    use bevy_math::{IVec2, UVec2};
+
+    use super::*;
+
+    fn info(x: i32) -> MonitorInfo {
+        MonitorInfo {
+            name: None,
+            physical_position: IVec2::new(x, 0),
+            physical_size: UVec2::new(1920, 1080),
+            scale_factor: 1.0,
+            refresh_rate_millihertz: None,
+        }
+    }
+
+    fn drain<E: bevy_ecs::event::Event>(world: &mut World) -> Vec<E> {
+        world.resource_mut::<Events<E>>().drain().collect()
+    }
+
+    #[test]
+    fn monitor_changes_are_reported() {
+        let mut world = World::new();
+        world.init_resource::<Monitors>();
+        world.init_resource::<Events<MonitorConnected>>();
+        world.init_resource::<Events<MonitorDisconnected>>();
+        let listings = Cell::new(0);
+        let video_modes = |_: &u32| {
+            listings.set(listings.get() + 1);
+            vec![VideoModeInfo {
+                physical_size: UVec2::new(1920, 1080),
+                bit_depth: 32,
+                refresh_rate_millihertz: 60_000,
+            }]
+        };
+
+        let mut tracker = MonitorTracker::<u32>::default();
+        tracker.update(&mut world, [(info(0), true, 1)], video_modes);
+        assert_eq!(world.resource::<Monitors>().len(), 1);
+        assert!(world.resource::<Monitors>().primary().is_some());
+        assert_eq!(
+            drain::<MonitorConnected>(&mut world),
+            vec![MonitorConnected { monitor: info(0) }]
+        );
+
+        tracker.update(
+            &mut world,
+            [(info(0), true, 1), (info(1920), false, 2)],
+            video_modes,
+        );
+        assert_eq!(world.resource::<Monitors>().len(), 2);
+        assert_eq!(
+            drain::<MonitorConnected>(&mut world),
+            vec![MonitorConnected {
+                monitor: info(1920)
+            }]
+        );
+        // The video modes of the monitor listed before aren't listed again.
+        assert_eq!(listings.get(), 2);
+
+        tracker.update(&mut world, [(info(1920), true, 2)], video_modes);
+        assert_eq!(
+            world
+                .resource::<Monitors>()
+                .get(0)
+                .map(|monitor| &monitor.info),
+            Some(&info(1920))
+        );
+        assert_eq!(
+            drain::<MonitorDisconnected>(&mut world),
+            vec![MonitorDisconnected { monitor: info(0) }]
+        );
+        assert!(drain::<MonitorConnected>(&mut world).is_empty());
+    }
+}
@@ -0,0 +1,114 @@
+use std::{
+    any::Any,
+    panic,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use bevy_app::{App, Plugin};
+use bevy_ecs::prelude::*;
+
+/// Opt-in plugin showing a native message box with the panic message when the app panics, before
+/// the process dies.
+///
+/// Without it, players of a shipped game only see the window vanish, as the panic message is
+/// printed to a console they usually don't have.
+///
+/// The message box is only shown by the thread running the app, for panics unwinding out of the
+/// `winit` runner and ending the app: panics caught by the app, e.g. in a task, don't show it.
+/// The plugin chains the previous panic hook, so the message is still logged as usual.
+///
+/// # Supported platforms
+///
+/// Windows (`MessageBoxW`) and macOS (`NSAlert`). Other platforms have no system message box API,
+/// so this plugin does nothing there.
+pub struct PanicDialogPlugin {
+    /// The title of the message box, e.g. the name of the game.
+    pub title: String,
+}
+
+impl Default for PanicDialogPlugin {
+    fn default() -> Self {
+        Self {
+            title: "Error".to_owned(),
+        }
+    }
+}
+
+impl Plugin for PanicDialogPlugin {
+    fn build(&self, app: &mut App) {
+        let dialog = PanicDialog {
+            title: self.title.clone(),
+            last_panic: Arc::default(),
+        };
+
+        // Only the message is recorded here: the hook can't know if the panic will be caught, and
+        // may run on any thread.
+        let last_panic = dialog.last_panic.clone();
+        let previous_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            previous_hook(info);
+
+            let payload = payload_message(info.payload());
+            let message = match info.location() {
+                Some(location) => format!("{payload}\n\n({location})"),
+                None => payload.to_owned(),
+            };
+            *last_panic.lock().unwrap_or_else(PoisonError::into_inner) = Some(message);
+        }));
+
+        app.insert_resource(dialog);
+    }
+}
+
+/// The settings of the [`PanicDialogPlugin`], and the message of the last panic recorded by its
+/// hook.
+#[derive(Resource)]
+pub(crate) struct PanicDialog {
+    title: String,
+    last_panic: Arc<Mutex<Option<String>>>,
+}
+
+impl PanicDialog {
+    /// Shows the message box for the panic with `payload` unwinding out of the runner, if the
+    /// [`PanicDialogPlugin`] was added.
+    pub(crate) fn show(world: &World, payload: &(dyn Any + Send)) {
+        let Some(dialog) = world.get_resource::<PanicDialog>() else {
+            return;
+        };
+
+        // Panics of the systems running on other threads are resumed on the main thread, with
+        // the payload of the original panic whose location the hook recorded.
+        let message = dialog
+            .last_panic
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+            .unwrap_or_else(|| payload_message(payload).to_owned());
+        show_message_box(
+            &dialog.title,
+            &format!("The application crashed:\n\n{message}"),
+        );
+    }
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("Box<dyn Any>")
+}
+
+/// Shows a blocking error message box.
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+fn show_message_box(title: &str, message: &str) {
+    rfd::MessageDialog::new()
+        .set_level(rfd::MessageLevel::Error)
+        .set_buttons(rfd::MessageButtons::Ok)
+        .set_title(title)
+        .set_description(message)
+        .show();
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn show_message_box(_title: &str, _message: &str) {}
@@ -0,0 +1,272 @@
+use std::mem;
+
+use bevy_app::{App, First, Plugin};
+use bevy_ecs::prelude::*;
+use bevy_time::{Real, Time, TimeSystem, TimeUpdateStrategy};
+use bevy_utils::{tracing::info, Duration};
+use bevy_window::RequestRedraw;
+
+use crate::{WinitEvent, WinitEventBuffer, WinitEventSystem, WinitEvents};
+
+/// Adds the [`WinitReplay`] resource, recording the translated [`WinitEvent`]s and the time
+/// elapsed on each update, and replaying them later.
+///
+/// While replaying, each update receives the events of the recorded update in the same order and
+/// advances time by the same duration, regardless of how long the update took. The events the OS
+/// sends meanwhile are discarded before they are translated, so they neither reach the app nor
+/// update the [`Window`](bevy_window::Window) components. Replaying a session from the same
+/// initial state thus reproduces it exactly, which makes bug reports about input and window
+/// handling reproducible.
+///
+/// **Note:** Replayed events don't update the [`Window`](bevy_window::Window) components either,
+/// so a session should be replayed in windows of the same size as the recorded ones.
+#[derive(Default)]
+pub struct WinitReplayPlugin;
+
+impl Plugin for WinitReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WinitReplay>()
+            .init_resource::<TimeUpdateStrategy>()
+            .add_systems(
+                WinitEvents,
+                replay_winit_events.before(WinitEventSystem::Translate),
+            )
+            .add_systems(First, record_winit_events.after(TimeSystem));
+    }
+}
+
+/// The events and timings of a recorded session, see [`WinitReplayPlugin`].
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct WinitRecording {
+    /// The recorded updates, in order.
+    pub frames: Vec<RecordedFrame>,
+}
+
+/// An update of a [`WinitRecording`].
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecordedFrame {
+    /// The real time elapsed since the previous update.
+    pub delta: Duration,
+    /// The events received by the update, in the order they were received.
+    pub events: Vec<WinitEvent>,
+}
+
+/// Resource recording and replaying sessions, added by the [`WinitReplayPlugin`].
+#[derive(Resource, Debug, Default)]
+pub enum WinitReplay {
+    /// Neither recording nor replaying.
+    #[default]
+    Off,
+    /// Recording the updates into the [`WinitRecording`].
+    Recording(WinitRecording),
+    /// Replaying the [`WinitRecording`], from the update at index `next_frame`.
+    Replaying {
+        /// The recording being replayed.
+        recording: WinitRecording,
+        /// The index of the next update to replay.
+        next_frame: usize,
+    },
+}
+
+impl WinitReplay {
+    /// Starts recording from the next update, discarding the current recording or replay.
+    pub fn start_recording(&mut self) {
+        *self = Self::Recording(WinitRecording::default());
+    }
+
+    /// Stops recording, returning the recorded session.
+    ///
+    /// Returns `None` if the session wasn't being recorded.
+    pub fn stop_recording(&mut self) -> Option<WinitRecording> {
+        match mem::take(self) {
+            Self::Recording(recording) => Some(recording),
+            other => {
+                *self = other;
+                None
+            }
+        }
+    }
+
+    /// Starts replaying `recording` from the next update, discarding the current recording or
+    /// replay.
+    pub fn replay(&mut self, recording: WinitRecording) {
+        *self = Self::Replaying {
+            recording,
+            next_frame: 0,
+        };
+    }
+
+    /// Returns `true` if a session is being recorded.
+    pub fn is_recording(&self) -> bool {
+        matches!(self, Self::Recording(_))
+    }
+
+    /// Returns `true` if a session is being replayed.
+    pub fn is_replaying(&self) -> bool {
+        matches!(self, Self::Replaying { .. })
+    }
+}
+
+/// Replaces the events received for this update by the recorded ones, and sets the time elapsed.
+fn replay_winit_events(
+    mut replay: ResMut<WinitReplay>,
+    mut buffer: NonSendMut<WinitEventBuffer>,
+    mut time_update_strategy: ResMut<TimeUpdateStrategy>,
+    mut redraw: EventWriter<RequestRedraw>,
+) {
+    let WinitReplay::Replaying {
+        recording,
+        next_frame,
+    } = &mut *replay
+    else {
+        return;
+    };
+    let Some(frame) = recording.frames.get(*next_frame) else {
+        info!("Finished replaying {} updates", recording.frames.len());
+        *replay = WinitReplay::Off;
+        *time_update_strategy = TimeUpdateStrategy::Automatic;
+        return;
+    };
+
+    // Discard the events of the OS before they update the windows, the recorded events are
+    // already translated.
+    buffer.raw.clear();
+    buffer.received.clear();
+    buffer.translated_received.clear();
+    buffer.translated.clone_from(&frame.events);
+    *time_update_strategy = TimeUpdateStrategy::ManualDuration(frame.delta);
+    *next_frame += 1;
+    // Keep updating in reactive update modes, as the OS events no longer drive the updates.
+    redraw.send(RequestRedraw);
+}
+
+fn record_winit_events(
+    mut replay: ResMut<WinitReplay>,
+    mut events: EventReader<WinitEvent>,
+    time: Res<Time<Real>>,
+) {
+    let WinitReplay::Recording(recording) = &mut *replay else {
+        events.clear();
+        return;
+    };
+    recording.frames.push(RecordedFrame {
+        delta: time.delta(),
+        events: events.read().cloned().collect(),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::MainScheduleOrder;
+    use bevy_ecs::schedule::ScheduleLabel;
+    use bevy_time::TimePlugin;
+    use bevy_window::{RequestRedraw, WindowFocused};
+    use winit::event::Event;
+
+    use super::*;
+    use crate::{winit_event::forward_winit_events, SequencedInput};
+
+    /// The frames received by the app, recorded like [`record_winit_events`] does.
+    #[derive(Resource, Default)]
+    struct ReceivedFrames(Vec<RecordedFrame>);
+
+    fn receive_frames(
+        mut received: ResMut<ReceivedFrames>,
+        mut events: EventReader<WinitEvent>,
+        time: Res<Time<Real>>,
+    ) {
+        received.0.push(RecordedFrame {
+            delta: time.delta(),
+            events: events.read().cloned().collect(),
+        });
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((TimePlugin, WinitReplayPlugin))
+            .init_non_send_resource::<WinitEventBuffer>()
+            .init_resource::<ReceivedFrames>()
+            .add_event::<WinitEvent>()
+            .add_event::<SequencedInput>()
+            .add_event::<WindowFocused>()
+            .add_event::<RequestRedraw>()
+            .init_schedule(WinitEvents)
+            .configure_sets(
+                WinitEvents,
+                (WinitEventSystem::Translate, WinitEventSystem::Forward).chain(),
+            )
+            .add_systems(
+                WinitEvents,
+                forward_winit_events.in_set(WinitEventSystem::Forward),
+            )
+            .add_systems(First, receive_frames.after(TimeSystem));
+        app.world_mut()
+            .resource_mut::<MainScheduleOrder>()
+            .insert_before(First, WinitEvents.intern());
+        // The first update has no time delta.
+        app.update();
+        app
+    }
+
+    fn focused(focused: bool) -> WinitEvent {
+        WinitEvent::WindowFocused(WindowFocused {
+            window: Entity::PLACEHOLDER,
+            focused,
+        })
+    }
+
+    #[test]
+    fn replay_reproduces_the_recording() {
+        let mut app = app();
+        app.world_mut()
+            .resource_mut::<WinitReplay>()
+            .start_recording();
+        for (delta, events) in [
+            (10, vec![focused(false)]),
+            (20, vec![]),
+            (30, vec![focused(true), focused(false)]),
+        ] {
+            *app.world_mut().resource_mut::<TimeUpdateStrategy>() =
+                TimeUpdateStrategy::ManualDuration(Duration::from_millis(delta));
+            app.world_mut()
+                .non_send_resource_mut::<WinitEventBuffer>()
+                .translated = events;
+            app.update();
+        }
+        let recording = app
+            .world_mut()
+            .resource_mut::<WinitReplay>()
+            .stop_recording()
+            .unwrap();
+        assert_eq!(recording.frames.len(), 3);
+        assert_eq!(recording.frames[2].events.len(), 2);
+
+        app.world_mut().resource_mut::<ReceivedFrames>().0.clear();
+        *app.world_mut().resource_mut::<TimeUpdateStrategy>() = TimeUpdateStrategy::Automatic;
+        app.world_mut()
+            .resource_mut::<WinitReplay>()
+            .replay(recording.clone());
+        for _ in 0..3 {
+            // The events of the OS are discarded while replaying.
+            app.world_mut()
+                .non_send_resource_mut::<WinitEventBuffer>()
+                .push(Event::UserEvent(RequestRedraw));
+            app.update();
+            assert!(app
+                .world()
+                .non_send_resource::<WinitEventBuffer>()
+                .raw_events()
+                .is_empty());
+        }
+        assert_eq!(app.world().resource::<ReceivedFrames>().0, recording.frames);
+
+        app.update();
+        assert!(!app.world().resource::<WinitReplay>().is_replaying());
+        assert!(matches!(
+            app.world().resource::<TimeUpdateStrategy>(),
+            TimeUpdateStrategy::Automatic
+        ));
+    }
+}
@@ -0,0 +1,163 @@
+use std::{fs, path::PathBuf, time::SystemTime};
+
+use bevy_app::{App, Last, Plugin};
+use bevy_ecs::prelude::*;
+use bevy_time::{Real, Time};
+use bevy_utils::{
+    tracing::{info, warn},
+    Duration,
+};
+
+use crate::WinitSettings;
+
+/// Loads the [`WinitSettings`] from a [RON](https://github.com/ron-rs/ron) file, and reloads
+/// them whenever the file changes.
+///
+/// This lets the update modes be tuned while the app runs, e.g. to find the
+/// [`ReactiveLowPower`](crate::UpdateMode::ReactiveLowPower) wait time that saves the most power
+/// without hurting responsiveness, without recompiling. Fields missing from the file keep their
+/// default value.
+///
+/// The file is read once when the plugin is built, then its modification time is checked every
+/// [`poll_interval`](Self::poll_interval). A file that can't be read or parsed leaves the current
+/// settings unchanged and logs a warning.
+///
+/// ```ron
+/// (
+///     focused_mode: Reactive(wait: (secs: 0, nanos: 16666667)),
+///     unfocused_mode: ReactiveLowPower(wait: (secs: 1, nanos: 0)),
+///     low_latency_input: true,
+/// )
+/// ```
+///
+/// # Supported platforms
+///
+/// All platforms but `wasm32`, which has no file system. Add this plugin after the
+/// [`WinitPlugin`](crate::WinitPlugin), as it replaces the [`WinitSettings`] resource.
+pub struct WinitSettingsFilePlugin {
+    /// The path of the settings file.
+    pub path: PathBuf,
+    /// How often to check whether the file changed, in real time.
+    pub poll_interval: Duration,
+}
+
+impl WinitSettingsFilePlugin {
+    /// Creates the plugin loading the settings from `path`, checking it for changes every second.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            poll_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+impl Plugin for WinitSettingsFilePlugin {
+    fn build(&self, app: &mut App) {
+        let mut file = WinitSettingsFile {
+            path: self.path.clone(),
+            poll_interval: self.poll_interval,
+            modified: None,
+            since_last_poll: Duration::ZERO,
+        };
+        if let Some(settings) = file.reload() {
+            app.insert_resource(settings);
+        }
+        app.insert_resource(file)
+            .add_systems(Last, reload_winit_settings);
+    }
+}
+
+/// The state of the [`WinitSettingsFilePlugin`].
+#[derive(Resource)]
+struct WinitSettingsFile {
+    path: PathBuf,
+    poll_interval: Duration,
+    modified: Option<SystemTime>,
+    since_last_poll: Duration,
+}
+
+impl WinitSettingsFile {
+    /// Reads and parses the file if it changed since it was last read.
+    fn reload(&mut self) -> Option<WinitSettings> {
+        let modified = match fs::metadata(&self.path).and_then(|metadata| metadata.modified()) {
+            Ok(modified) => modified,
+            Err(err) => {
+                warn!("Failed to read {}: {err}", self.path.display());
+                return None;
+            }
+        };
+        if self.modified == Some(modified) {
+            return None;
+        }
+        self.modified = Some(modified);
+
+        let settings = fs::read_to_string(&self.path)
+            .map_err(|err| err.to_string())
+            .and_then(|contents| ron::from_str(&contents).map_err(|err| err.to_string()));
+        match settings {
+            Ok(settings) => {
+                info!("Loaded the winit settings from {}", self.path.display());
+                Some(settings)
+            }
+            Err(err) => {
+                warn!("Failed to load {}: {err}", self.path.display());
+                None
+            }
+        }
+    }
+}
+
+fn reload_winit_settings(
+    mut commands: Commands,
+    mut file: ResMut<WinitSettingsFile>,
+    time: Res<Time<Real>>,
+) {
+    file.since_last_poll += time.delta();
+    if file.since_last_poll < file.poll_interval {
+        return;
+    }
+    file.since_last_poll = Duration::ZERO;
+
+    if let Some(settings) = file.reload() {
+        commands.insert_resource(settings);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::UpdateMode;
+
+    use super::*;
+
+    #[test]
+    fn settings_are_parsed_and_applied() {
+        let path =
+            std::env::temp_dir().join(format!("bevy_winit_settings_{}.ron", std::process::id()));
+        fs::write(
+            &path,
+            "(focused_mode: Continuous, unfocused_mode: BusyPoll, low_latency_input: true)",
+        )
+        .unwrap();
+
+        let mut app = App::new();
+        app.add_plugins(WinitSettingsFilePlugin::new(&path));
+        let settings = app.world().resource::<WinitSettings>();
+        assert_eq!(settings.focused_mode, UpdateMode::Continuous);
+        assert_eq!(settings.unfocused_mode, UpdateMode::BusyPoll);
+        assert!(settings.low_latency_input);
+        // Fields missing from the file keep their default value.
+        assert_eq!(
+            settings.redraw_broadcast,
+            WinitSettings::default().redraw_broadcast
+        );
+
+        // An unchanged file isn't parsed again, and an invalid one is ignored.
+        let mut file = app.world_mut().resource_mut::<WinitSettingsFile>();
+        assert!(file.reload().is_none());
+        fs::write(&path, "(focused_mode: ").unwrap();
+        file.modified = None;
+        assert!(file.reload().is_none());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
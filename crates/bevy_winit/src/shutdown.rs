@@ -0,0 +1,84 @@
+use bevy_app::AppExit;
+use bevy_ecs::{prelude::*, system::SystemState};
+use bevy_math::ivec2;
+use bevy_window::Window;
+
+use crate::WinitWindows;
+
+/// Why the runner is shutting down, see [`WinitShutdownHooks`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShutdownCause {
+    /// The app exited normally, with an [`AppExit`] event.
+    Exit(AppExit),
+    /// The app panicked while handling an event. The panic resumes once the hooks have run.
+    Panic,
+}
+
+/// A callback invoked by the runner as it shuts down.
+pub type ShutdownHook = Box<dyn FnMut(&ShutdownCause, &mut World)>;
+
+/// Non-send resource holding callbacks that the runner invokes once as it shuts down, whether the
+/// app exited or panicked.
+///
+/// Before the hooks run, the position and size of the [`Window`] components are refreshed from
+/// the actual windows, so hooks persisting the window placement save the last geometry even if
+/// the app crashed before handling the latest events.
+///
+/// After a panic, the [`World`] may be in an inconsistent state, e.g. if a system panicked
+/// halfway through updating it, so hooks should only read what they need to flush.
+///
+/// The resource is initialized by [`WinitPlugin`](crate::WinitPlugin). Plugins added before it can
+/// call `world.init_non_send_resource::<WinitShutdownHooks>()` themselves to register their hooks.
+///
+/// Use `NonSendMut<WinitShutdownHooks>` to access this resource.
+#[derive(Default)]
+pub struct WinitShutdownHooks {
+    hooks: Vec<ShutdownHook>,
+}
+
+impl WinitShutdownHooks {
+    /// Registers a callback run when the runner shuts down.
+    pub fn add_hook(
+        &mut self,
+        hook: impl FnMut(&ShutdownCause, &mut World) + 'static,
+    ) -> &mut Self {
+        self.hooks.push(Box::new(hook));
+        self
+    }
+
+    /// Refreshes the window geometry and runs the hooks registered in `world`.
+    ///
+    /// The hooks are removed from `world`, so they run at most once.
+    pub(crate) fn run(world: &mut World, cause: &ShutdownCause) {
+        let Some(mut hooks) = world.remove_non_send_resource::<Self>() else {
+            return;
+        };
+        if hooks.hooks.is_empty() {
+            return;
+        }
+
+        sync_window_geometry(world);
+        for hook in &mut hooks.hooks {
+            hook(cause, world);
+        }
+    }
+}
+
+/// Copies the position and size of the actual windows to their [`Window`] components.
+fn sync_window_geometry(world: &mut World) {
+    let mut state =
+        SystemState::<(NonSend<WinitWindows>, Query<(Entity, &mut Window)>)>::new(world);
+    let (winit_windows, mut windows) = state.get_mut(world);
+    for (entity, mut window) in &mut windows {
+        let Some(winit_window) = winit_windows.get_window(entity) else {
+            continue;
+        };
+        if let Ok(position) = winit_window.outer_position() {
+            window.position.set(ivec2(position.x, position.y));
+        }
+        let size = winit_window.inner_size();
+        window
+            .resolution
+            .set_physical_resolution(size.width, size.height);
+    }
+}
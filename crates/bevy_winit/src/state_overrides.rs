@@ -0,0 +1,106 @@
+use std::marker::PhantomData;
+
+use bevy_app::{App, Plugin};
+use bevy_ecs::{prelude::*, schedule::SystemConfigs};
+use bevy_state::state::{StateTransition, StateTransitionSteps, States};
+use bevy_utils::HashMap;
+
+/// Plugin applying the [`StateOverrides<S, O>`] after each transition of the
+/// [`State<S>`](bevy_state::state::State), see [`StateUpdateModesPlugin`](crate::StateUpdateModesPlugin)
+/// and [`StateWindowOverridesPlugin`](crate::StateWindowOverridesPlugin).
+pub struct StateOverridesPlugin<S: States, O: StateOverride>(PhantomData<(S, O)>);
+
+impl<S: States, O: StateOverride> Default for StateOverridesPlugin<S, O> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<S: States, O: StateOverride> Plugin for StateOverridesPlugin<S, O> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            StateTransition,
+            O::apply_system::<S>().after(StateTransitionSteps::EnterSchedules),
+        );
+    }
+}
+
+/// Properties of a [`Target`](StateOverride::Target) overridden while the
+/// [`State<S>`](bevy_state::state::State) has a given value, see [`StateOverrides`].
+pub trait StateOverride: Clone + Send + Sync + 'static {
+    /// What the overridden properties belong to.
+    type Target: 'static;
+
+    /// Returns the system calling [`StateOverrides::apply`] for the [`StateOverrides<S, Self>`]
+    /// that changed or whose state changed.
+    fn apply_system<S: States>() -> SystemConfigs;
+
+    /// Returns the current values of `target` for the properties overridden by `self`.
+    fn read(&self, target: &Self::Target) -> Self;
+
+    /// Sets the overridden properties on `target`, only touching the ones that differ.
+    fn write(&self, target: &mut Mut<Self::Target>);
+
+    /// Returns `self` without the properties that changed from their `applied` value to their
+    /// `current` one, so restoring `self` keeps the changes made while the overrides applied.
+    fn unedited(self, applied: &Self, current: &Self) -> Self;
+}
+
+/// The overrides applied in each value of the [`State<S>`](bevy_state::state::State), and the
+/// values they replaced.
+///
+/// The overrides of the current state are applied by [`StateOverrides::apply`]. The properties
+/// overridden in the previous state are first set back to the values they had before, unless
+/// they were changed in the meantime, so the target ends up as configured in states without
+/// overrides of their own.
+#[derive(Resource, Component, Debug, Clone)]
+pub struct StateOverrides<S: States, O: StateOverride> {
+    overrides: HashMap<S, O>,
+    /// The overrides applied in the current state, and the values they replaced.
+    applied: Option<(O, O)>,
+}
+
+impl<S: States, O: StateOverride> Default for StateOverrides<S, O> {
+    fn default() -> Self {
+        Self {
+            overrides: HashMap::default(),
+            applied: None,
+        }
+    }
+}
+
+impl<S: States, O: StateOverride> StateOverrides<S, O> {
+    /// Applies `overrides` in `state`.
+    pub fn with_overrides(mut self, state: S, overrides: O) -> Self {
+        self.set_overrides(state, overrides);
+        self
+    }
+
+    /// Applies `overrides` in `state`, replacing its previous overrides.
+    pub fn set_overrides(&mut self, state: S, overrides: O) {
+        self.overrides.insert(state, overrides);
+    }
+
+    /// Removes the overrides of `state`, returning them if it had some.
+    pub fn remove_overrides(&mut self, state: &S) -> Option<O> {
+        self.overrides.remove(state)
+    }
+
+    /// Returns the overrides of `state`, if it has some.
+    pub fn overrides(&self, state: &S) -> Option<&O> {
+        self.overrides.get(state)
+    }
+
+    /// Restores the properties overridden in the previous state, then applies the overrides of
+    /// `state` to `target`.
+    pub fn apply(&mut self, state: Option<&S>, mut target: Mut<O::Target>) {
+        if let Some((applied, replaced)) = self.applied.take() {
+            let current = applied.read(&target);
+            replaced.unedited(&applied, &current).write(&mut target);
+        }
+        if let Some(overrides) = state.and_then(|state| self.overrides.get(state)) {
+            self.applied = Some((overrides.clone(), overrides.read(&target)));
+            overrides.write(&mut target);
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use bevy_ecs::{prelude::*, schedule::SystemConfigs};
+use bevy_state::state::{State, States};
+
+use crate::{StateOverride, StateOverrides, StateOverridesPlugin, UpdateMode, WinitSettings};
+
+/// Plugin switching the update modes of the [`WinitSettings`] with the [`State<S>`], e.g. to run
+/// [`Continuous`](UpdateMode::Continuous) in game and use a low-power
+/// [`Reactive`](UpdateMode::Reactive) mode in menus.
+///
+/// The focused and unfocused modes are set from the [`StateUpdateModes<S>`] resource after each
+/// transition of `S`, and the runner picks them up as soon as the update ends. In states without
+/// modes of their own, the modes of the [`WinitSettings`] in effect before entering a state with
+/// its own modes are restored, unless the app changed them in the meantime.
+///
+/// ```no_run
+/// # use bevy_app::App;
+/// # use bevy_state::prelude::*;
+/// # use bevy_utils::Duration;
+/// # use bevy_winit::{StateUpdateModes, StateUpdateModesPlugin, UpdateMode};
+/// # #[derive(States, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+/// # enum GameState { #[default] Menu, InGame }
+/// let menu_mode = UpdateMode::Reactive {
+///     wait: Duration::from_secs(1),
+/// };
+/// App::new()
+///     .add_plugins(StateUpdateModesPlugin::<GameState>::default())
+///     .insert_resource(
+///         StateUpdateModes::default()
+///             .with_overrides(GameState::InGame, (UpdateMode::Continuous, menu_mode))
+///             .with_overrides(GameState::Menu, (menu_mode, menu_mode)),
+///     );
+/// ```
+pub type StateUpdateModesPlugin<S> = StateOverridesPlugin<S, (UpdateMode, UpdateMode)>;
+
+/// Resource holding the focused and unfocused update modes used in each value of the
+/// [`State<S>`], see [`StateUpdateModesPlugin`].
+pub type StateUpdateModes<S> = StateOverrides<S, (UpdateMode, UpdateMode)>;
+
+/// Overrides [`WinitSettings::focused_mode`] and [`WinitSettings::unfocused_mode`].
+impl StateOverride for (UpdateMode, UpdateMode) {
+    type Target = WinitSettings;
+
+    fn apply_system<S: States>() -> SystemConfigs {
+        apply_state_update_modes::<S>.into_configs()
+    }
+
+    fn read(&self, settings: &WinitSettings) -> Self {
+        (settings.focused_mode, settings.unfocused_mode)
+    }
+
+    fn write(&self, settings: &mut Mut<WinitSettings>) {
+        if self.read(settings) != *self {
+            (settings.focused_mode, settings.unfocused_mode) = *self;
+        }
+    }
+
+    fn unedited(self, applied: &Self, current: &Self) -> Self {
+        let (focused_mode, unfocused_mode) = self;
+        (
+            if applied.0 == current.0 {
+                focused_mode
+            } else {
+                current.0
+            },
+            if applied.1 == current.1 {
+                unfocused_mode
+            } else {
+                current.1
+            },
+        )
+    }
+}
+
+/// Applies the modes of the current state to the [`WinitSettings`].
+fn apply_state_update_modes<S: States>(
+    state: Option<Res<State<S>>>,
+    modes: Option<ResMut<StateUpdateModes<S>>>,
+    settings: Option<ResMut<WinitSettings>>,
+) {
+    let (Some(mut modes), Some(settings)) = (modes, settings) else {
+        return;
+    };
+    let state_changed = state.as_ref().is_some_and(|state| state.is_changed());
+    if !state_changed && !modes.is_changed() {
+        return;
+    }
+
+    modes
+        .bypass_change_detection()
+        .apply(state.as_deref().map(State::get), settings.into());
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_state::state::NextState;
+    use bevy_utils::Duration;
+
+    use super::*;
+
+    #[derive(States, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+    enum GameState {
+        #[default]
+        Menu,
+        InGame,
+    }
+
+    fn reactive(wait: u64) -> UpdateMode {
+        UpdateMode::Reactive {
+            wait: Duration::from_secs(wait),
+        }
+    }
+
+    fn set_state(app: &mut App, state: GameState) -> (UpdateMode, UpdateMode) {
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(state);
+        app.update();
+        let settings = app.world().resource::<WinitSettings>();
+        (settings.focused_mode, settings.unfocused_mode)
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.init_state::<GameState>()
+            .add_plugins(StateUpdateModesPlugin::<GameState>::default())
+            .insert_resource(WinitSettings {
+                focused_mode: reactive(1),
+                unfocused_mode: reactive(2),
+                ..Default::default()
+            })
+            .insert_resource(
+                StateUpdateModes::default()
+                    .with_overrides(GameState::InGame, (UpdateMode::Continuous, reactive(3))),
+            );
+        app.update();
+        app
+    }
+
+    #[test]
+    fn modes_are_restored_when_leaving_the_state() {
+        let mut app = app();
+        assert_eq!(
+            set_state(&mut app, GameState::InGame),
+            (UpdateMode::Continuous, reactive(3))
+        );
+        assert_eq!(
+            set_state(&mut app, GameState::Menu),
+            (reactive(1), reactive(2))
+        );
+    }
+
+    #[test]
+    fn modes_changed_in_the_state_are_kept() {
+        let mut app = app();
+        set_state(&mut app, GameState::InGame);
+        app.world_mut().resource_mut::<WinitSettings>().focused_mode = reactive(4);
+        assert_eq!(
+            set_state(&mut app, GameState::Menu),
+            (reactive(4), reactive(2))
+        );
+    }
+}
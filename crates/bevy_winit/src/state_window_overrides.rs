@@ -0,0 +1,221 @@
+use bevy_ecs::{prelude::*, schedule::SystemConfigs};
+use bevy_state::state::{State, States};
+use bevy_window::{CursorGrabMode, Window, WindowMode};
+
+use crate::{StateOverride, StateOverrides, StateOverridesPlugin};
+
+/// Plugin applying the [`StateWindowOverrides<S>`] of the windows with the [`State<S>`], e.g. to
+/// grab and hide the cursor in game and release it in menus.
+///
+/// The overrides of the current state are applied after each transition of `S`, and when the
+/// component is added or changed. The properties overridden in the previous state are first set
+/// back to the values they had before it overrode them, unless the app changed them in the
+/// meantime, so the windows end up as configured in states without overrides of their own.
+///
+/// ```no_run
+/// # use bevy_app::{App, Startup};
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_state::prelude::*;
+/// # use bevy_window::{CursorGrabMode, PrimaryWindow};
+/// # use bevy_winit::{StateWindowOverrides, StateWindowOverridesPlugin, WindowOverrides};
+/// # #[derive(States, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+/// # enum GameState { #[default] Menu, InGame, Editor }
+/// fn setup(mut commands: Commands, window: Query<Entity, With<PrimaryWindow>>) {
+///     commands.entity(window.single()).insert(
+///         StateWindowOverrides::default()
+///             .with_overrides(
+///                 GameState::InGame,
+///                 WindowOverrides {
+///                     cursor_grab_mode: Some(CursorGrabMode::Locked),
+///                     cursor_visible: Some(false),
+///                     ..Default::default()
+///                 },
+///             )
+///             .with_overrides(
+///                 GameState::Editor,
+///                 WindowOverrides {
+///                     title: Some("My Game - Editor".to_string()),
+///                     ..Default::default()
+///                 },
+///             ),
+///     );
+/// }
+///
+/// App::new()
+///     .add_plugins(StateWindowOverridesPlugin::<GameState>::default())
+///     .add_systems(Startup, setup);
+/// ```
+pub type StateWindowOverridesPlugin<S> = StateOverridesPlugin<S, WindowOverrides>;
+
+/// Window properties to override, see [`StateWindowOverrides`].
+///
+/// The fields left to `None` keep the value set on the [`Window`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WindowOverrides {
+    /// Overrides [`Window::title`].
+    pub title: Option<String>,
+    /// Overrides [`Window::mode`].
+    pub mode: Option<WindowMode>,
+    /// Overrides [`Cursor::grab_mode`](bevy_window::Cursor::grab_mode).
+    pub cursor_grab_mode: Option<CursorGrabMode>,
+    /// Overrides [`Cursor::visible`](bevy_window::Cursor::visible).
+    pub cursor_visible: Option<bool>,
+}
+
+impl StateOverride for WindowOverrides {
+    type Target = Window;
+
+    fn apply_system<S: States>() -> SystemConfigs {
+        apply_state_window_overrides::<S>.into_configs()
+    }
+
+    fn read(&self, window: &Window) -> Self {
+        Self {
+            title: self.title.as_ref().map(|_| window.title.clone()),
+            mode: self.mode.map(|_| window.mode),
+            cursor_grab_mode: self.cursor_grab_mode.map(|_| window.cursor.grab_mode),
+            cursor_visible: self.cursor_visible.map(|_| window.cursor.visible),
+        }
+    }
+
+    fn write(&self, window: &mut Mut<Window>) {
+        if let Some(title) = &self.title {
+            if window.title != *title {
+                window.title.clone_from(title);
+            }
+        }
+        if let Some(mode) = self.mode {
+            if window.mode != mode {
+                window.mode = mode;
+            }
+        }
+        if let Some(grab_mode) = self.cursor_grab_mode {
+            if window.cursor.grab_mode != grab_mode {
+                window.cursor.grab_mode = grab_mode;
+            }
+        }
+        if let Some(visible) = self.cursor_visible {
+            if window.cursor.visible != visible {
+                window.cursor.visible = visible;
+            }
+        }
+    }
+
+    fn unedited(self, applied: &Self, current: &Self) -> Self {
+        fn unedited<T: PartialEq>(
+            value: Option<T>,
+            applied: &Option<T>,
+            current: &Option<T>,
+        ) -> Option<T> {
+            value.filter(|_| applied == current)
+        }
+        Self {
+            title: unedited(self.title, &applied.title, &current.title),
+            mode: unedited(self.mode, &applied.mode, &current.mode),
+            cursor_grab_mode: unedited(
+                self.cursor_grab_mode,
+                &applied.cursor_grab_mode,
+                &current.cursor_grab_mode,
+            ),
+            cursor_visible: unedited(
+                self.cursor_visible,
+                &applied.cursor_visible,
+                &current.cursor_visible,
+            ),
+        }
+    }
+}
+
+/// Component holding the [`WindowOverrides`] of a window in each value of the [`State<S>`], see
+/// [`StateWindowOverridesPlugin`].
+pub type StateWindowOverrides<S> = StateOverrides<S, WindowOverrides>;
+
+/// Applies the overrides of the current state to the windows.
+fn apply_state_window_overrides<S: States>(
+    state: Option<Res<State<S>>>,
+    mut windows: Query<(&mut Window, &mut StateWindowOverrides<S>)>,
+) {
+    let state_changed = state.as_ref().is_some_and(|state| state.is_changed());
+    for (window, mut overrides) in &mut windows {
+        if !state_changed && !overrides.is_changed() {
+            continue;
+        }
+
+        overrides
+            .bypass_change_detection()
+            .apply(state.as_deref().map(State::get), window);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_state::state::NextState;
+
+    use super::*;
+
+    #[derive(States, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+    enum GameState {
+        #[default]
+        Menu,
+        InGame,
+    }
+
+    fn app() -> (App, Entity) {
+        let mut app = App::new();
+        app.init_state::<GameState>()
+            .add_plugins(StateWindowOverridesPlugin::<GameState>::default());
+        let window = app
+            .world_mut()
+            .spawn((
+                Window {
+                    title: "Game".to_string(),
+                    ..Default::default()
+                },
+                StateWindowOverrides::default().with_overrides(
+                    GameState::InGame,
+                    WindowOverrides {
+                        title: Some("Game - Playing".to_string()),
+                        cursor_grab_mode: Some(CursorGrabMode::Locked),
+                        ..Default::default()
+                    },
+                ),
+            ))
+            .id();
+        app.update();
+        (app, window)
+    }
+
+    fn set_state(app: &mut App, window: Entity, state: GameState) -> (String, CursorGrabMode) {
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(state);
+        app.update();
+        let window = app.world().get::<Window>(window).unwrap();
+        (window.title.clone(), window.cursor.grab_mode)
+    }
+
+    #[test]
+    fn overrides_are_restored_when_leaving_the_state() {
+        let (mut app, window) = app();
+        assert_eq!(
+            set_state(&mut app, window, GameState::InGame),
+            ("Game - Playing".to_string(), CursorGrabMode::Locked)
+        );
+        assert_eq!(
+            set_state(&mut app, window, GameState::Menu),
+            ("Game".to_string(), CursorGrabMode::None)
+        );
+    }
+
+    #[test]
+    fn properties_changed_in_the_state_are_kept() {
+        let (mut app, window) = app();
+        set_state(&mut app, window, GameState::InGame);
+        app.world_mut().get_mut::<Window>(window).unwrap().title = "Game - Level 2".to_string();
+        assert_eq!(
+            set_state(&mut app, window, GameState::Menu),
+            ("Game - Level 2".to_string(), CursorGrabMode::None)
+        );
+    }
+}
@@ -1,32 +1,50 @@
+use approx::relative_eq;
 use bevy_ecs::{
-    entity::Entity,
-    event::EventWriter,
-    prelude::{Changed, Component},
-    query::QueryFilter,
+    change_detection::{DetectChanges, DetectChangesMut},
+    entity::{Entity, EntityHashMap, EntityHashSet},
+    event::{EventWriter, Events},
+    prelude::{Added, Changed, Component, Ref},
+    query::{QueryFilter, Without},
     removal_detection::RemovedComponents,
-    system::{NonSendMut, Query, SystemParamItem},
+    system::{Commands, Local, NonSend, NonSendMut, Query, Res, ResMut, Resource, SystemParamItem},
+    world::Mut,
 };
-use bevy_utils::tracing::{error, info, warn};
+use bevy_input::{
+    mouse::{MouseButtonInput, MouseMotion, MouseScrollUnit, MouseWheel},
+    touchpad::{TouchpadMagnify, TouchpadRotate},
+};
+use bevy_math::{ivec2, DVec2, UVec2, Vec2};
+use bevy_utils::{
+    tracing::{error, info, warn},
+    Duration, Instant,
+};
+#[allow(deprecated)]
 use bevy_window::{
-    ClosingWindow, RawHandleWrapper, Window, WindowClosed, WindowClosing, WindowCreated,
-    WindowMode, WindowResized,
+    ApplicationLifetime, ClosingWindow, CursorEntered, CursorLeft, CursorMoved, CursorTransferred,
+    FileDragAndDrop, Ime, ManagedWindow, PresentFeedback, PrimaryWindow, RawHandleWrapper,
+    ReceivedCharacter, RequestRedraw, SplashWindow, SurfaceDestroyed, SurfaceRecreated, Window,
+    WindowBackendScaleFactorChanged, WindowCloseRequested, WindowClosed, WindowClosing,
+    WindowCreated, WindowCycleDirection, WindowCycleRequested, WindowDestroyed, WindowError,
+    WindowFocusRequestCompleted, WindowFocused, WindowMoved, WindowOccluded, WindowOperation,
+    WindowRedrawRequested, WindowResized, WindowScaleFactorChanged, WindowSizeConstrained,
+    WindowThemeChanged,
 };
 
 use winit::{
-    dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize},
+    dpi::{LogicalSize, PhysicalSize},
+    event::{self, DeviceEvent, Event, WindowEvent},
     event_loop::EventLoopWindowTarget,
+    keyboard::{KeyCode, ModifiersState, PhysicalKey},
 };
 
 use bevy_ecs::query::With;
-#[cfg(target_arch = "wasm32")]
-use winit::platform::web::WindowExtWebSys;
 
 use crate::{
-    converters::{
-        self, convert_enabled_buttons, convert_window_level, convert_window_theme,
-        convert_winit_theme,
-    },
-    get_best_videomode, get_fitting_videomode, CreateWindowParams, WinitWindows,
+    backend::{BackendWindow, WindowBackend},
+    converters::{self, convert_winit_theme},
+    winit_windows::WinitCreateContext,
+    AnyWindowFocused, AppSendEvent, CreateWindowParams, RawWinitEvent, WinitEventBuffer,
+    WinitWindowHandle, WinitWindows,
 };
 
 /// Creates new windows on the [`winit`] backend for each entity with a newly-added
@@ -45,10 +63,37 @@ pub fn create_windows<F: QueryFilter + 'static>(
         mut adapters,
         mut handlers,
         accessibility_requested,
+        accesskit_enabled,
+        builder_hook,
     ): SystemParamItem<CreateWindowParams<F>>,
 ) {
-    for (entity, mut window) in &mut created_windows {
-        if winit_windows.get_window(entity).is_some() {
+    let mut context = WinitCreateContext {
+        event_loop,
+        adapters: &mut adapters,
+        handlers: &mut handlers,
+        accessibility_requested: &accessibility_requested,
+        accesskit_enabled: &accesskit_enabled,
+        builder_hook: &builder_hook,
+    };
+    create_backend_windows(
+        &mut *winit_windows,
+        &mut context,
+        &mut commands,
+        &mut created_windows,
+        &mut window_created_events,
+    );
+}
+
+/// Creates the windows of the `backend` for the entities of `created_windows` without one.
+pub(crate) fn create_backend_windows<B: WindowBackend, F: QueryFilter>(
+    backend: &mut B,
+    context: &mut B::CreateContext<'_>,
+    commands: &mut Commands,
+    created_windows: &mut Query<(Entity, &mut Window), F>,
+    window_created_events: &mut EventWriter<WindowCreated>,
+) {
+    for (entity, mut window) in created_windows.iter_mut() {
+        if backend.window(entity).is_some() {
             continue;
         }
 
@@ -58,76 +103,618 @@ pub fn create_windows<F: QueryFilter + 'static>(
             entity
         );
 
-        let winit_window = winit_windows.create_window(
-            event_loop,
-            entity,
-            &window,
-            &mut adapters,
-            &mut handlers,
-            &accessibility_requested,
-        );
+        let mut entity_commands = commands.entity(entity);
+        let backend_window = backend.create_window(context, entity, &window, &mut entity_commands);
 
-        if let Some(theme) = winit_window.theme() {
-            window.window_theme = Some(convert_winit_theme(theme));
+        if let Some(theme) = backend_window.theme() {
+            window.window_theme = Some(theme);
         }
 
         window
             .resolution
-            .set_scale_factor(winit_window.scale_factor() as f32);
+            .set_scale_factor(backend_window.scale_factor());
 
-        commands.entity(entity).insert(CachedWindow {
-            window: window.clone(),
-        });
-
-        if let Ok(handle_wrapper) = RawHandleWrapper::new(winit_window) {
-            commands.entity(entity).insert(handle_wrapper);
+        if window.opacity < 1.0 {
+            backend_window.set_opacity(window.opacity);
         }
 
-        #[cfg(target_arch = "wasm32")]
-        {
-            if window.fit_canvas_to_parent {
-                let canvas = winit_window
-                    .canvas()
-                    .expect("window.canvas() can only be called in main thread.");
-                let style = canvas.style();
-                style.set_property("width", "100%").unwrap();
-                style.set_property("height", "100%").unwrap();
-            }
-        }
+        entity_commands.insert((
+            CachedWindow {
+                window: window.clone(),
+            },
+            ManagedWindow,
+        ));
         window_created_events.send(WindowCreated { window: entity });
     }
 }
 
-pub(crate) fn despawn_windows(
-    closing: Query<Entity, With<ClosingWindow>>,
+/// Drives the close lifecycle of the windows marked with [`ClosingWindow`], see
+/// [`ClosingWindow`] for the order of the steps.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn despawn_windows<B: WindowBackend>(
+    mut commands: Commands,
+    closing: Query<(Entity, Ref<ClosingWindow>), (With<Window>, With<ManagedWindow>)>,
     mut closed: RemovedComponents<Window>,
     window_entities: Query<&Window>,
     mut closing_events: EventWriter<WindowClosing>,
+    mut destroyed_events: EventWriter<WindowDestroyed>,
     mut closed_events: EventWriter<WindowClosed>,
-    mut winit_windows: NonSendMut<WinitWindows>,
+    mut backend: NonSendMut<B>,
+    mut destroyed: Local<EntityHashSet>,
 ) {
-    for window in closing.iter() {
-        closing_events.send(WindowClosing { window });
+    for (window, marker) in &closing {
+        if marker.is_added() {
+            closing_events.send(WindowClosing { window });
+        } else if destroyed.insert(window) {
+            // The final update with the window alive has run.
+            info!("Destroying window {:?}", window);
+            backend.destroy_window(window);
+            commands
+                .entity(window)
+                .remove::<(RawHandleWrapper, WinitWindowHandle)>();
+            destroyed_events.send(WindowDestroyed { window });
+        } else {
+            destroyed.remove(&window);
+            commands.entity(window).despawn();
+            closed_events.send(WindowClosed { window });
+        }
     }
     for window in closed.read() {
-        info!("Closing window {:?}", window);
         // Guard to verify that the window is in fact actually gone,
         // rather than having the component added
         // and removed in the same frame.
-        if !window_entities.contains(window) {
-            winit_windows.remove_window(window);
+        if window_entities.contains(window) {
+            continue;
+        }
+        destroyed.remove(&window);
+        // Windows despawned by the app skip the lifecycle, destroy them right away.
+        if backend.destroy_window(window) {
+            info!("Closing window {:?}", window);
+            destroyed_events.send(WindowDestroyed { window });
             closed_events.send(WindowClosed { window });
         }
     }
 }
 
+/// Sends the [`SurfaceDestroyed`] and [`SurfaceRecreated`] events when the [`RawHandleWrapper`] of
+/// an open window is removed and inserted back.
+pub(crate) fn report_surface_changes(
+    created: Query<Entity, (With<Window>, Added<RawHandleWrapper>)>,
+    mut removed: RemovedComponents<RawHandleWrapper>,
+    open_windows: Query<(), (With<Window>, Without<ClosingWindow>)>,
+    mut destroyed_events: EventWriter<SurfaceDestroyed>,
+    mut recreated_events: EventWriter<SurfaceRecreated>,
+    mut without_surface: Local<EntityHashSet>,
+) {
+    for window in removed.read() {
+        if open_windows.contains(window) && without_surface.insert(window) {
+            destroyed_events.send(SurfaceDestroyed { window });
+        }
+    }
+    for window in &created {
+        if without_surface.remove(&window) {
+            recreated_events.send(SurfaceRecreated { window });
+        }
+    }
+    without_surface.retain(|window| open_windows.contains(*window));
+}
+
+/// Translates the raw [`winit`] events buffered by the runner into [`WinitEvent`](crate::WinitEvent)s,
+/// updating the [`Window`] components they affect.
+///
+/// This system runs in the [`WinitEventSystem::Translate`](crate::WinitEventSystem::Translate) set
+/// of the [`WinitEvents`](crate::WinitEvents) schedule.
+pub(crate) fn translate_winit_events(
+    mut buffer: NonSendMut<WinitEventBuffer>,
+    mut raw_events: Option<ResMut<Events<RawWinitEvent>>>,
+    winit_windows: NonSend<WinitWindows>,
+    mut windows: Query<(&mut Window, &mut CachedWindow)>,
+    mut started: Local<bool>,
+    mut modifiers: Local<ModifiersState>,
+    mut last_crossing: Local<Option<CursorCrossing>>,
+) {
+    let WinitEventBuffer {
+        raw,
+        received,
+        translated,
+        translated_received,
+        ..
+    } = &mut *buffer;
+
+    for (event, timestamp) in raw.drain(..).zip(received.drain(..)) {
+        if let Some(raw_events) = raw_events.as_mut() {
+            raw_events.send(RawWinitEvent {
+                window: match &event {
+                    Event::WindowEvent { window_id, .. } => {
+                        winit_windows.get_window_entity(*window_id)
+                    }
+                    _ => None,
+                },
+                event: event.clone(),
+                received: timestamp,
+            });
+        }
+        match event {
+            Event::WindowEvent { event, window_id } => {
+                let Some(window) = winit_windows.get_window_entity(window_id) else {
+                    continue;
+                };
+                let Ok((mut win, mut cache)) = windows.get_mut(window) else {
+                    continue;
+                };
+
+                if let WindowEvent::ModifiersChanged(new_modifiers) = &event {
+                    *modifiers = new_modifiers.state();
+                }
+                let cycle_direction = window_cycle_direction(&event, *modifiers);
+                let previous_size = win.physical_size();
+                let resized = matches!(event, WindowEvent::Resized(_));
+                let crossing = match event {
+                    WindowEvent::CursorEntered { .. } => Some(true),
+                    WindowEvent::CursorLeft { .. } => Some(false),
+                    _ => None,
+                };
+
+                let first_translated = translated.len();
+                translate_window_event(event, window, &mut win, translated);
+                translated_received
+                    .extend((first_translated..translated.len()).map(|index| (index, timestamp)));
+
+                if let (true, Some(aspect_ratio), Some(winit_window)) =
+                    (resized, win.aspect_ratio, winit_windows.get_window(window))
+                {
+                    if let Some(size) =
+                        aspect_ratio_size(win.physical_size(), previous_size, aspect_ratio)
+                    {
+                        // The window will be resized again once the OS applied the new size.
+                        let _ = (**winit_window).request_inner_size(size);
+                    }
+                }
+
+                if let Some(direction) = cycle_direction {
+                    translated.send(WindowCycleRequested { window, direction });
+                }
+
+                if let Some(entered) = crossing {
+                    // Platforms report the cursor leaving a window and entering the next one in
+                    // either order, so the two halves are paired regardless of their order.
+                    match last_crossing.take() {
+                        Some(last)
+                            if last.window != window
+                                && last.entered != entered
+                                && timestamp.saturating_duration_since(last.timestamp)
+                                    <= CURSOR_TRANSFER_DELAY =>
+                        {
+                            let (from, to) = if entered {
+                                (last.window, window)
+                            } else {
+                                (window, last.window)
+                            };
+                            translated.send(CursorTransferred { from, to });
+                        }
+                        _ => {
+                            *last_crossing = Some(CursorCrossing {
+                                window,
+                                entered,
+                                timestamp,
+                            });
+                        }
+                    }
+                }
+
+                if win.is_changed() {
+                    cache.window = win.clone();
+                }
+            }
+            Event::DeviceEvent {
+                event: DeviceEvent::MouseMotion { delta: (x, y) },
+                ..
+            } => {
+                let delta = Vec2::new(x as f32, y as f32);
+                translated.send(MouseMotion { delta });
+            }
+            Event::Suspended => {
+                translated.send(ApplicationLifetime::Suspended);
+            }
+            Event::Resumed => {
+                if *started {
+                    translated.send(ApplicationLifetime::Resumed);
+                } else {
+                    *started = true;
+                    translated.send(ApplicationLifetime::Started);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The longest time between the cursor leaving a window and entering another for the move to be
+/// reported as a [`CursorTransferred`] event.
+const CURSOR_TRANSFER_DELAY: Duration = Duration::from_millis(50);
+
+/// The cursor entering or leaving a window, waiting to be paired into a [`CursorTransferred`].
+#[derive(Clone, Copy)]
+pub(crate) struct CursorCrossing {
+    window: Entity,
+    entered: bool,
+    timestamp: Instant,
+}
+
+/// Updates [`AnyWindowFocused`] once the focus changes of the [`Window`] components have been
+/// translated.
+pub(crate) fn update_any_window_focused(
+    mut any_window_focused: ResMut<AnyWindowFocused>,
+    changed_windows: Query<(), Changed<Window>>,
+    mut removed_windows: RemovedComponents<Window>,
+    windows: Query<(Entity, &Window)>,
+) {
+    let removed = removed_windows.read().count() > 0;
+    if changed_windows.is_empty() && !removed {
+        return;
+    }
+
+    let focused = windows
+        .iter()
+        .find(|(_, window)| window.focused)
+        .map(|(entity, _)| entity);
+    any_window_focused.set_if_neq(AnyWindowFocused(focused));
+}
+
+/// Returns the size `size` should be changed to so it matches `aspect_ratio`, if it doesn't.
+///
+/// The dimension that changed the most since `previous_size` is kept, so that dragging any edge of
+/// the window resizes it as expected.
+fn aspect_ratio_size(
+    size: UVec2,
+    previous_size: UVec2,
+    aspect_ratio: f32,
+) -> Option<PhysicalSize<u32>> {
+    if !aspect_ratio.is_finite() || aspect_ratio <= 0.0 || size.x == 0 || size.y == 0 {
+        return None;
+    }
+
+    let corrected = if size.x.abs_diff(previous_size.x) >= size.y.abs_diff(previous_size.y) {
+        UVec2::new(size.x, (size.x as f32 / aspect_ratio).round() as u32)
+    } else {
+        UVec2::new((size.y as f32 * aspect_ratio).round() as u32, size.y)
+    }
+    .max(UVec2::ONE);
+
+    // Rounding can't always produce an exact ratio, so allow a pixel of error.
+    if corrected.x.abs_diff(size.x) <= 1 && corrected.y.abs_diff(size.y) <= 1 {
+        return None;
+    }
+    Some(PhysicalSize::new(corrected.x, corrected.y))
+}
+
+/// Returns the direction of the platform's window-cycling shortcut, if `event` is one.
+fn window_cycle_direction(
+    event: &WindowEvent,
+    modifiers: ModifiersState,
+) -> Option<WindowCycleDirection> {
+    let WindowEvent::KeyboardInput { event, .. } = event else {
+        return None;
+    };
+
+    #[cfg(target_os = "macos")]
+    let (key, modifier_pressed) = (KeyCode::Backquote, modifiers.super_key());
+    #[cfg(not(target_os = "macos"))]
+    let (key, modifier_pressed) = (KeyCode::F6, modifiers.control_key());
+
+    if !event.state.is_pressed()
+        || !modifier_pressed
+        || event.physical_key != PhysicalKey::Code(key)
+    {
+        return None;
+    }
+    Some(if modifiers.shift_key() {
+        WindowCycleDirection::Previous
+    } else {
+        WindowCycleDirection::Next
+    })
+}
+
+fn translate_window_event(
+    event: WindowEvent,
+    window: Entity,
+    win: &mut Mut<'_, Window>,
+    translated: &mut Vec<crate::WinitEvent>,
+) {
+    match event {
+        WindowEvent::Resized(size) => {
+            win.resolution
+                .set_physical_resolution(size.width, size.height);
+            translated.send(WindowResized {
+                window,
+                width: win.width(),
+                height: win.height(),
+            });
+        }
+        WindowEvent::CloseRequested => translated.send(WindowCloseRequested { window }),
+        WindowEvent::KeyboardInput { ref event, .. } => {
+            if event.state.is_pressed() {
+                if let Some(char) = &event.text {
+                    let char = char.clone();
+                    #[allow(deprecated)]
+                    translated.send(ReceivedCharacter { window, char });
+                }
+            }
+            translated.send(converters::convert_keyboard_input(event, window));
+        }
+        WindowEvent::CursorMoved { position, .. } => {
+            let physical_position = DVec2::new(position.x, position.y);
+
+            let last_position = win.physical_cursor_position();
+            let delta = last_position.map(|last_pos| {
+                (physical_position.as_vec2() - last_pos) / win.resolution.scale_factor()
+            });
+
+            win.set_physical_cursor_position(Some(physical_position));
+            let position = (physical_position / win.resolution.scale_factor() as f64).as_vec2();
+            translated.send(CursorMoved {
+                window,
+                position,
+                delta,
+            });
+        }
+        WindowEvent::CursorEntered { .. } => {
+            translated.send(CursorEntered { window });
+        }
+        WindowEvent::CursorLeft { .. } => {
+            win.set_physical_cursor_position(None);
+            translated.send(CursorLeft { window });
+        }
+        WindowEvent::MouseInput { state, button, .. } => {
+            translated.send(MouseButtonInput {
+                button: converters::convert_mouse_button(button),
+                state: converters::convert_element_state(state),
+                window,
+            });
+        }
+        WindowEvent::TouchpadMagnify { delta, .. } => {
+            translated.send(TouchpadMagnify(delta as f32));
+        }
+        WindowEvent::TouchpadRotate { delta, .. } => {
+            translated.send(TouchpadRotate(delta));
+        }
+        WindowEvent::MouseWheel { delta, .. } => match delta {
+            event::MouseScrollDelta::LineDelta(x, y) => {
+                translated.send(MouseWheel {
+                    unit: MouseScrollUnit::Line,
+                    x,
+                    y,
+                    window,
+                });
+            }
+            event::MouseScrollDelta::PixelDelta(p) => {
+                translated.send(MouseWheel {
+                    unit: MouseScrollUnit::Pixel,
+                    x: p.x as f32,
+                    y: p.y as f32,
+                    window,
+                });
+            }
+        },
+        WindowEvent::Touch(touch) => {
+            let location = touch
+                .location
+                .to_logical(win.resolution.scale_factor() as f64);
+            translated.send(converters::convert_touch_input(touch, location, window));
+        }
+        WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+            // The physical size requested for windows overriding the scale factor has already
+            // been applied by the runner, as `winit` only accepts it while handling the event.
+            let prior_factor = win.resolution.scale_factor();
+            let prior_base_factor = win.resolution.base_scale_factor();
+            let (prior_width, prior_height) = (win.width(), win.height());
+            let prior_inner_size = PhysicalSize::new(win.physical_width(), win.physical_height());
+            win.resolution.set_scale_factor(scale_factor as f32);
+            // Note: this may be different from new_scale_factor if
+            // `scale_factor_override` is set to Some(thing), or if the window prefers an integer
+            // scale factor
+            let new_factor = win.resolution.scale_factor();
+
+            // Predict the size `winit` gives the window, rounding like it does: truncating would
+            // make the surface a pixel smaller than the window with fractional scale factors,
+            // which makes it blurry.
+            let new_inner_size: PhysicalSize<u32> =
+                if win.resolution.scale_factor_override().is_some() {
+                    LogicalSize::new(prior_width, prior_height).to_physical(new_factor as f64)
+                } else {
+                    prior_inner_size
+                        .to_logical::<f64>(prior_base_factor as f64)
+                        .to_physical(scale_factor)
+                };
+            let new_logical_width = new_inner_size.width as f32 / new_factor;
+            let new_logical_height = new_inner_size.height as f32 / new_factor;
+
+            let width_equal = relative_eq!(prior_width, new_logical_width);
+            let height_equal = relative_eq!(prior_height, new_logical_height);
+            win.resolution
+                .set_physical_resolution(new_inner_size.width, new_inner_size.height);
+
+            translated.send(WindowBackendScaleFactorChanged {
+                window,
+                scale_factor,
+            });
+            if win.resolution.scale_factor_override().is_none()
+                && !relative_eq!(new_factor, prior_factor)
+            {
+                translated.send(WindowScaleFactorChanged {
+                    window,
+                    scale_factor: new_factor as f64,
+                });
+            }
+
+            if !width_equal || !height_equal {
+                translated.send(WindowResized {
+                    window,
+                    width: new_logical_width,
+                    height: new_logical_height,
+                });
+            }
+        }
+        WindowEvent::Focused(focused) => {
+            win.focused = focused;
+            translated.send(WindowFocused { window, focused });
+        }
+        WindowEvent::Occluded(occluded) => {
+            translated.send(WindowOccluded { window, occluded });
+        }
+        WindowEvent::RedrawRequested => {
+            translated.send(WindowRedrawRequested { window });
+        }
+        WindowEvent::DroppedFile(path_buf) => {
+            translated.send(FileDragAndDrop::DroppedFile { window, path_buf });
+        }
+        WindowEvent::HoveredFile(path_buf) => {
+            translated.send(FileDragAndDrop::HoveredFile { window, path_buf });
+        }
+        WindowEvent::HoveredFileCancelled => {
+            translated.send(FileDragAndDrop::HoveredFileCanceled { window });
+        }
+        WindowEvent::Moved(position) => {
+            let position = ivec2(position.x, position.y);
+            win.position.set(position);
+            translated.send(WindowMoved { window, position });
+        }
+        WindowEvent::Ime(event) => match event {
+            event::Ime::Preedit(value, cursor) => {
+                translated.send(Ime::Preedit {
+                    window,
+                    value,
+                    cursor,
+                });
+            }
+            event::Ime::Commit(value) => {
+                translated.send(Ime::Commit { window, value });
+            }
+            event::Ime::Enabled => {
+                translated.send(Ime::Enabled { window });
+            }
+            event::Ime::Disabled => {
+                translated.send(Ime::Disabled { window });
+            }
+        },
+        WindowEvent::ThemeChanged(theme) => {
+            translated.send(WindowThemeChanged {
+                window,
+                theme: convert_winit_theme(theme),
+            });
+        }
+        WindowEvent::Destroyed => {
+            translated.send(WindowDestroyed { window });
+        }
+        _ => {}
+    }
+}
+
+/// Sends a [`WindowSizeConstrained`] event if the logical size of `window` doesn't fit its resize
+/// constraints, returning `true` if it doesn't.
+fn check_size_constraints(
+    entity: Entity,
+    window: &Window,
+    window_constrained: &mut EventWriter<WindowSizeConstrained>,
+) -> bool {
+    let requested = Vec2::new(window.width(), window.height());
+    let constrained = window
+        .resize_constraints
+        .check_constraints()
+        .clamp(requested);
+    if constrained != requested {
+        window_constrained.send(WindowSizeConstrained {
+            window: entity,
+            requested,
+            constrained,
+        });
+    }
+    constrained != requested
+}
+
+/// Closes the [`SplashWindow`]s once a primary window that isn't a splash window has presented a
+/// frame, as reported through the [`PresentFeedback`].
+///
+/// Redraws are requested while waiting, so the splash windows also close in reactive update modes.
+pub(crate) fn close_splash_windows(
+    mut commands: Commands,
+    splash_windows: Query<Entity, (With<Window>, With<SplashWindow>)>,
+    primary_window: Query<
+        (),
+        (
+            With<Window>,
+            With<PrimaryWindow>,
+            With<CachedWindow>,
+            Without<SplashWindow>,
+        ),
+    >,
+    present_feedback: Option<Res<PresentFeedback>>,
+    mut redraw: EventWriter<RequestRedraw>,
+    mut primary_window_created: Local<Option<Instant>>,
+) {
+    if splash_windows.is_empty() {
+        return;
+    }
+    if primary_window.is_empty() {
+        *primary_window_created = None;
+        return;
+    }
+    // The first frame of the primary window is presented after the update it was created in.
+    let created = *primary_window_created.get_or_insert_with(Instant::now);
+    let presented = match present_feedback {
+        Some(present_feedback) => present_feedback
+            .last_presented()
+            .is_some_and(|presented| presented > created),
+        None => true,
+    };
+    if !presented {
+        redraw.send(RequestRedraw);
+        return;
+    }
+    for entity in &splash_windows {
+        commands.entity(entity).despawn();
+    }
+    *primary_window_created = None;
+}
+
+/// How long the OS has to grant a focus request before it is reported as refused.
+const FOCUS_REQUEST_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// The focus requests made with [`Window::request_focus`] waiting for the OS to grant them.
+#[derive(Resource, Debug, Default)]
+pub(crate) struct PendingFocusRequests(EntityHashMap<Instant>);
+
+/// Sends a [`WindowFocusRequestCompleted`] event once a window requesting the focus got it, or
+/// once it didn't get it in time.
+pub(crate) fn report_focus_requests<B: WindowBackend>(
+    mut focus_requests: ResMut<PendingFocusRequests>,
+    backend: NonSend<B>,
+    mut completed: EventWriter<WindowFocusRequestCompleted>,
+) {
+    focus_requests.0.retain(|&window, requested_at| {
+        let Some(backend_window) = backend.window(window) else {
+            return false;
+        };
+        let granted = backend_window.has_focus();
+        if !granted && requested_at.elapsed() < FOCUS_REQUEST_TIMEOUT {
+            return true;
+        }
+        completed.send(WindowFocusRequestCompleted { window, granted });
+        false
+    });
+}
+
 /// The cached state of the window so we can check which properties were changed from within the app.
 #[derive(Debug, Clone, Component)]
 pub struct CachedWindow {
     pub window: Window,
 }
 
-/// Propagates changes from [`Window`] entities to the [`winit`] backend.
+/// Propagates changes from [`Window`] entities to the windows of the backend.
+///
+/// Each field is compared with the state last applied, kept in [`CachedWindow`], and only the
+/// fields that changed are applied. Operations refused by the platform send a
+/// [`WindowError`] event.
 ///
 /// # Notes
 ///
@@ -135,160 +722,147 @@ pub struct CachedWindow {
 /// - [`Window::transparent`] cannot be changed after the window is created.
 /// - [`Window::canvas`] cannot be changed after the window is created.
 /// - [`Window::focused`] cannot be manually changed to `false` after the window is created.
-pub(crate) fn changed_windows(
+pub(crate) fn changed_windows<B: WindowBackend>(
     mut changed_windows: Query<(Entity, &mut Window, &mut CachedWindow), Changed<Window>>,
-    winit_windows: NonSendMut<WinitWindows>,
+    mut backend: NonSendMut<B>,
     mut window_resized: EventWriter<WindowResized>,
+    mut window_constrained: EventWriter<WindowSizeConstrained>,
+    mut focus_requests: ResMut<PendingFocusRequests>,
+    mut window_errors: EventWriter<WindowError>,
 ) {
+    let mut grab_results = Vec::new();
     for (entity, mut window, mut cache) in &mut changed_windows {
-        let Some(winit_window) = winit_windows.get_window(entity) else {
+        let Some(backend_window) = backend.window_mut(entity) else {
             continue;
         };
 
         if window.title != cache.window.title {
-            winit_window.set_title(window.title.as_str());
+            backend_window.set_title(window.title.as_str());
         }
 
         if window.mode != cache.window.mode {
-            let new_mode = match window.mode {
-                WindowMode::BorderlessFullscreen => {
-                    Some(Some(winit::window::Fullscreen::Borderless(None)))
-                }
-                mode @ (WindowMode::Fullscreen | WindowMode::SizedFullscreen) => {
-                    if let Some(current_monitor) = winit_window.current_monitor() {
-                        let videomode = match mode {
-                            WindowMode::Fullscreen => get_best_videomode(&current_monitor),
-                            WindowMode::SizedFullscreen => get_fitting_videomode(
-                                &current_monitor,
-                                window.width() as u32,
-                                window.height() as u32,
-                            ),
-                            _ => unreachable!(),
-                        };
-
-                        Some(Some(winit::window::Fullscreen::Exclusive(videomode)))
-                    } else {
-                        warn!("Could not determine current monitor, ignoring exclusive fullscreen request for window {:?}", window.title);
-                        None
-                    }
-                }
-                WindowMode::Windowed => Some(None),
-            };
-
-            if let Some(new_mode) = new_mode {
-                if winit_window.fullscreen() != new_mode {
-                    winit_window.set_fullscreen(new_mode);
-                }
+            if let Err(error) = backend_window.set_mode(&window) {
+                warn!(
+                    "Ignoring the fullscreen request for window {:?}: {error}",
+                    window.title
+                );
+                window_errors.send(WindowError {
+                    window: entity,
+                    operation: WindowOperation::SetFullscreen,
+                    error,
+                });
             }
         }
-        if window.resolution != cache.window.resolution {
-            let physical_size = PhysicalSize::new(
-                window.resolution.physical_width(),
-                window.resolution.physical_height(),
-            );
-            if let Some(size_now) = winit_window.request_inner_size(physical_size) {
+
+        if window.resolution != cache.window.resolution
+            || window.aspect_ratio != cache.window.aspect_ratio
+        {
+            let constrained = check_size_constraints(entity, &window, &mut window_constrained);
+            let size = window.physical_size();
+            let physical_size = window
+                .aspect_ratio
+                .and_then(|aspect_ratio| aspect_ratio_size(size, size, aspect_ratio))
+                .unwrap_or(PhysicalSize::new(size.x, size.y));
+            if let Some(size_now) = backend_window.request_inner_size(physical_size) {
+                // Sizes changed by the constraints are reported with `WindowSizeConstrained`.
+                if size_now != physical_size && !constrained {
+                    window_errors.send(WindowError {
+                        window: entity,
+                        operation: WindowOperation::RequestInnerSize,
+                        error: format!(
+                            "requested {}x{}, got {}x{}",
+                            physical_size.width,
+                            physical_size.height,
+                            size_now.width,
+                            size_now.height
+                        ),
+                    });
+                }
                 crate::react_to_resize(&mut window, size_now, &mut window_resized, entity);
             }
         }
 
         if window.physical_cursor_position() != cache.window.physical_cursor_position() {
             if let Some(physical_position) = window.physical_cursor_position() {
-                let position = PhysicalPosition::new(physical_position.x, physical_position.y);
-
-                if let Err(err) = winit_window.set_cursor_position(position) {
-                    error!("could not set cursor position: {:?}", err);
+                if let Err(error) = backend_window.set_cursor_position(physical_position) {
+                    error!("could not set cursor position: {}", error);
+                    window_errors.send(WindowError {
+                        window: entity,
+                        operation: WindowOperation::SetCursorPosition,
+                        error,
+                    });
                 }
             }
         }
 
         if window.cursor.icon != cache.window.cursor.icon {
-            winit_window.set_cursor_icon(converters::convert_cursor_icon(window.cursor.icon));
+            backend_window.set_cursor_icon(window.cursor.icon);
         }
 
         if window.cursor.grab_mode != cache.window.cursor.grab_mode {
-            crate::winit_windows::attempt_grab(winit_window, window.cursor.grab_mode);
+            let grabbed = backend_window.set_cursor_grab(window.cursor.grab_mode);
+            grab_results.push((entity, grabbed));
         }
 
         if window.cursor.visible != cache.window.cursor.visible {
-            winit_window.set_cursor_visible(window.cursor.visible);
+            backend_window.set_cursor_visible(window.cursor.visible);
         }
 
         if window.cursor.hit_test != cache.window.cursor.hit_test {
-            if let Err(err) = winit_window.set_cursor_hittest(window.cursor.hit_test) {
+            if let Err(error) = backend_window.set_cursor_hit_test(window.cursor.hit_test) {
                 window.cursor.hit_test = cache.window.cursor.hit_test;
                 warn!(
-                    "Could not set cursor hit test for window {:?}: {:?}",
-                    window.title, err
+                    "Could not set cursor hit test for window {:?}: {}",
+                    window.title, error
                 );
+                window_errors.send(WindowError {
+                    window: entity,
+                    operation: WindowOperation::SetCursorHitTest,
+                    error,
+                });
             }
         }
 
-        if window.decorations != cache.window.decorations
-            && window.decorations != winit_window.is_decorated()
-        {
-            winit_window.set_decorations(window.decorations);
+        if window.decorations != cache.window.decorations {
+            backend_window.set_decorations(window.decorations);
         }
 
-        if window.resizable != cache.window.resizable
-            && window.resizable != winit_window.is_resizable()
-        {
-            winit_window.set_resizable(window.resizable);
+        if window.resizable != cache.window.resizable {
+            backend_window.set_resizable(window.resizable);
         }
 
         if window.enabled_buttons != cache.window.enabled_buttons {
-            winit_window.set_enabled_buttons(convert_enabled_buttons(window.enabled_buttons));
+            backend_window.set_enabled_buttons(window.enabled_buttons);
         }
 
         if window.resize_constraints != cache.window.resize_constraints {
-            let constraints = window.resize_constraints.check_constraints();
-            let min_inner_size = LogicalSize {
-                width: constraints.min_width,
-                height: constraints.min_height,
-            };
-            let max_inner_size = LogicalSize {
-                width: constraints.max_width,
-                height: constraints.max_height,
-            };
-
-            winit_window.set_min_inner_size(Some(min_inner_size));
-            if constraints.max_width.is_finite() && constraints.max_height.is_finite() {
-                winit_window.set_max_inner_size(Some(max_inner_size));
-            }
+            backend_window.set_resize_constraints(window.resize_constraints);
+            check_size_constraints(entity, &window, &mut window_constrained);
         }
 
         if window.position != cache.window.position {
-            if let Some(position) = crate::winit_window_position(
-                &window.position,
-                &window.resolution,
-                winit_window.available_monitors(),
-                winit_window.primary_monitor(),
-                winit_window.current_monitor(),
-            ) {
-                let should_set = match winit_window.outer_position() {
-                    Ok(current_position) => current_position != position,
-                    _ => true,
-                };
-
-                if should_set {
-                    winit_window.set_outer_position(position);
-                }
-            }
+            backend_window.set_position(&window);
         }
 
         if let Some(maximized) = window.internal.take_maximize_request() {
-            winit_window.set_maximized(maximized);
+            backend_window.set_maximized(maximized);
         }
 
         if let Some(minimized) = window.internal.take_minimize_request() {
-            winit_window.set_minimized(minimized);
+            backend_window.set_minimized(minimized);
         }
 
         if window.focused != cache.window.focused && window.focused {
-            winit_window.focus_window();
+            backend_window.focus();
+        }
+
+        if window.internal.take_focus_request() {
+            backend_window.focus();
+            focus_requests.0.insert(entity, Instant::now());
         }
 
         if window.window_level != cache.window.window_level {
-            winit_window.set_window_level(convert_window_level(window.window_level));
+            backend_window.set_window_level(window.window_level);
         }
 
         // Currently unsupported changes
@@ -306,24 +880,244 @@ pub(crate) fn changed_windows(
         }
 
         if window.ime_enabled != cache.window.ime_enabled {
-            winit_window.set_ime_allowed(window.ime_enabled);
+            backend_window.set_ime_allowed(window.ime_enabled);
         }
 
         if window.ime_position != cache.window.ime_position {
-            winit_window.set_ime_cursor_area(
-                LogicalPosition::new(window.ime_position.x, window.ime_position.y),
-                PhysicalSize::new(10, 10),
-            );
+            backend_window.set_ime_position(window.ime_position);
         }
 
         if window.window_theme != cache.window.window_theme {
-            winit_window.set_theme(window.window_theme.map(convert_window_theme));
+            backend_window.set_theme(window.window_theme);
         }
 
         if window.visible != cache.window.visible {
-            winit_window.set_visible(window.visible);
+            backend_window.set_visible(window.visible);
+        }
+
+        if window.opacity != cache.window.opacity {
+            backend_window.set_opacity(window.opacity);
         }
 
         cache.window = window.clone();
     }
+
+    for (entity, grabbed) in grab_results {
+        backend.cursor_grab_applied(entity, grabbed);
+    }
+}
+
+/// How many times grabbing the cursor is attempted while the window has focus before giving up.
+const CURSOR_GRAB_ATTEMPTS: u32 = 3;
+
+/// Retries the cursor grabs refused by the platform while their window has focus, sending a
+/// [`WindowError`] event if they keep failing.
+pub(crate) fn retry_cursor_grabs(
+    mut winit_windows: NonSendMut<WinitWindows>,
+    windows: Query<&Window>,
+    mut window_errors: EventWriter<WindowError>,
+) {
+    let WinitWindows {
+        windows: winit_windows,
+        entity_to_winit,
+        cursor_grab_retries,
+        ..
+    } = &mut *winit_windows;
+    cursor_grab_retries.retain(|&entity, attempts| {
+        let (Ok(window), Some(winit_window)) = (
+            windows.get(entity),
+            entity_to_winit
+                .get(&entity)
+                .and_then(|winit_id| winit_windows.get(winit_id)),
+        ) else {
+            return false;
+        };
+        // Wayland and X11 refuse to grab the cursor of windows that aren't focused.
+        if !winit_window.has_focus() {
+            return true;
+        }
+
+        let grab_mode = window.cursor.grab_mode;
+        match crate::winit_windows::attempt_grab(winit_window, grab_mode) {
+            Ok(()) => {
+                // The visibility may have been refused along with the grab.
+                (**winit_window).set_cursor_visible(window.cursor.visible);
+                false
+            }
+            Err(err) => {
+                *attempts += 1;
+                if *attempts < CURSOR_GRAB_ATTEMPTS {
+                    return true;
+                }
+                window_errors.send(WindowError {
+                    window: entity,
+                    operation: WindowOperation::SetCursorGrab,
+                    error: err.to_string(),
+                });
+                false
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::{App, First, Last, MainScheduleOrder};
+    use bevy_ecs::schedule::{IntoSystemConfigs, IntoSystemSetConfigs, ScheduleLabel};
+    use bevy_math::IVec2;
+    use bevy_window::WindowPosition;
+    use winit::{dpi::PhysicalPosition, window::WindowId};
+
+    use super::*;
+    use crate::{
+        winit_event::forward_winit_events, SequencedInput, WinitEvent, WinitEventBuffer,
+        WinitEventSystem, WinitEvents,
+    };
+
+    /// Whether the windows were focused when the systems between the translation and the
+    /// forwarding of the events ran.
+    #[derive(Resource, Default)]
+    struct FocusBeforeForward(Vec<bool>);
+
+    fn drain<E: bevy_ecs::event::Event>(app: &mut App) -> Vec<E> {
+        app.world_mut()
+            .resource_mut::<Events<E>>()
+            .drain()
+            .collect()
+    }
+
+    #[test]
+    fn buffered_events_are_translated_before_first() {
+        let mut app = App::new();
+        app.init_non_send_resource::<WinitEventBuffer>()
+            .init_non_send_resource::<WinitWindows>()
+            .init_resource::<FocusBeforeForward>()
+            .add_event::<WinitEvent>()
+            .add_event::<SequencedInput>()
+            .add_event::<WindowFocused>()
+            .add_event::<WindowResized>()
+            .add_event::<WindowMoved>()
+            .add_event::<ApplicationLifetime>()
+            .init_schedule(WinitEvents)
+            .configure_sets(
+                WinitEvents,
+                (WinitEventSystem::Translate, WinitEventSystem::Forward).chain(),
+            )
+            .add_systems(
+                WinitEvents,
+                (
+                    translate_winit_events.in_set(WinitEventSystem::Translate),
+                    forward_winit_events.in_set(WinitEventSystem::Forward),
+                    (|windows: Query<&Window>, mut focus: ResMut<FocusBeforeForward>| {
+                        focus.0.extend(windows.iter().map(|window| window.focused));
+                    })
+                    .after(WinitEventSystem::Translate)
+                    .before(WinitEventSystem::Forward),
+                ),
+            );
+        app.world_mut()
+            .resource_mut::<MainScheduleOrder>()
+            .insert_before(First, WinitEvents.intern());
+
+        let window = Window {
+            focused: false,
+            ..Default::default()
+        };
+        let window = app
+            .world_mut()
+            .spawn((
+                CachedWindow {
+                    window: window.clone(),
+                },
+                window,
+            ))
+            .id();
+        let window_id = WindowId::from(7);
+        app.world_mut()
+            .non_send_resource_mut::<WinitWindows>()
+            .winit_to_entity
+            .insert(window_id, window);
+
+        let mut buffer = app.world_mut().non_send_resource_mut::<WinitEventBuffer>();
+        for event in [
+            WindowEvent::Focused(true),
+            WindowEvent::Resized(PhysicalSize::new(640, 480)),
+            WindowEvent::Moved(PhysicalPosition::new(10, 20)),
+        ] {
+            buffer.push(Event::WindowEvent { window_id, event });
+        }
+        buffer.push(Event::Resumed);
+        app.update();
+
+        let win = app.world().get::<Window>(window).unwrap();
+        assert!(win.focused);
+        assert_eq!(win.physical_size(), UVec2::new(640, 480));
+        assert_eq!(win.position, WindowPosition::At(IVec2::new(10, 20)));
+        let cache = app.world().get::<CachedWindow>(window).unwrap();
+        assert_eq!(cache.window.physical_size(), UVec2::new(640, 480));
+        assert_eq!(app.world().resource::<FocusBeforeForward>().0, vec![true]);
+        assert!(app
+            .world()
+            .non_send_resource::<WinitEventBuffer>()
+            .is_empty());
+
+        assert_eq!(
+            drain::<WindowFocused>(&mut app),
+            vec![WindowFocused {
+                window,
+                focused: true
+            }]
+        );
+        assert_eq!(
+            drain::<WindowResized>(&mut app),
+            vec![WindowResized {
+                window,
+                width: 640.0,
+                height: 480.0
+            }]
+        );
+        assert_eq!(
+            drain::<WindowMoved>(&mut app),
+            vec![WindowMoved {
+                window,
+                position: IVec2::new(10, 20)
+            }]
+        );
+        assert_eq!(
+            drain::<ApplicationLifetime>(&mut app),
+            vec![ApplicationLifetime::Started]
+        );
+    }
+
+    #[test]
+    fn splash_windows_close_once_the_primary_window_presented() {
+        let mut app = App::new();
+        app.init_resource::<PresentFeedback>()
+            .add_event::<RequestRedraw>()
+            .add_systems(Last, close_splash_windows);
+        let splash = app
+            .world_mut()
+            .spawn((Window::default(), SplashWindow))
+            .id();
+        app.update();
+        assert!(app.world().get_entity(splash).is_some());
+
+        let window = Window::default();
+        app.world_mut().spawn((
+            CachedWindow {
+                window: window.clone(),
+            },
+            window,
+            PrimaryWindow,
+        ));
+        app.update();
+        assert!(app.world().get_entity(splash).is_some());
+        assert!(!drain::<RequestRedraw>(&mut app).is_empty());
+
+        app.world()
+            .resource::<PresentFeedback>()
+            .report_presented(Instant::now());
+        app.update();
+        assert!(app.world().get_entity(splash).is_none());
+    }
 }
@@ -0,0 +1,144 @@
+use bevy_app::{App, First, Plugin};
+use bevy_ecs::prelude::*;
+use bevy_utils::{Duration, Instant};
+
+/// A function returning for how long the user hasn't interacted with the system at all, or `None`
+/// if it can't be determined.
+pub type IdleTimeQuery = Box<dyn FnMut() -> Option<Duration> + Send + Sync>;
+
+/// Opt-in plugin keeping the [`SystemIdle`] resource up to date with the time elapsed since the
+/// user last interacted with the system, in any application.
+///
+/// Unlike the app's own input events, this notices that the user walked away even while they
+/// were using other applications, so always-on-top widgets can dim or pause.
+///
+/// The idle time is queried from the X server with the `x11` feature on Linux. On other platforms,
+/// insert a [`SystemIdleSource`] with a platform-specific query.
+///
+/// **Note:** The idle time is only updated when the app updates, so apps using a reactive
+/// [`UpdateMode`](crate::UpdateMode) should use a `wait` shorter than the precision they need.
+pub struct SystemIdlePlugin {
+    /// The idle time after which the user is considered away, see [`SystemIdle::is_idle`].
+    pub threshold: Duration,
+    /// The minimum time between two queries of the idle time.
+    pub poll_interval: Duration,
+}
+
+impl Default for SystemIdlePlugin {
+    fn default() -> Self {
+        Self {
+            threshold: Duration::from_secs(5 * 60),
+            poll_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+impl Plugin for SystemIdlePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SystemIdle {
+            idle_time: None,
+            threshold: self.threshold,
+            poll_interval: self.poll_interval,
+        })
+        .init_resource::<SystemIdleSource>()
+        .add_systems(First, update_system_idle);
+    }
+}
+
+/// The time elapsed since the user last interacted with the system, maintained by the
+/// [`SystemIdlePlugin`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SystemIdle {
+    idle_time: Option<Duration>,
+    threshold: Duration,
+    poll_interval: Duration,
+}
+
+impl SystemIdle {
+    /// Returns for how long the user hasn't interacted with the system, or `None` if the platform
+    /// can't tell.
+    pub fn idle_time(&self) -> Option<Duration> {
+        self.idle_time
+    }
+
+    /// Returns `true` if the user hasn't interacted with the system for at least the
+    /// [`threshold`](Self::threshold).
+    pub fn is_idle(&self) -> bool {
+        self.idle_time
+            .is_some_and(|idle_time| idle_time >= self.threshold)
+    }
+
+    /// Returns the idle time after which the user is considered away.
+    pub fn threshold(&self) -> Duration {
+        self.threshold
+    }
+
+    /// Sets the idle time after which the user is considered away.
+    pub fn set_threshold(&mut self, threshold: Duration) {
+        self.threshold = threshold;
+    }
+}
+
+/// Resource holding the function the [`SystemIdlePlugin`] uses to query the idle time.
+///
+/// It defaults to the platform's implementation, if there is one. Insert it before adding the
+/// [`SystemIdlePlugin`] to replace it.
+#[derive(Resource)]
+pub struct SystemIdleSource(IdleTimeQuery);
+
+impl SystemIdleSource {
+    /// Creates a source calling `query` to get the idle time.
+    pub fn new(query: impl FnMut() -> Option<Duration> + Send + Sync + 'static) -> Self {
+        Self(Box::new(query))
+    }
+}
+
+impl Default for SystemIdleSource {
+    fn default() -> Self {
+        cfg_if::cfg_if! {
+            if #[cfg(all(target_os = "linux", feature = "x11"))] {
+                Self::new(x11_idle_time_query())
+            } else {
+                Self::new(|| None)
+            }
+        }
+    }
+}
+
+fn update_system_idle(
+    mut idle: ResMut<SystemIdle>,
+    mut source: ResMut<SystemIdleSource>,
+    mut last_poll: Local<Option<Instant>>,
+) {
+    let now = Instant::now();
+    if last_poll.is_some_and(|last_poll| now.duration_since(last_poll) < idle.poll_interval) {
+        return;
+    }
+    *last_poll = Some(now);
+
+    let idle_time = (source.0)();
+    if idle.idle_time != idle_time {
+        idle.idle_time = idle_time;
+    }
+}
+
+/// Queries the idle time with the X server's screen saver extension.
+#[cfg(all(target_os = "linux", feature = "x11"))]
+fn x11_idle_time_query() -> impl FnMut() -> Option<Duration> + Send + Sync {
+    use x11rb::{connection::Connection, protocol::screensaver, rust_connection::RustConnection};
+
+    // Connect on the first query, and only try once so platforms without an X server (e.g.
+    // Wayland without XWayland) don't pay for it on every query.
+    let mut connection: Option<Option<(RustConnection, usize)>> = None;
+    move || {
+        let (connection, screen) = connection
+            .get_or_insert_with(|| x11rb::connect(None).ok())
+            .as_ref()?;
+        let root = connection.setup().roots.get(*screen)?.root;
+        let info = screensaver::query_info(connection, root)
+            .ok()?
+            .reply()
+            .ok()?;
+        Some(Duration::from_millis(info.ms_since_user_input.into()))
+    }
+}
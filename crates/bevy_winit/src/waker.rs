@@ -0,0 +1,93 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex, PoisonError,
+};
+
+use bevy_ecs::prelude::*;
+use bevy_window::RequestRedraw;
+
+use crate::EventLoopProxy;
+
+/// Resource waking up the `winit` event loop from any thread or sub-app, to run an update.
+///
+/// Sending [`RequestRedraw`] events only works from the main world, and only once an update runs:
+/// a sub-app (e.g. the render app, once an async GPU readback completes) or a background task
+/// can't wake an app sleeping in a [`Reactive`](crate::UpdateMode::Reactive) or
+/// [`ReactiveLowPower`](crate::UpdateMode::ReactiveLowPower) update mode with them. The waker
+/// sends a user event to the event loop instead, for which the runner runs an update, even if
+/// the app has no windows, without asking every window to redraw.
+///
+/// This is the wake path shared by the [`WinitEventSender`](crate::WinitEventSender) of each
+/// [`winit_channel`](crate::winit_channel) and the
+/// [`MainThreadExecutor`](crate::MainThreadExecutor).
+///
+/// The [`WinitPlugin`](crate::WinitPlugin) inserts the waker into the main world. It is
+/// `Send + Sync` and can be cloned freely, e.g. into the callbacks of a buffer mapping. Plugins
+/// creating a sub-app after the `WinitPlugin` insert a clone into it, and the systems of a
+/// sub-app can also extract it from the main world.
+///
+/// ```no_run
+/// # use bevy_app::{App, AppLabel, SubApp};
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_winit::WinitWaker;
+/// # #[derive(AppLabel, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// # struct ReadbackApp;
+/// # let mut app = App::new();
+/// let mut sub_app = SubApp::new();
+/// if let Some(waker) = app.world().get_resource::<WinitWaker>() {
+///     sub_app.insert_resource(waker.clone());
+/// }
+/// app.insert_sub_app(ReadbackApp, sub_app);
+///
+/// fn read_back(waker: Res<WinitWaker>) {
+///     let waker = waker.clone();
+///     std::thread::spawn(move || {
+///         // Wait for the readback here, then wake up the main app to use it.
+///         waker.wake();
+///     });
+/// }
+/// ```
+#[derive(Resource, Debug, Clone)]
+pub struct WinitWaker {
+    // `EventLoopProxy` is `Send` on all platforms, but not always `Sync`.
+    proxy: Arc<Mutex<EventLoopProxy>>,
+    /// The wakeups sent and not yet received by the runner, to tell them apart from the
+    /// [`RequestRedraw`] events sent directly through the [`EventLoopProxy`].
+    pending_wakes: Arc<AtomicUsize>,
+}
+
+impl WinitWaker {
+    pub(crate) fn new(proxy: EventLoopProxy) -> Self {
+        Self {
+            proxy: Arc::new(Mutex::new(proxy)),
+            pending_wakes: Arc::default(),
+        }
+    }
+
+    /// Wakes up the event loop, running an update.
+    ///
+    /// Returns `false` if the app is no longer running.
+    pub fn wake(&self) -> bool {
+        self.pending_wakes.fetch_add(1, Ordering::SeqCst);
+        let sent = self
+            .proxy
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .send_event(RequestRedraw)
+            .is_ok();
+        if !sent {
+            self.pending_wakes.fetch_sub(1, Ordering::SeqCst);
+        }
+        sent
+    }
+
+    /// Returns `true` if a user event received by the runner was sent by [`wake`](Self::wake),
+    /// rather than directly through the [`EventLoopProxy`].
+    pub(crate) fn take_wake(&self) -> bool {
+        self.pending_wakes
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |pending| {
+                pending.checked_sub(1)
+            })
+            .is_ok()
+    }
+}
@@ -0,0 +1,236 @@
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::prelude::*;
+use bevy_input::{
+    mouse::{MouseButton, MouseButtonInput},
+    ButtonState, InputSystem,
+};
+use bevy_math::{DVec2, Vec2};
+use bevy_window::{CursorEntered, CursorMoved, Window};
+
+use crate::WinitWindows;
+
+/// Opt-in plugin tracking drags that start in a window of the app and end in another one.
+///
+/// Start a drag with [`WindowDrag::start`], e.g. when a draggable item is pressed. While the left
+/// mouse button is held, [`WindowDragHovered`] events report the window under the cursor, and
+/// releasing it sends a [`WindowDragDropped`] event, or a [`WindowDragCancelled`] event if the
+/// cursor isn't over a window of the app.
+#[derive(Default)]
+pub struct WindowDragPlugin;
+
+impl Plugin for WindowDragPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WindowDrag>()
+            .add_event::<WindowDragHovered>()
+            .add_event::<WindowDragDropped>()
+            .add_event::<WindowDragCancelled>()
+            .add_systems(PreUpdate, track_window_drag.after(InputSystem));
+    }
+}
+
+/// What is being dragged between windows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DragPayload {
+    /// An app-defined identifier of the dragged content.
+    pub id: u64,
+    /// The entity being dragged, if any.
+    pub entity: Option<Entity>,
+}
+
+/// A drag in progress, tracked by the [`WindowDragPlugin`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ActiveWindowDrag {
+    /// The window the drag started in.
+    pub source: Entity,
+    /// What is being dragged.
+    pub payload: DragPayload,
+    /// The window under the cursor and the cursor position in it, in logical pixels.
+    pub hovered: Option<(Entity, Vec2)>,
+}
+
+/// Resource holding the drag in progress between the windows of the app, if any.
+#[derive(Resource, Debug, Default)]
+pub struct WindowDrag {
+    current: Option<ActiveWindowDrag>,
+}
+
+impl WindowDrag {
+    /// Starts dragging `payload` from the `source` window, replacing any drag in progress.
+    pub fn start(&mut self, source: Entity, payload: DragPayload) {
+        self.current = Some(ActiveWindowDrag {
+            source,
+            payload,
+            hovered: None,
+        });
+    }
+
+    /// Stops the drag in progress without sending any event.
+    pub fn cancel(&mut self) -> Option<ActiveWindowDrag> {
+        self.current.take()
+    }
+
+    /// Returns the drag in progress, if any.
+    pub fn current(&self) -> Option<&ActiveWindowDrag> {
+        self.current.as_ref()
+    }
+
+    /// Returns `true` if a drag is in progress.
+    pub fn is_dragging(&self) -> bool {
+        self.current.is_some()
+    }
+}
+
+/// An event sent when a drag moves over a window of the app.
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct WindowDragHovered {
+    /// The window the drag started in.
+    pub source: Entity,
+    /// The window under the cursor.
+    pub window: Entity,
+    /// The cursor position in `window`, in logical pixels.
+    pub position: Vec2,
+    /// What is being dragged.
+    pub payload: DragPayload,
+}
+
+/// An event sent when a drag is released over a window of the app.
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct WindowDragDropped {
+    /// The window the drag started in.
+    pub source: Entity,
+    /// The window the drag was dropped on.
+    pub target: Entity,
+    /// The cursor position in `target`, in logical pixels.
+    pub position: Vec2,
+    /// What was dragged.
+    pub payload: DragPayload,
+}
+
+/// An event sent when a drag is released outside of the windows of the app.
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct WindowDragCancelled {
+    /// The window the drag started in.
+    pub source: Entity,
+    /// What was dragged.
+    pub payload: DragPayload,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn track_window_drag(
+    mut drag: ResMut<WindowDrag>,
+    mut cursor_moved: EventReader<CursorMoved>,
+    mut cursor_entered: EventReader<CursorEntered>,
+    mut mouse_buttons: EventReader<MouseButtonInput>,
+    mut hovered_events: EventWriter<WindowDragHovered>,
+    mut dropped_events: EventWriter<WindowDragDropped>,
+    mut cancelled_events: EventWriter<WindowDragCancelled>,
+    windows: Query<&Window>,
+    winit_windows: NonSend<WinitWindows>,
+) {
+    let Some(current) = drag.current.as_mut() else {
+        cursor_moved.clear();
+        cursor_entered.clear();
+        mouse_buttons.clear();
+        return;
+    };
+
+    for event in cursor_entered.read() {
+        if let Some(position) = windows
+            .get(event.window)
+            .ok()
+            .and_then(Window::cursor_position)
+        {
+            current.hovered = Some((event.window, position));
+        }
+    }
+
+    let mut moved = false;
+    for event in cursor_moved.read() {
+        // The source window usually keeps receiving the cursor while a button is held, even
+        // outside of its bounds, so its positions are mapped to the window under the cursor.
+        current.hovered = if event.window == current.source {
+            window_under_cursor(event.window, event.position, &windows, &winit_windows)
+        } else {
+            Some((event.window, event.position))
+        };
+        moved = true;
+    }
+    if moved {
+        if let Some((window, position)) = current.hovered {
+            hovered_events.send(WindowDragHovered {
+                source: current.source,
+                window,
+                position,
+                payload: current.payload,
+            });
+        }
+    }
+
+    let released = mouse_buttons
+        .read()
+        .any(|event| event.button == MouseButton::Left && event.state == ButtonState::Released);
+    if !released {
+        return;
+    }
+    let Some(ActiveWindowDrag {
+        source,
+        payload,
+        hovered,
+    }) = drag.current.take()
+    else {
+        return;
+    };
+    match hovered {
+        Some((target, position)) => {
+            dropped_events.send(WindowDragDropped {
+                source,
+                target,
+                position,
+                payload,
+            });
+        }
+        None => {
+            cancelled_events.send(WindowDragCancelled { source, payload });
+        }
+    }
+}
+
+/// Returns the window under a cursor at `position` (in logical pixels) relative to `window`, and
+/// the cursor position in it.
+fn window_under_cursor(
+    window: Entity,
+    position: Vec2,
+    windows: &Query<&Window>,
+    winit_windows: &WinitWindows,
+) -> Option<(Entity, Vec2)> {
+    let inner_position = |entity: Entity| {
+        let winit_window = winit_windows.get_window(entity)?;
+        let position = winit_window.inner_position().ok()?;
+        Some((
+            winit_window,
+            DVec2::new(position.x as f64, position.y as f64),
+        ))
+    };
+
+    let (_, origin) = inner_position(window)?;
+    let scale_factor = windows.get(window).ok()?.scale_factor() as f64;
+    let global = origin + position.as_dvec2() * scale_factor;
+
+    for (entity, _) in winit_windows.iter() {
+        let Some((winit_window, origin)) = inner_position(entity) else {
+            continue;
+        };
+        let size = winit_window.inner_size();
+        let local = global - origin;
+        if local.x >= 0.0
+            && local.y >= 0.0
+            && local.x < size.width as f64
+            && local.y < size.height as f64
+            && winit_window.is_visible().unwrap_or(true)
+        {
+            let scale_factor = winit_window.scale_factor();
+            return Some((entity, (local / scale_factor).as_vec2()));
+        }
+    }
+    None
+}
@@ -0,0 +1,69 @@
+use bevy_ecs::prelude::*;
+use bevy_math::UVec2;
+use bevy_window::{CursorIcon, Window, WindowWrapper};
+use winit::window::WindowId;
+
+use crate::converters::convert_cursor_icon;
+
+/// Component giving systems access to the `winit` window of their entity, without looking it up
+/// in the [`WinitWindows`](crate::WinitWindows) non-send resource.
+///
+/// It is inserted on each window entity once its window is created, and removed when the window
+/// is destroyed. Systems using it can run on any thread, so it only exposes the operations that
+/// don't wait for the main thread: on platforms where windows can only be used from the main
+/// thread, they are queued to it.
+///
+/// **Note:** Changes made through this handle aren't reflected in the [`Window`] component, and
+/// are overwritten when the matching [`Window`] field changes.
+#[derive(Component, Clone)]
+pub struct WinitWindowHandle {
+    window: WindowWrapper<winit::window::Window>,
+    inner_size: UVec2,
+}
+
+impl WinitWindowHandle {
+    pub(crate) fn new(window: &WindowWrapper<winit::window::Window>) -> Self {
+        Self {
+            window: window.clone(),
+            inner_size: UVec2::new(window.inner_size().width, window.inner_size().height),
+        }
+    }
+
+    /// Returns the id of the `winit` window.
+    pub fn id(&self) -> WindowId {
+        self.window.id()
+    }
+
+    /// Asks the window to redraw, waking up the event loop.
+    pub fn request_redraw(&self) {
+        self.window.request_redraw();
+    }
+
+    /// Sets the cursor icon shown over the window.
+    pub fn set_cursor(&self, icon: CursorIcon) {
+        self.window.set_cursor_icon(convert_cursor_icon(icon));
+    }
+
+    /// Shows or hides the cursor over the window.
+    pub fn set_cursor_visible(&self, visible: bool) {
+        self.window.set_cursor_visible(visible);
+    }
+
+    /// Returns the size of the window's client area in physical pixels, as of the start of the
+    /// update.
+    pub fn inner_size(&self) -> UVec2 {
+        self.inner_size
+    }
+}
+
+/// Keeps the size cached by the [`WinitWindowHandle`]s up to date.
+pub(crate) fn update_window_handles(
+    mut windows: Query<(&Window, &mut WinitWindowHandle), Changed<Window>>,
+) {
+    for (window, mut handle) in &mut windows {
+        let size = window.physical_size();
+        if handle.inner_size != size {
+            handle.inner_size = size;
+        }
+    }
+}
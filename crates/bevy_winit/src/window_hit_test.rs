@@ -0,0 +1,236 @@
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::{entity::EntityHashMap, prelude::*};
+use bevy_input::{
+    mouse::{MouseButton, MouseButtonInput},
+    with_timestamps, ButtonState, InputSystem, Timestamped,
+};
+use bevy_math::{Rect, Vec2};
+use bevy_utils::{tracing::warn, Duration, Instant};
+use bevy_window::{Window, WindowCloseRequested};
+use winit::window::ResizeDirection;
+
+use crate::WinitWindows;
+
+/// Opt-in plugin giving the regions declared by the [`WindowHitTest`] component of a window the
+/// behavior of the native titlebar parts they draw.
+///
+/// This is intended for undecorated windows drawing their own titlebar: pressing a
+/// [`Caption`](HitTestKind::Caption) region moves the window or toggles its maximized state on
+/// double click, pressing a [`Resize`](HitTestKind::Resize) region resizes it, and clicking a
+/// button region acts like the native button.
+///
+/// **Note:** On Windows 11, hovering the native maximize button shows the snap layouts flyout.
+/// This requires answering the hit-test queries of the OS, which `winit` doesn't let apps do, so
+/// [`HitTestKind::MaximizeButton`] regions only toggle the maximized state when clicked.
+#[derive(Default)]
+pub struct WindowHitTestPlugin;
+
+impl Plugin for WindowHitTestPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreUpdate, apply_window_hit_test.after(InputSystem));
+    }
+}
+
+/// Component declaring the parts of the custom titlebar of a [`Window`], handled by the
+/// [`WindowHitTestPlugin`].
+///
+/// Regions are in logical pixels, relative to the top-left corner of the window's client area.
+/// When regions overlap, the last one wins.
+#[derive(Component, Debug, Clone, Default)]
+pub struct WindowHitTest {
+    /// The declared regions.
+    pub regions: Vec<HitTestRegion>,
+}
+
+impl WindowHitTest {
+    /// Adds a region of the given kind.
+    pub fn with_region(mut self, rect: Rect, kind: HitTestKind) -> Self {
+        self.regions.push(HitTestRegion { rect, kind });
+        self
+    }
+
+    /// Returns the kind of the region at `position`, if any.
+    pub fn hit(&self, position: Vec2) -> Option<HitTestKind> {
+        self.regions
+            .iter()
+            .rev()
+            .find(|region| region.rect.contains(position))
+            .map(|region| region.kind)
+    }
+}
+
+/// A region of a [`WindowHitTest`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HitTestRegion {
+    /// The area of the region, in logical pixels.
+    pub rect: Rect,
+    /// The titlebar part drawn in the region.
+    pub kind: HitTestKind,
+}
+
+/// The titlebar part drawn in a [`HitTestRegion`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HitTestKind {
+    /// The caption of the titlebar, moving the window when dragged, and toggling its maximized
+    /// state when double-clicked if
+    /// [`EnabledButtons::maximize`](bevy_window::EnabledButtons::maximize) is set.
+    Caption,
+    /// A border or corner of the window, resizing the window from this side when dragged if
+    /// [`Window::resizable`] is set.
+    Resize(ResizeEdge),
+    /// A minimize button, minimizing the window when clicked if
+    /// [`EnabledButtons::minimize`](bevy_window::EnabledButtons::minimize) is set.
+    MinimizeButton,
+    /// A maximize button, toggling the maximized state of the window when clicked if
+    /// [`EnabledButtons::maximize`](bevy_window::EnabledButtons::maximize) is set.
+    MaximizeButton,
+    /// A close button, sending a [`WindowCloseRequested`] event when clicked if
+    /// [`EnabledButtons::close`](bevy_window::EnabledButtons::close) is set.
+    CloseButton,
+}
+
+/// The side of the window resized by a [`HitTestKind::Resize`] region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResizeEdge {
+    /// The top border.
+    North,
+    /// The top-right corner.
+    NorthEast,
+    /// The right border.
+    East,
+    /// The bottom-right corner.
+    SouthEast,
+    /// The bottom border.
+    South,
+    /// The bottom-left corner.
+    SouthWest,
+    /// The left border.
+    West,
+    /// The top-left corner.
+    NorthWest,
+}
+
+impl From<ResizeEdge> for ResizeDirection {
+    fn from(edge: ResizeEdge) -> Self {
+        match edge {
+            ResizeEdge::North => ResizeDirection::North,
+            ResizeEdge::NorthEast => ResizeDirection::NorthEast,
+            ResizeEdge::East => ResizeDirection::East,
+            ResizeEdge::SouthEast => ResizeDirection::SouthEast,
+            ResizeEdge::South => ResizeDirection::South,
+            ResizeEdge::SouthWest => ResizeDirection::SouthWest,
+            ResizeEdge::West => ResizeDirection::West,
+            ResizeEdge::NorthWest => ResizeDirection::NorthWest,
+        }
+    }
+}
+
+/// The longest time between two presses in a [`HitTestKind::Caption`] region making a double
+/// click, matching the default of Windows.
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
+fn apply_window_hit_test(
+    mut mouse_buttons: EventReader<MouseButtonInput>,
+    mut timestamped_mouse_buttons: EventReader<Timestamped<MouseButtonInput>>,
+    mut windows: Query<(&mut Window, &WindowHitTest)>,
+    winit_windows: NonSend<WinitWindows>,
+    mut close_requested: EventWriter<WindowCloseRequested>,
+    mut pressed: Local<EntityHashMap<HitTestKind>>,
+    mut last_caption_press: Local<EntityHashMap<Instant>>,
+) {
+    for (event, timestamp) in
+        with_timestamps(mouse_buttons.read(), timestamped_mouse_buttons.read())
+    {
+        if event.button != MouseButton::Left {
+            continue;
+        }
+        let Ok((mut window, hit_test)) = windows.get_mut(event.window) else {
+            continue;
+        };
+        let hit = window
+            .cursor_position()
+            .and_then(|position| hit_test.hit(position));
+
+        match event.state {
+            // The OS takes over the move and resize drags, which must start while the button is
+            // pressed.
+            ButtonState::Pressed => match hit {
+                Some(HitTestKind::Caption) => {
+                    pressed.remove(&event.window);
+                    let time = timestamp.unwrap_or_else(Instant::now);
+                    let double_click =
+                        last_caption_press
+                            .remove(&event.window)
+                            .is_some_and(|last| {
+                                time.saturating_duration_since(last) <= DOUBLE_CLICK_INTERVAL
+                            });
+                    if double_click && window.enabled_buttons.maximize {
+                        let maximized = winit_windows
+                            .get_window(event.window)
+                            .is_some_and(|winit_window| winit_window.is_maximized());
+                        window.set_maximized(!maximized);
+                        continue;
+                    }
+                    if !double_click {
+                        last_caption_press.insert(event.window, time);
+                    }
+                    if let Some(winit_window) = winit_windows.get_window(event.window) {
+                        if let Err(err) = winit_window.drag_window() {
+                            warn!("Could not drag window {:?}: {err}", event.window);
+                        }
+                    }
+                }
+                Some(HitTestKind::Resize(edge)) => {
+                    pressed.remove(&event.window);
+                    if !window.resizable {
+                        continue;
+                    }
+                    if let Some(winit_window) = winit_windows.get_window(event.window) {
+                        if let Err(err) = winit_window.drag_resize_window(edge.into()) {
+                            warn!("Could not resize window {:?}: {err}", event.window);
+                        }
+                    }
+                }
+                Some(kind) => {
+                    pressed.insert(event.window, kind);
+                }
+                None => {
+                    pressed.remove(&event.window);
+                }
+            },
+            // Like native buttons, a region is activated when the button is released over the
+            // region it was pressed in.
+            ButtonState::Released => {
+                let Some(kind) = pressed
+                    .remove(&event.window)
+                    .filter(|&kind| hit == Some(kind))
+                else {
+                    continue;
+                };
+                match kind {
+                    HitTestKind::Caption | HitTestKind::Resize(_) => {}
+                    HitTestKind::MinimizeButton => {
+                        if window.enabled_buttons.minimize {
+                            window.set_minimized(true);
+                        }
+                    }
+                    HitTestKind::CloseButton => {
+                        if window.enabled_buttons.close {
+                            close_requested.send(WindowCloseRequested {
+                                window: event.window,
+                            });
+                        }
+                    }
+                    HitTestKind::MaximizeButton => {
+                        if window.enabled_buttons.maximize {
+                            let maximized = winit_windows
+                                .get_window(event.window)
+                                .is_some_and(|winit_window| winit_window.is_maximized());
+                            window.set_maximized(!maximized);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
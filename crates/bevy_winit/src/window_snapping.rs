@@ -0,0 +1,206 @@
+use bevy_app::{App, Last, Plugin};
+use bevy_ecs::prelude::*;
+use bevy_math::{IVec2, UVec2};
+use bevy_window::{Window, WindowMoved, WindowPosition};
+
+use crate::WinitWindows;
+
+/// Opt-in plugin snapping windows to the edges of their monitor and of the other windows of the
+/// app when they are moved close enough to them.
+///
+/// This is useful for tool-palette style apps made of several windows. The behavior is configured
+/// by the [`WindowSnapping`] resource.
+#[derive(Default)]
+pub struct WindowSnappingPlugin;
+
+impl Plugin for WindowSnappingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WindowSnapping>().add_systems(
+            Last,
+            snap_moved_windows.before(crate::system::changed_windows::<crate::WinitWindows>),
+        );
+    }
+}
+
+/// Settings of the [`WindowSnappingPlugin`].
+#[derive(Resource, Debug, Clone)]
+pub struct WindowSnapping {
+    /// The distance, in physical pixels, under which a window edge snaps to another edge.
+    pub threshold: u32,
+    /// Snap windows to the edges of the monitor they are on.
+    pub snap_to_monitor: bool,
+    /// Snap windows to the edges of the other windows of the app.
+    pub snap_to_windows: bool,
+}
+
+impl Default for WindowSnapping {
+    fn default() -> Self {
+        Self {
+            threshold: 16,
+            snap_to_monitor: true,
+            snap_to_windows: true,
+        }
+    }
+}
+
+/// A rectangle in physical pixels.
+#[derive(Clone, Copy)]
+struct SnapRect {
+    position: IVec2,
+    size: IVec2,
+}
+
+impl SnapRect {
+    fn new(position: IVec2, size: UVec2) -> Self {
+        Self {
+            position,
+            size: size.as_ivec2(),
+        }
+    }
+}
+
+/// A rectangle whose edges a moving window snaps to.
+#[derive(Clone, Copy)]
+struct SnapTarget {
+    rect: SnapRect,
+    /// Whether the window snaps against the outside of the edges, like against another window,
+    /// or against their inside, like against its monitor.
+    outside: bool,
+}
+
+fn snap_moved_windows(
+    settings: Res<WindowSnapping>,
+    mut moved: EventReader<WindowMoved>,
+    mut windows: Query<&mut Window>,
+    winit_windows: NonSend<WinitWindows>,
+) {
+    for &WindowMoved { window, position } in moved.read() {
+        let Some(winit_window) = winit_windows.get_window(window) else {
+            continue;
+        };
+        let size = winit_window.outer_size();
+        let moving = SnapRect::new(position, UVec2::new(size.width, size.height));
+
+        let mut targets = Vec::new();
+        if settings.snap_to_monitor {
+            if let Some(monitor) = winit_window.current_monitor() {
+                let position = monitor.position();
+                let size = monitor.size();
+                targets.push(SnapTarget {
+                    rect: SnapRect::new(
+                        IVec2::new(position.x, position.y),
+                        UVec2::new(size.width, size.height),
+                    ),
+                    outside: false,
+                });
+            }
+        }
+        if settings.snap_to_windows {
+            for (entity, other) in winit_windows.iter_windows() {
+                let Ok(position) = other.outer_position() else {
+                    continue;
+                };
+                if entity == window || !other.is_visible().unwrap_or(true) {
+                    continue;
+                }
+                let size = other.outer_size();
+                targets.push(SnapTarget {
+                    rect: SnapRect::new(
+                        IVec2::new(position.x, position.y),
+                        UVec2::new(size.width, size.height),
+                    ),
+                    outside: true,
+                });
+            }
+        }
+
+        let snapped = snap(moving, &targets, settings.threshold as i32);
+        if snapped != position {
+            if let Ok(mut window) = windows.get_mut(window) {
+                window.position = WindowPosition::At(snapped);
+            }
+        }
+    }
+}
+
+/// Returns the position of `moving` once its edges are snapped to the closest edges of `targets`
+/// within `threshold`, on each axis independently.
+///
+/// An edge only snaps to the targets `moving` overlaps on the other axis, give or take
+/// `threshold`, so windows don't snap to the edges of windows far above, below or beside them.
+fn snap(moving: SnapRect, targets: &[SnapTarget], threshold: i32) -> IVec2 {
+    let mut snapped = moving.position;
+    for axis in 0..2 {
+        let other_axis = 1 - axis;
+        let mut best: Option<(i32, i32)> = None;
+        for &SnapTarget { rect, outside } in targets {
+            let overlaps = moving.position[other_axis]
+                < rect.position[other_axis] + rect.size[other_axis] + threshold
+                && rect.position[other_axis]
+                    < moving.position[other_axis] + moving.size[other_axis] + threshold;
+            if !overlaps {
+                continue;
+            }
+            let start = rect.position[axis];
+            let end = start + rect.size[axis];
+            let candidates = if outside {
+                [start - moving.size[axis], end]
+            } else {
+                [start, end - moving.size[axis]]
+            };
+            for candidate in candidates {
+                let distance = (candidate - moving.position[axis]).abs();
+                if distance <= threshold
+                    && !matches!(best, Some((best_distance, _)) if best_distance <= distance)
+                {
+                    best = Some((distance, candidate));
+                }
+            }
+        }
+        if let Some((_, best)) = best {
+            snapped[axis] = best;
+        }
+    }
+    snapped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(x: i32, y: i32) -> SnapTarget {
+        SnapTarget {
+            rect: SnapRect::new(IVec2::new(x, y), UVec2::new(400, 300)),
+            outside: true,
+        }
+    }
+
+    #[test]
+    fn windows_snap_to_the_windows_they_overlap() {
+        let moving = |x, y| SnapRect::new(IVec2::new(x, y), UVec2::new(200, 200));
+        let monitor = SnapTarget {
+            rect: SnapRect::new(IVec2::ZERO, UVec2::new(1920, 1080)),
+            outside: false,
+        };
+
+        // Right next to the window, on the same row.
+        assert_eq!(
+            snap(moving(410, 50), &[window(0, 0)], 16),
+            IVec2::new(400, 50)
+        );
+        assert_eq!(
+            snap(moving(1000, 310), &[window(1000, 0)], 16),
+            IVec2::new(1000, 300)
+        );
+        // Far below the window, its right edge is out of reach.
+        assert_eq!(
+            snap(moving(410, 800), &[window(0, 0)], 16),
+            IVec2::new(410, 800)
+        );
+        // The monitor edges still apply.
+        assert_eq!(
+            snap(moving(410, 870), &[window(0, 0), monitor], 16),
+            IVec2::new(410, 880)
+        );
+    }
+}
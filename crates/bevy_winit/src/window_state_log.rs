@@ -0,0 +1,243 @@
+use bevy_app::{App, Last, Plugin};
+use bevy_ecs::{
+    entity::{EntityHashMap, EntityHashSet},
+    prelude::*,
+};
+use bevy_math::UVec2;
+use bevy_time::{Real, Time};
+use bevy_utils::{
+    tracing::{debug, warn},
+    Duration, HashMap,
+};
+use bevy_window::{CursorGrabMode, Window, WindowMode, WindowOccluded};
+use winit::window::Fullscreen;
+
+use crate::WinitWindows;
+
+/// Debug plugin logging the effective state of each window, and flagging the mismatches between
+/// the [`Window`] component and the actual `winit` window.
+///
+/// The state is checked when the [`Window`] component changes or the window is occluded or
+/// revealed, and periodically if [`WindowStateLog::interval`] is set. It is logged at the debug
+/// level when it differs from the state last logged for the window. This helps diagnosing windows
+/// ending up with another size or mode than requested, e.g. because of the window manager.
+#[derive(Default)]
+pub struct WindowStateLogPlugin;
+
+impl Plugin for WindowStateLogPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WindowStateLog>().add_systems(
+            Last,
+            log_window_state.after(crate::system::changed_windows::<crate::WinitWindows>),
+        );
+    }
+}
+
+/// Settings of the [`WindowStateLogPlugin`].
+#[derive(Resource, Debug, Clone)]
+pub struct WindowStateLog {
+    /// Also check the state of every window at this interval, in real time.
+    ///
+    /// `None` only checks the state of windows when their [`Window`] component changes.
+    pub interval: Option<Duration>,
+    /// How many consecutive updates a mismatch between the [`Window`] component and the `winit`
+    /// window must last before it is warned about.
+    ///
+    /// The platform applies some changes asynchronously, so the `winit` window can lag behind the
+    /// [`Window`] component for a few updates. Defaults to `3`.
+    pub mismatch_updates: u32,
+}
+
+impl Default for WindowStateLog {
+    fn default() -> Self {
+        Self {
+            interval: None,
+            mismatch_updates: 3,
+        }
+    }
+}
+
+/// The state of a window, as logged.
+#[derive(Debug, Clone, PartialEq)]
+struct LoggedState {
+    title: String,
+    requested_size: UVec2,
+    actual_size: UVec2,
+    scale_factor: f32,
+    base_scale_factor: f32,
+    backend_scale_factor: f64,
+    requested_mode: WindowMode,
+    actual_mode: &'static str,
+    grab_mode: CursorGrabMode,
+    focused: bool,
+    actual_focused: bool,
+    occluded: bool,
+}
+
+impl LoggedState {
+    /// Describes how the `winit` window doesn't match the [`Window`] component.
+    fn describe(&self, mismatch: Mismatch) -> String {
+        match mismatch {
+            Mismatch::Size => format!(
+                "is {}x{} instead of the requested {}x{}",
+                self.actual_size.x,
+                self.actual_size.y,
+                self.requested_size.x,
+                self.requested_size.y
+            ),
+            Mismatch::ScaleFactor => format!(
+                "has a scale factor of {} instead of {}",
+                self.backend_scale_factor, self.base_scale_factor
+            ),
+            Mismatch::Mode => format!(
+                "is in {} mode instead of {:?}",
+                self.actual_mode, self.requested_mode
+            ),
+            Mismatch::Focus => format!(
+                "focus is {} instead of {}",
+                self.actual_focused, self.focused
+            ),
+        }
+    }
+}
+
+/// A property of the [`Window`] component not matching the `winit` window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Mismatch {
+    Size,
+    ScaleFactor,
+    Mode,
+    Focus,
+}
+
+#[derive(Default)]
+struct WindowLogState {
+    logged: Option<LoggedState>,
+    /// The mismatches found by the last check, and for how many consecutive updates they lasted.
+    mismatches: HashMap<Mismatch, u32>,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn log_window_state(
+    settings: Res<WindowStateLog>,
+    time: Res<Time<Real>>,
+    windows: Query<(Entity, Ref<Window>)>,
+    winit_windows: NonSend<WinitWindows>,
+    mut occluded_events: EventReader<WindowOccluded>,
+    mut occluded: Local<EntityHashMap<bool>>,
+    mut log_states: Local<EntityHashMap<WindowLogState>>,
+    mut since_last_log: Local<Duration>,
+) {
+    let mut occlusion_changed = EntityHashSet::default();
+    for event in occluded_events.read() {
+        occluded.insert(event.window, event.occluded);
+        occlusion_changed.insert(event.window);
+    }
+
+    *since_last_log += time.delta();
+    let check_all = settings
+        .interval
+        .is_some_and(|interval| *since_last_log >= interval);
+    if check_all {
+        *since_last_log = Duration::ZERO;
+    }
+
+    for (entity, window) in &windows {
+        let log_state = log_states.entry(entity).or_default();
+        // Windows with mismatches not warned about yet are checked on each update.
+        let pending_mismatch = log_state
+            .mismatches
+            .values()
+            .any(|&updates| updates < settings.mismatch_updates);
+        if !check_all
+            && !pending_mismatch
+            && !window.is_changed()
+            && !occlusion_changed.contains(&entity)
+        {
+            continue;
+        }
+        let Some(winit_window) = winit_windows.get_window(entity) else {
+            continue;
+        };
+
+        let actual_size = winit_window.inner_size();
+        let actual_mode = match winit_window.fullscreen() {
+            None => "windowed",
+            Some(Fullscreen::Borderless(_)) => "borderless fullscreen",
+            Some(Fullscreen::Exclusive(_)) => "exclusive fullscreen",
+        };
+        let state = LoggedState {
+            title: window.title.clone(),
+            requested_size: window.physical_size(),
+            actual_size: UVec2::new(actual_size.width, actual_size.height),
+            scale_factor: window.scale_factor(),
+            base_scale_factor: window.resolution.base_scale_factor(),
+            backend_scale_factor: winit_window.scale_factor(),
+            requested_mode: window.mode,
+            actual_mode,
+            grab_mode: window.cursor.grab_mode,
+            focused: window.focused,
+            actual_focused: winit_window.has_focus(),
+            occluded: occluded.get(&entity).copied().unwrap_or(false),
+        };
+
+        let mode_matches = match state.requested_mode {
+            WindowMode::Windowed => winit_window.fullscreen().is_none(),
+            WindowMode::BorderlessFullscreen => {
+                matches!(winit_window.fullscreen(), Some(Fullscreen::Borderless(_)))
+            }
+            WindowMode::Fullscreen
+            | WindowMode::SizedFullscreen
+            | WindowMode::ExclusiveFullscreen { .. } => {
+                matches!(winit_window.fullscreen(), Some(Fullscreen::Exclusive(_)))
+            }
+        };
+        let mismatches = [
+            (Mismatch::Size, state.requested_size != state.actual_size),
+            (
+                Mismatch::ScaleFactor,
+                state.base_scale_factor != state.backend_scale_factor as f32,
+            ),
+            (Mismatch::Mode, !mode_matches),
+            (Mismatch::Focus, state.focused != state.actual_focused),
+        ];
+        for (mismatch, found) in mismatches {
+            if !found {
+                log_state.mismatches.remove(&mismatch);
+                continue;
+            }
+            let updates = log_state.mismatches.entry(mismatch).or_default();
+            *updates += 1;
+            if *updates == settings.mismatch_updates {
+                warn!(
+                    "Window {entity:?} {} for {} updates",
+                    state.describe(mismatch),
+                    settings.mismatch_updates
+                );
+            }
+        }
+
+        if log_state.logged.as_ref() != Some(&state) {
+            debug!(
+                window = ?entity,
+                title = %state.title,
+                requested_size = ?state.requested_size,
+                actual_size = ?state.actual_size,
+                scale_factor = state.scale_factor,
+                base_scale_factor = state.base_scale_factor,
+                backend_scale_factor = state.backend_scale_factor,
+                requested_mode = ?state.requested_mode,
+                actual_mode = state.actual_mode,
+                grab_mode = ?state.grab_mode,
+                focused = state.focused,
+                actual_focused = state.actual_focused,
+                occluded = state.occluded,
+                "Window state"
+            );
+            log_state.logged = Some(state);
+        }
+    }
+
+    occluded.retain(|&entity, _| windows.contains(entity));
+    log_states.retain(|&entity, _| windows.contains(entity));
+}
@@ -1,13 +1,65 @@
-use bevy_ecs::system::Resource;
-use bevy_utils::Duration;
+use std::cmp::Reverse;
+
+use bevy_ecs::{
+    component::Component, entity::Entity, event::Event, reflect::ReflectResource, system::Resource,
+    world::World,
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_utils::{
+    tracing::{error, warn},
+    warn_once, Duration, Instant,
+};
+use winit::{event::WindowEvent, window::WindowId};
 
 /// Settings for the [`WinitPlugin`](super::WinitPlugin).
+///
+/// The update modes can be overridden for single windows with a [`WindowUpdateMode`].
 #[derive(Debug, Resource, Clone)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct WinitSettings {
     /// Determines how frequently the application can update when it has focus.
     pub focused_mode: UpdateMode,
     /// Determines how frequently the application can update when it's out of focus.
     pub unfocused_mode: UpdateMode,
+    /// Determines which windows are asked to redraw when the event loop is about to wait.
+    ///
+    /// Explicit [`RequestRedraw`](bevy_window::RequestRedraw) events always redraw every window.
+    pub redraw_broadcast: RedrawBroadcast,
+    /// Stop asking windows to redraw while the OS reports them as fully covered by other windows,
+    /// e.g. on macOS or Wayland, and resume once they are uncovered.
+    ///
+    /// This saves the GPU time spent rendering frames nobody can see. The app keeps updating: in
+    /// [`UpdateMode::Continuous`], once every window is paused, the updates are paced to the
+    /// frame time measured when they were paused, as no frame presentation paces them anymore.
+    pub pause_occluded_windows: bool,
+    /// Number of updates to run once an [`AppExit`](bevy_app::AppExit) has been detected, before
+    /// the event loop exits.
+    ///
+    /// An [`AppWillExit`](crate::AppWillExit) event is sent before each of them, giving systems
+    /// that save state or flush telemetry a last chance to run.
+    pub exit_updates: u32,
+    /// Update the app as soon as a keyboard, mouse button, mouse wheel or touch input is received
+    /// while the event loop is waiting, instead of on the next redraw.
+    ///
+    /// This shaves up to a frame of input latency, e.g. for rhythm or competitive games, at the
+    /// cost of additional updates.
+    pub low_latency_input: bool,
+    /// Log a warning when an update takes longer than this, with the timings of the previous
+    /// update.
+    ///
+    /// The windows can't be moved, resized or closed while an update runs, so this helps finding
+    /// the systems stalling the event loop. `None` disables the watchdog.
+    pub update_watchdog: Option<Duration>,
+    /// What to do with the window events received for windows that no longer exist, or whose
+    /// entity has no [`Window`](bevy_window::Window) component.
+    pub missing_window_policy: MissingWindowPolicy,
+    /// How the runner waits for the `wait` time of the [`Reactive`](UpdateMode::Reactive) and
+    /// [`ReactiveLowPower`](UpdateMode::ReactiveLowPower) update modes to elapse.
+    pub wait_strategy: WaitStrategy,
 }
 
 impl WinitSettings {
@@ -21,6 +73,13 @@ impl WinitSettings {
             unfocused_mode: UpdateMode::ReactiveLowPower {
                 wait: Duration::from_secs_f64(1.0 / 60.0), // 60Hz
             },
+            redraw_broadcast: RedrawBroadcast::AllWindows,
+            pause_occluded_windows: false,
+            exit_updates: 0,
+            low_latency_input: false,
+            update_watchdog: None,
+            missing_window_policy: MissingWindowPolicy::Warn,
+            wait_strategy: WaitStrategy::Sleep,
         }
     }
 
@@ -38,6 +97,13 @@ impl WinitSettings {
             unfocused_mode: UpdateMode::ReactiveLowPower {
                 wait: Duration::from_secs(60),
             },
+            redraw_broadcast: RedrawBroadcast::AllWindows,
+            pause_occluded_windows: false,
+            exit_updates: 0,
+            low_latency_input: false,
+            update_watchdog: None,
+            missing_window_policy: MissingWindowPolicy::Warn,
+            wait_strategy: WaitStrategy::Sleep,
         }
     }
 
@@ -64,10 +130,18 @@ impl Default for WinitSettings {
 /// [`PresentMode`](bevy_window::PresentMode) setting. If an app can update faster than the refresh
 /// rate, but VSync is enabled, the update rate will be indirectly limited by the renderer.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum UpdateMode {
     /// The [`App`](bevy_app::App) will update over and over, as fast as it possibly can, until an
     /// [`AppExit`](bevy_app::AppExit) event appears.
     Continuous,
+    /// The [`App`](bevy_app::App) will update every time the event loop runs, until an
+    /// [`AppExit`](bevy_app::AppExit) event appears, and the event loop never waits for events.
+    ///
+    /// Unlike [`Continuous`](UpdateMode::Continuous), which lets the event loop wait for the next
+    /// redraw on most platforms, this keeps a CPU core busy at all times. Use it for
+    /// latency-critical apps, such as VR companion windows or audio tools.
+    BusyPoll,
     /// The [`App`](bevy_app::App) will update in response to the following, until an
     /// [`AppExit`](bevy_app::AppExit) event appears:
     /// - `wait` time has elapsed since the previous update
@@ -100,4 +174,516 @@ pub enum UpdateMode {
         /// The [`App`](bevy_app::App) will wait indefinitely if you set this to [`Duration::MAX`].
         wait: Duration,
     },
+    /// The [`App`](bevy_app::App) will update in response to the same events as
+    /// [`Reactive`](UpdateMode::Reactive), and wake up exactly when the next
+    /// [`FixedUpdate`](bevy_app::FixedUpdate) step is due according to
+    /// [`Time<Fixed>`](bevy_time::Fixed).
+    ///
+    /// This keeps fixed-tick simulations running at their own rate, one fixed step per update,
+    /// instead of accumulating several steps per update when the app wakes up late.
+    ///
+    /// **Note:** The app waits indefinitely for events while [`Time<Virtual>`](bevy_time::Virtual)
+    /// is paused or if [`Time<Fixed>`](bevy_time::Fixed) is missing.
+    FixedTimestep,
+}
+
+impl UpdateMode {
+    /// Returns the mode updating the most often out of `self` and `other`.
+    ///
+    /// The mode waiting the least between two updates without events wins, `fixed_timestep` being
+    /// the real time between two fixed steps for [`FixedTimestep`](UpdateMode::FixedTimestep), or
+    /// `None` while they don't run. Between modes waiting as long, [`BusyPoll`](UpdateMode::BusyPoll)
+    /// comes first, then [`Continuous`](UpdateMode::Continuous),
+    /// [`FixedTimestep`](UpdateMode::FixedTimestep), [`Reactive`](UpdateMode::Reactive) and
+    /// [`ReactiveLowPower`](UpdateMode::ReactiveLowPower), as they wake up for more events.
+    pub(crate) fn most_aggressive(
+        self,
+        other: UpdateMode,
+        fixed_timestep: Option<Duration>,
+    ) -> UpdateMode {
+        fn rank(mode: UpdateMode) -> u8 {
+            match mode {
+                UpdateMode::BusyPoll => 4,
+                UpdateMode::Continuous => 3,
+                UpdateMode::FixedTimestep => 2,
+                UpdateMode::Reactive { .. } => 1,
+                UpdateMode::ReactiveLowPower { .. } => 0,
+            }
+        }
+        let key = |mode: UpdateMode| (Reverse(mode.wake_interval(fixed_timestep)), rank(mode));
+        if key(other) > key(self) {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// Returns the longest time the runner waits between two updates in this mode, if no event
+    /// wakes it up.
+    fn wake_interval(self, fixed_timestep: Option<Duration>) -> Duration {
+        match self {
+            UpdateMode::BusyPoll | UpdateMode::Continuous => Duration::ZERO,
+            UpdateMode::FixedTimestep => fixed_timestep.unwrap_or(Duration::MAX),
+            UpdateMode::Reactive { wait, .. } | UpdateMode::ReactiveLowPower { wait, .. } => wait,
+        }
+    }
+}
+
+/// Component overriding the [`WinitSettings`] update modes while a window is visible, e.g. to let
+/// a tool palette run in [`ReactiveLowPower`](UpdateMode::ReactiveLowPower) next to a
+/// [`Continuous`](UpdateMode::Continuous) viewport.
+///
+/// The runner uses the mode updating the most often among the
+/// [visible](bevy_window::Window::visible) windows, e.g. [`Continuous`](UpdateMode::Continuous)
+/// over [`Reactive`](UpdateMode::Reactive), windows without this component using the modes of the
+/// [`WinitSettings`]. As for the [`WinitSettings`], the focused mode is used while any of
+/// the app's windows has focus.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct WindowUpdateMode {
+    /// The update mode of the window while the app has focus.
+    pub focused_mode: UpdateMode,
+    /// The update mode of the window while the app is out of focus.
+    pub unfocused_mode: UpdateMode,
+}
+
+impl WindowUpdateMode {
+    /// Uses `mode` whether the app has focus or not.
+    pub fn new(mode: UpdateMode) -> Self {
+        Self {
+            focused_mode: mode,
+            unfocused_mode: mode,
+        }
+    }
+
+    /// Returns the update mode of the window, depending on whether the app has focus.
+    pub fn update_mode(&self, focused: bool) -> UpdateMode {
+        match focused {
+            true => self.focused_mode,
+            false => self.unfocused_mode,
+        }
+    }
+}
+
+/// Sent when the [`UpdateMode`] the app runs with changes, either because the focus moved to or
+/// away from the app's windows or because the [`WinitSettings`] changed.
+///
+/// Systems can use it to scale their work with the update rate, e.g. to pause audio rendering or
+/// reduce asset streaming while running in [`ReactiveLowPower`](UpdateMode::ReactiveLowPower).
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct UpdateModeChanged {
+    /// The update mode the app ran with until now.
+    pub previous: UpdateMode,
+    /// The update mode the app runs with from now on.
+    pub current: UpdateMode,
+}
+
+/// How the runner waits for the `wait` time of a [`Reactive`](UpdateMode::Reactive) or
+/// [`ReactiveLowPower`](UpdateMode::ReactiveLowPower) update mode to elapse, see
+/// [`WinitSettings::wait_strategy`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum WaitStrategy {
+    /// Let the OS wake the event loop up when the time has elapsed.
+    ///
+    /// This uses no CPU while waiting, but the OS may wake the event loop up several milliseconds
+    /// late, notably on Windows.
+    #[default]
+    Sleep,
+    /// Let the OS wake the event loop up `spin` before the time has elapsed, then busy-wait until
+    /// it has.
+    ///
+    /// This trades some CPU time for precise update intervals, e.g. for frame pacing. `spin`
+    /// should be a bit larger than how late the OS usually wakes up, which
+    /// [`WinitStartCause::resume_delay`](crate::WinitStartCause::resume_delay) can measure.
+    ///
+    /// **Note:** This behaves like [`Sleep`](WaitStrategy::Sleep) on `wasm32`, where blocking the
+    /// main thread would freeze the page.
+    SleepAndSpin {
+        /// How long before the deadline the OS should wake the event loop up.
+        spin: Duration,
+    },
+}
+
+/// Resource capping how often the winit runner updates the app, e.g. to run at 30 frames per
+/// second, independently of VSync.
+///
+/// The limiter only holds updates back, on top of the [`UpdateMode`]: a
+/// [`Continuous`](UpdateMode::Continuous) app runs at the target rate, while a
+/// [`Reactive`](UpdateMode::Reactive) app still waits for events, but never updates more often.
+///
+/// Frames are due at regular intervals from the previous due time rather than from when the
+/// previous update started, so the duration of the updates and late wake-ups don't make the frame
+/// rate drift. The runner sleeps until `spin` before the next frame is due, then busy-waits for
+/// the rest of the time. If the app falls more than a frame behind, the pacing restarts from the
+/// late frame instead of running several updates back to back.
+///
+/// ```no_run
+/// # use bevy_app::App;
+/// # use bevy_winit::FrameLimiter;
+/// App::new().insert_resource(FrameLimiter::from_fps(30.0));
+/// ```
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct FrameLimiter {
+    /// The time between the start of two frames.
+    pub frame_time: Duration,
+    /// How long before the next frame is due the runner stops sleeping and busy-waits.
+    ///
+    /// The OS may wake the event loop up several milliseconds late, notably on Windows, which
+    /// this makes up for at the cost of some CPU time. It is ignored on `wasm32`, where blocking
+    /// the main thread would freeze the page.
+    pub spin: Duration,
+}
+
+impl FrameLimiter {
+    /// Creates a limiter running at most `fps` frames per second, busy-waiting for the last 2ms.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fps` is not positive.
+    pub fn from_fps(fps: f64) -> Self {
+        Self::from_frame_time(Duration::from_secs_f64(1.0 / fps))
+    }
+
+    /// Creates a limiter starting a frame at most every `frame_time`, busy-waiting for the last
+    /// 2ms.
+    pub fn from_frame_time(frame_time: Duration) -> Self {
+        Self {
+            frame_time,
+            spin: Duration::from_millis(2),
+        }
+    }
+
+    /// Busy-waits for the last `spin` before each frame, see [`FrameLimiter::spin`].
+    pub fn with_spin(mut self, spin: Duration) -> Self {
+        self.spin = spin;
+        self
+    }
+
+    /// Returns the maximum number of frames per second.
+    pub fn fps(&self) -> f64 {
+        1.0 / self.frame_time.as_secs_f64()
+    }
+
+    /// Returns when the frame following the one that was due at `due` and started at `start` is
+    /// due.
+    pub(crate) fn next_frame(&self, due: Option<Instant>, start: Instant) -> Option<Instant> {
+        due.and_then(|due| due.checked_add(self.frame_time))
+            .filter(|next| *next > start)
+            .or_else(|| start.checked_add(self.frame_time))
+    }
+}
+
+/// Resource overriding the control flow the winit runner computes from the [`UpdateMode`], to
+/// drive the event loop directly from systems, e.g. for an audio engine needing exact wake-ups.
+///
+/// The override applies whenever the event loop is about to wait, as long as the resource exists.
+/// The app updates whenever the event loop wakes up on its own, as when the `wait` of a
+/// [`Reactive`](UpdateMode::Reactive) update mode elapsed, and in response to events as set by
+/// the [`UpdateMode`].
+///
+/// ```no_run
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_utils::{Duration, Instant};
+/// # use bevy_winit::WinitControlFlow;
+/// fn schedule_next_buffer(mut commands: Commands) {
+///     let next_buffer = Instant::now() + Duration::from_millis(5);
+///     commands.insert_resource(WinitControlFlow::WaitUntil(next_buffer));
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WinitControlFlow {
+    /// Wake up again as soon as the pending events are handled, updating continuously.
+    Poll,
+    /// Wait for the next event.
+    Wait,
+    /// Wait for the next event or until the given instant, whichever comes first.
+    WaitUntil(Instant),
+    /// Exit the app, as if an [`AppExit::Success`](bevy_app::AppExit::Success) event was sent.
+    ///
+    /// The resource is removed once the exit is requested.
+    Exit,
+}
+
+/// What the runner does with window events received for a window it doesn't know, or whose entity
+/// has no [`Window`](bevy_window::Window) component.
+///
+/// Such events are expected for a short time after a window is despawned, as the OS may still
+/// send events for it, so apps closing windows frequently may want to silence them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum MissingWindowPolicy {
+    /// Skip the events silently.
+    Ignore,
+    /// Skip the events, logging a warning for the first one only.
+    WarnOnce,
+    /// Skip the events, logging a warning for each of them.
+    #[default]
+    Warn,
+    /// Skip the events, logging an error for each of them.
+    Error,
+    /// Skip the events, sending a [`MissingWindowEvent`] for each of them.
+    Event,
+}
+
+/// Sent for each window event skipped because its window is missing, when the
+/// [`MissingWindowPolicy`] is [`Event`](MissingWindowPolicy::Event).
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MissingWindowEvent {
+    /// The `winit` id of the window the event was sent to.
+    pub window_id: WindowId,
+    /// The entity of the window, or `None` if the window isn't known at all.
+    pub window: Option<Entity>,
+    /// The name of the [`WindowEvent`] variant skipped, e.g. `"CursorMoved"`.
+    pub kind: &'static str,
+}
+
+impl MissingWindowPolicy {
+    /// Reports the event skipped for the window `window_id` according to the policy.
+    ///
+    /// `window` is the entity of the window if it is known, but has no
+    /// [`Window`](bevy_window::Window) component.
+    pub(crate) fn report(
+        self,
+        world: &mut World,
+        window_id: WindowId,
+        window: Option<Entity>,
+        event: &WindowEvent,
+    ) {
+        let message = || match window {
+            Some(window) => {
+                format!("Window {window:?} is missing `Window` component, skipping event {event:?}")
+            }
+            None => format!("Skipped event {event:?} for unknown winit Window Id {window_id:?}"),
+        };
+        match self {
+            MissingWindowPolicy::Ignore => {}
+            MissingWindowPolicy::WarnOnce => warn_once!("{}", message()),
+            MissingWindowPolicy::Warn => warn!("{}", message()),
+            MissingWindowPolicy::Error => error!("{}", message()),
+            MissingWindowPolicy::Event => {
+                world.send_event(MissingWindowEvent {
+                    window_id,
+                    window,
+                    kind: window_event_kind(event),
+                });
+            }
+        }
+    }
+}
+
+/// Returns the name of the variant of `event`.
+fn window_event_kind(event: &WindowEvent) -> &'static str {
+    match event {
+        WindowEvent::ActivationTokenDone { .. } => "ActivationTokenDone",
+        WindowEvent::Resized(_) => "Resized",
+        WindowEvent::Moved(_) => "Moved",
+        WindowEvent::CloseRequested => "CloseRequested",
+        WindowEvent::Destroyed => "Destroyed",
+        WindowEvent::DroppedFile(_) => "DroppedFile",
+        WindowEvent::HoveredFile(_) => "HoveredFile",
+        WindowEvent::HoveredFileCancelled => "HoveredFileCancelled",
+        WindowEvent::Focused(_) => "Focused",
+        WindowEvent::KeyboardInput { .. } => "KeyboardInput",
+        WindowEvent::ModifiersChanged(_) => "ModifiersChanged",
+        WindowEvent::Ime(_) => "Ime",
+        WindowEvent::CursorMoved { .. } => "CursorMoved",
+        WindowEvent::CursorEntered { .. } => "CursorEntered",
+        WindowEvent::CursorLeft { .. } => "CursorLeft",
+        WindowEvent::MouseWheel { .. } => "MouseWheel",
+        WindowEvent::MouseInput { .. } => "MouseInput",
+        WindowEvent::TouchpadMagnify { .. } => "TouchpadMagnify",
+        WindowEvent::SmartMagnify { .. } => "SmartMagnify",
+        WindowEvent::TouchpadRotate { .. } => "TouchpadRotate",
+        WindowEvent::TouchpadPressure { .. } => "TouchpadPressure",
+        WindowEvent::AxisMotion { .. } => "AxisMotion",
+        WindowEvent::Touch(_) => "Touch",
+        WindowEvent::ScaleFactorChanged { .. } => "ScaleFactorChanged",
+        WindowEvent::ThemeChanged(_) => "ThemeChanged",
+        WindowEvent::Occluded(_) => "Occluded",
+        WindowEvent::RedrawRequested => "RedrawRequested",
+    }
+}
+
+/// Determines which windows receive a redraw request when the app schedules one on its own, e.g.
+/// to keep a [`Continuous`](UpdateMode::Continuous) app running or after the [`UpdateMode`] changed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum RedrawBroadcast {
+    /// Every window is asked to redraw.
+    #[default]
+    AllWindows,
+    /// Only windows that currently have focus are asked to redraw.
+    FocusedWindows,
+    /// No window is asked to redraw, redraws are left entirely to
+    /// [`RequestRedraw`](bevy_window::RequestRedraw) events.
+    ///
+    /// **Note:** On platforms where [`Continuous`](UpdateMode::Continuous) relies on
+    /// [`ControlFlow::Wait`](winit::event_loop::ControlFlow::Wait), the app will then only update
+    /// in response to new events.
+    Disabled,
+}
+
+/// The reasons why the winit runner last updated the [`App`](bevy_app::App).
+///
+/// Several reasons can apply to the same update. This is both a resource, holding the reasons of
+/// the current update, and an event, sent once per update. It is useful to find out what keeps
+/// waking up an app using a [`Reactive`](UpdateMode::Reactive) or
+/// [`ReactiveLowPower`](UpdateMode::ReactiveLowPower) update mode.
+#[derive(Event, Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource, Debug, Default, PartialEq)]
+pub struct WakeReason {
+    /// The `wait` time of the current [`UpdateMode`] has elapsed.
+    pub timer_elapsed: bool,
+    /// A [window event](winit::event::WindowEvent) has been received.
+    pub window_event: bool,
+    /// A [raw input event](winit::event::DeviceEvent) has been received.
+    pub device_event: bool,
+    /// An event has been sent through the [`EventLoopProxy`](crate::EventLoopProxy).
+    pub user_event: bool,
+    /// A window has been asked to redraw, either by a
+    /// [`RequestRedraw`](bevy_window::RequestRedraw) event or by the runner itself.
+    pub redraw_requested: bool,
+    /// The update was forced to initialize the app on startup.
+    pub startup: bool,
+    /// The update was forced to let the app react to being suspended or resumed.
+    pub lifecycle: bool,
+}
+
+/// Resource tracking whether one of the app's windows has focus, and which one.
+///
+/// It is kept in sync with the [`Window`](bevy_window::Window) components when the [`winit`]
+/// events of each update are translated, so systems interested in the app's focus don't need to
+/// query every window. The runner also uses it to pick between the
+/// [`focused_mode`](WinitSettings::focused_mode) and
+/// [`unfocused_mode`](WinitSettings::unfocused_mode).
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource, Debug, Default, PartialEq)]
+pub struct AnyWindowFocused(pub Option<Entity>);
+
+impl AnyWindowFocused {
+    /// Returns `true` if one of the app's windows has focus.
+    pub fn is_focused(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Returns the window that has focus, if any.
+    pub fn window(&self) -> Option<Entity> {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::event::Events;
+
+    use super::*;
+
+    fn reactive(wait: u64) -> UpdateMode {
+        UpdateMode::Reactive {
+            wait: Duration::from_millis(wait),
+        }
+    }
+
+    fn reactive_low_power(wait: u64) -> UpdateMode {
+        UpdateMode::ReactiveLowPower {
+            wait: Duration::from_millis(wait),
+        }
+    }
+
+    #[test]
+    fn most_aggressive_mode_wakes_up_the_soonest() {
+        let most_aggressive = |a: UpdateMode, b: UpdateMode, fixed_timestep: Option<u64>| {
+            let fixed_timestep = fixed_timestep.map(Duration::from_millis);
+            let mode = a.most_aggressive(b, fixed_timestep);
+            assert_eq!(mode, b.most_aggressive(a, fixed_timestep));
+            mode
+        };
+
+        assert_eq!(
+            most_aggressive(reactive(5000), reactive_low_power(16), None),
+            reactive_low_power(16)
+        );
+        assert_eq!(
+            most_aggressive(reactive(16), reactive_low_power(16), None),
+            reactive(16)
+        );
+        assert_eq!(
+            most_aggressive(reactive(0), UpdateMode::Continuous, None),
+            UpdateMode::Continuous
+        );
+        assert_eq!(
+            most_aggressive(UpdateMode::BusyPoll, UpdateMode::Continuous, None),
+            UpdateMode::BusyPoll
+        );
+        assert_eq!(
+            most_aggressive(UpdateMode::FixedTimestep, reactive(5000), Some(16)),
+            UpdateMode::FixedTimestep
+        );
+        assert_eq!(
+            most_aggressive(UpdateMode::FixedTimestep, reactive(1), Some(16)),
+            reactive(1)
+        );
+        assert_eq!(
+            most_aggressive(UpdateMode::FixedTimestep, reactive(5000), None),
+            reactive(5000)
+        );
+    }
+
+    #[test]
+    fn frames_are_due_at_a_steady_pace() {
+        let limiter = FrameLimiter::from_frame_time(Duration::from_millis(10));
+        let start = Instant::now();
+        let ms = |ms| start + Duration::from_millis(ms);
+
+        assert_eq!(limiter.next_frame(None, start), Some(ms(10)));
+        // Frames starting a bit late don't push the following ones back.
+        assert_eq!(limiter.next_frame(Some(ms(10)), ms(13)), Some(ms(20)));
+        assert_eq!(limiter.next_frame(Some(ms(20)), ms(29)), Some(ms(30)));
+    }
+
+    #[test]
+    fn late_frames_are_not_caught_up() {
+        let limiter = FrameLimiter::from_frame_time(Duration::from_millis(10));
+        let start = Instant::now();
+        let ms = |ms| start + Duration::from_millis(ms);
+
+        // After a stall, the next frame is a full frame time away instead of running the missed
+        // frames back to back.
+        assert_eq!(limiter.next_frame(Some(ms(10)), ms(45)), Some(ms(55)));
+        assert_eq!(limiter.next_frame(Some(ms(10)), ms(20)), Some(ms(30)));
+    }
+
+    #[test]
+    fn missing_windows_are_reported_by_policy() {
+        let mut world = World::new();
+        world.init_resource::<Events<MissingWindowEvent>>();
+        let window_id = WindowId::from(7);
+        let window = world.spawn_empty().id();
+
+        MissingWindowPolicy::Ignore.report(
+            &mut world,
+            window_id,
+            None,
+            &WindowEvent::Focused(true),
+        );
+        MissingWindowPolicy::Event.report(
+            &mut world,
+            window_id,
+            Some(window),
+            &WindowEvent::CloseRequested,
+        );
+
+        let events = world
+            .resource_mut::<Events<MissingWindowEvent>>()
+            .drain()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            vec![MissingWindowEvent {
+                window_id,
+                window: Some(window),
+                kind: "CloseRequested",
+            }]
+        );
+    }
 }
@@ -1,24 +1,543 @@
 #![allow(deprecated)]
 #![allow(missing_docs)]
 
-use bevy_app::App;
+use std::{borrow::Cow, mem};
+
+use bevy_app::{App, AppExit};
 use bevy_ecs::prelude::*;
+use bevy_ecs::schedule::ScheduleLabel;
 use bevy_input::keyboard::KeyboardInput;
 use bevy_input::touch::TouchInput;
 use bevy_input::{
     mouse::{MouseButtonInput, MouseMotion, MouseWheel},
     touchpad::{TouchpadMagnify, TouchpadRotate},
+    Timestamped,
 };
 use bevy_reflect::Reflect;
 #[cfg(feature = "serialize")]
 use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+use bevy_utils::{Duration, Instant};
 use bevy_window::{
-    ApplicationLifetime, CursorEntered, CursorLeft, CursorMoved, FileDragAndDrop, Ime,
-    ReceivedCharacter, RequestRedraw, WindowBackendScaleFactorChanged, WindowCloseRequested,
-    WindowCreated, WindowDestroyed, WindowFocused, WindowMoved, WindowOccluded, WindowResized,
-    WindowScaleFactorChanged, WindowThemeChanged,
+    ApplicationLifetime, CursorEntered, CursorLeft, CursorMoved, CursorTransferred,
+    FileDragAndDrop, Ime, ReceivedCharacter, RequestRedraw, WindowBackendScaleFactorChanged,
+    WindowCloseRequested, WindowCreated, WindowCycleRequested, WindowDestroyed, WindowFocused,
+    WindowMoved, WindowOccluded, WindowRedrawRequested, WindowResized, WindowScaleFactorChanged,
+    WindowThemeChanged,
+};
+use winit::{
+    event::{Event, StartCause, WindowEvent},
+    window::WindowId,
 };
 
+use crate::{UserEvent, WinitWaker};
+
+/// The schedule that translates the raw [`winit`] events received by the runner into Bevy events.
+///
+/// It runs before [`First`](bevy_app::First) in the [`Main`](bevy_app::Main) schedule, so the
+/// translated events are readable by every system of the update. Add systems to this schedule
+/// relative to the [`WinitEventSystem`] sets to observe or alter the translation.
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WinitEvents;
+
+/// Sets for the systems of the [`WinitEvents`] schedule, run in the order of the enum variants.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
+pub enum WinitEventSystem {
+    /// Drains the [`WinitEventBuffer`], updating the affected [`Window`](bevy_window::Window)
+    /// components and converting the raw events to [`WinitEvent`]s.
+    Translate,
+    /// Sends the translated [`WinitEvent`]s to the app, both as [`WinitEvent`] and as their
+    /// individual event types.
+    Forward,
+}
+
+/// Non-send resource buffering the raw [`winit`] events received by the runner since the last
+/// time the [`WinitEvents`] schedule ran.
+///
+/// Use `NonSendMut<WinitEventBuffer>` to access this resource.
+#[derive(Debug, Default)]
+pub struct WinitEventBuffer {
+    pub(crate) raw: Vec<Event<UserEvent>>,
+    pub(crate) received: Vec<Instant>,
+    pub(crate) translated: Vec<WinitEvent>,
+    /// When the raw events the `translated` events come from were received, by index.
+    pub(crate) translated_received: Vec<(usize, Instant)>,
+    next_input_sequence: u64,
+}
+
+impl WinitEventBuffer {
+    /// Queues a raw [`winit`] event to be translated the next time the [`WinitEvents`] schedule
+    /// runs.
+    ///
+    /// The time of the call is recorded as the time the event was received, and is sent with the
+    /// input events translated from it as [`Timestamped`] events.
+    pub fn push(&mut self, event: Event<UserEvent>) {
+        self.raw.push(event);
+        self.received.push(Instant::now());
+    }
+
+    /// Returns the raw [`winit`] events waiting to be translated, in the order they were received.
+    pub fn raw_events(&self) -> &[Event<UserEvent>] {
+        &self.raw
+    }
+
+    /// Returns the events translated by [`WinitEventSystem::Translate`] that have not been
+    /// forwarded to the app yet.
+    pub fn translated_events(&self) -> &[WinitEvent] {
+        &self.translated
+    }
+
+    /// Returns `true` if there are no raw or translated events waiting to be processed.
+    pub fn is_empty(&self) -> bool {
+        self.raw.is_empty() && self.translated.is_empty()
+    }
+}
+
+/// A raw [`winit`] event received by the runner, sent as a Bevy event when it is translated.
+///
+/// This gives access to the events Bevy doesn't translate, e.g.
+/// [`WindowEvent::ActivationTokenDone`] or [`WindowEvent::AxisMotion`], and to the exact sequence
+/// of the events received since the previous update, e.g. to correlate modifier changes with
+/// clicks, from regular systems.
+///
+/// Cloning every event has a cost, so these events are only sent once they are registered, by
+/// [`WinitPlugin::raw_events`](crate::WinitPlugin::raw_events) or the
+/// [`WinitDiagnosticsPlugin`](crate::WinitDiagnosticsPlugin). To react to the events before the
+/// runner handles them instead, register a [`WinitEventHooks`] pre-hook.
+#[derive(Event, Debug, Clone)]
+pub struct RawWinitEvent {
+    /// The window the event was sent to, or `None` if the event isn't a [`WindowEvent`] or the
+    /// window is unknown.
+    pub window: Option<Entity>,
+    /// The event, as received by the runner.
+    pub event: Event<UserEvent>,
+    /// When the runner received the event.
+    pub received: Instant,
+}
+
+/// Resource queueing raw [`winit`] events that the runner handles exactly as if they had been
+/// received from the OS.
+///
+/// Injected events go through the same path as real ones: event hooks, `AccessKit`, the
+/// [`WinitEventBuffer`] and the [`WinitEvents`] schedule. This makes it possible to script input
+/// and drive UI tests without platform-specific tooling.
+///
+/// The queue is drained by the runner after it handled the OS event during which the events were
+/// injected, so events injected by a system are handled right after the update it ran in. Queuing
+/// an event also wakes up the event loop, so the events are handled even if the app waits for OS
+/// events in a [`Reactive`](crate::UpdateMode::Reactive) update mode.
+///
+/// Window events target a [`WindowId`], which can be looked up for a window entity with
+/// [`WinitWindows::entity_to_winit`](crate::WinitWindows::entity_to_winit). Most input events
+/// can't be built outside of [`winit`] though, as they need a device id: use
+/// [`inject_translated`](Self::inject_translated) to inject the Bevy events instead.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_input::{keyboard::{Key, KeyCode, KeyboardInput}, ButtonState};
+/// # use bevy_window::PrimaryWindow;
+/// # use bevy_winit::WinitEventInjector;
+/// fn press_space(mut injector: ResMut<WinitEventInjector>, window: Query<Entity, With<PrimaryWindow>>) {
+///     injector.inject_translated(KeyboardInput {
+///         key_code: KeyCode::Space,
+///         logical_key: Key::Space,
+///         state: ButtonState::Pressed,
+///         window: window.single(),
+///     });
+/// }
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct WinitEventInjector {
+    events: Vec<Event<UserEvent>>,
+    translated: Vec<WinitEvent>,
+    waker: Option<WinitWaker>,
+    woken: bool,
+}
+
+impl WinitEventInjector {
+    /// Queues a raw [`winit`] event.
+    pub fn inject(&mut self, event: Event<UserEvent>) {
+        self.events.push(event);
+        self.wake();
+    }
+
+    /// Queues a [`WindowEvent`] for the window identified by `window_id`.
+    pub fn inject_window_event(&mut self, window_id: WindowId, event: WindowEvent) {
+        self.inject(Event::WindowEvent { window_id, event });
+    }
+
+    /// Queues a Bevy event the runner would translate from a [`winit`] event, e.g. a
+    /// [`KeyboardInput`] or a [`CursorMoved`].
+    ///
+    /// The event skips the translation, and is sent by the next [`WinitEvents`] schedule after
+    /// the events already translated, also as a [`WinitEvent`] and a [`SequencedInput`] for
+    /// input events.
+    pub fn inject_translated(&mut self, event: impl Into<WinitEvent>) {
+        self.translated.push(event.into());
+        self.wake();
+    }
+
+    /// Returns the number of events waiting to be handled by the runner.
+    pub fn len(&self) -> usize {
+        self.events.len() + self.translated.len()
+    }
+
+    /// Returns `true` if no events are waiting to be handled by the runner.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty() && self.translated.is_empty()
+    }
+
+    /// Sets the waker used to wake up the event loop when events are queued.
+    pub(crate) fn set_waker(&mut self, waker: WinitWaker) {
+        self.waker = Some(waker);
+    }
+
+    /// Wakes up the event loop once per batch of queued events.
+    fn wake(&mut self) {
+        if let Some(waker) = self.waker.as_ref().filter(|_| !self.woken) {
+            self.woken = waker.wake();
+        }
+    }
+
+    /// Takes the injected events out of `world`, in the order they were queued.
+    ///
+    /// The Bevy events are moved to the [`WinitEventBuffer`] right away, only the raw events are
+    /// returned for the runner to handle.
+    pub(crate) fn take(world: &mut World) -> Vec<Event<UserEvent>> {
+        let Some(mut injector) = world
+            .get_resource_mut::<Self>()
+            .filter(|injector| !injector.is_empty())
+        else {
+            return Vec::new();
+        };
+        injector.woken = false;
+        let events = mem::take(&mut injector.events);
+        let translated = mem::take(&mut injector.translated);
+        world
+            .non_send_resource_mut::<WinitEventBuffer>()
+            .translated
+            .extend(translated);
+        events
+    }
+}
+
+/// A labeled callback deciding whether the runner handles a raw [`winit`] event, see
+/// [`WinitEventFilters`].
+pub type WinitEventFiltersFn = Box<dyn Fn(&Event<UserEvent>, &World) -> bool + Send + Sync>;
+
+/// Resource holding labeled callbacks that decide which raw [`winit`] events the runner handles.
+///
+/// An event is dropped before the event hooks, `AccessKit` and the [`WinitEventBuffer`] see it as
+/// soon as one of the filters returns `false`, e.g. to ignore device events the app doesn't use.
+/// Only window and device events are filtered: the events driving the event loop, and the user
+/// events waking it up from other threads, always go through.
+///
+/// The filters are read for every event, so systems can add and remove them at any time, e.g. to
+/// ignore device events while a loading screen is up:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_winit::WinitEventFilters;
+/// # use winit::event::Event;
+/// fn show_loading_screen(mut filters: ResMut<WinitEventFilters>) {
+///     filters.add("loading_screen", |event, _world| {
+///         !matches!(event, Event::DeviceEvent { .. })
+///     });
+/// }
+///
+/// fn hide_loading_screen(mut filters: ResMut<WinitEventFilters>) {
+///     filters.remove("loading_screen");
+/// }
+/// ```
+#[derive(Resource, Default)]
+pub struct WinitEventFilters {
+    filters: Vec<(Cow<'static, str>, WinitEventFiltersFn)>,
+}
+
+impl WinitEventFilters {
+    /// Adds `filter` under `label`, replacing the filter previously added under the same label.
+    pub fn add(
+        &mut self,
+        label: impl Into<Cow<'static, str>>,
+        filter: impl Fn(&Event<UserEvent>, &World) -> bool + Send + Sync + 'static,
+    ) -> &mut Self {
+        let label = label.into();
+        let filter: WinitEventFiltersFn = Box::new(filter);
+        match self.filters.iter_mut().find(|(other, _)| *other == label) {
+            Some((_, previous)) => *previous = filter,
+            None => self.filters.push((label, filter)),
+        }
+        self
+    }
+
+    /// Removes the filter added under `label`, returning `true` if there was one.
+    pub fn remove(&mut self, label: &str) -> bool {
+        let len = self.filters.len();
+        self.filters.retain(|(other, _)| other != label);
+        self.filters.len() != len
+    }
+
+    /// Returns `true` if a filter was added under `label`.
+    pub fn contains(&self, label: &str) -> bool {
+        self.filters.iter().any(|(other, _)| other == label)
+    }
+
+    /// Returns the labels of the filters, in the order they were added.
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.filters.iter().map(|(label, _)| label.as_ref())
+    }
+
+    /// Removes every filter, so every event is handled.
+    pub fn clear(&mut self) {
+        self.filters.clear();
+    }
+
+    /// Returns `true` if every filter registered in `world` accepts `event`.
+    pub(crate) fn accepts(world: &World, event: &Event<UserEvent>) -> bool {
+        if !is_filtered(event) {
+            return true;
+        }
+        let Some(filters) = world.get_resource::<Self>() else {
+            return true;
+        };
+        filters
+            .filters
+            .iter()
+            .all(|(_, filter)| filter(event, world))
+    }
+}
+
+/// Returns `true` if `event` goes through the event filters, the events driving the event loop
+/// and the user events waking it up being always handled.
+fn is_filtered(event: &Event<UserEvent>) -> bool {
+    matches!(event, Event::WindowEvent { .. } | Event::DeviceEvent { .. })
+}
+
+/// Extension trait for [`App`] configuring the `winit` runner.
+pub trait WinitAppExt {
+    /// Adds a filter to the [`WinitEventFilters`] under `label`, replacing the filter previously
+    /// added under the same label.
+    ///
+    /// ```
+    /// # use bevy_app::App;
+    /// # use bevy_winit::WinitAppExt;
+    /// # use winit::event::Event;
+    /// App::new().add_winit_event_filter("no_device_events", |event, _world| {
+    ///     !matches!(event, Event::DeviceEvent { .. })
+    /// });
+    /// ```
+    fn add_winit_event_filter(
+        &mut self,
+        label: impl Into<Cow<'static, str>>,
+        filter: impl Fn(&Event<UserEvent>, &World) -> bool + Send + Sync + 'static,
+    ) -> &mut Self;
+}
+
+impl WinitAppExt for App {
+    fn add_winit_event_filter(
+        &mut self,
+        label: impl Into<Cow<'static, str>>,
+        filter: impl Fn(&Event<UserEvent>, &World) -> bool + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_insert_with(WinitEventFilters::default)
+            .add(label, filter);
+        self
+    }
+}
+
+/// A callback invoked by the runner with a raw [`winit`] event.
+pub type WinitEventHook = Box<dyn FnMut(&Event<UserEvent>, &mut World)>;
+
+/// Non-send resource holding callbacks that the runner invokes immediately before and after it
+/// handles each raw [`winit`] event.
+///
+/// Pre-hooks see events before the engine buffers or reacts to them, which is useful for
+/// integrations (e.g. UI libraries) that need the original event rather than its Bevy translation.
+/// Post-hooks run once the runner is done with the event, including any update it triggered.
+/// Systems reading the raw events during the next update should use [`RawWinitEvent`] instead.
+///
+/// The resource is initialized by [`WinitPlugin`](crate::WinitPlugin). Plugins added before it can
+/// call `world.init_non_send_resource::<WinitEventHooks>()` themselves to register their hooks.
+///
+/// Use `NonSendMut<WinitEventHooks>` to access this resource.
+#[derive(Default)]
+pub struct WinitEventHooks {
+    pre: Vec<WinitEventHook>,
+    post: Vec<WinitEventHook>,
+}
+
+impl WinitEventHooks {
+    /// Registers a callback run before the runner handles each event.
+    pub fn add_pre_hook(
+        &mut self,
+        hook: impl FnMut(&Event<UserEvent>, &mut World) + 'static,
+    ) -> &mut Self {
+        self.pre.push(Box::new(hook));
+        self
+    }
+
+    /// Registers a callback run after the runner handled each event.
+    pub fn add_post_hook(
+        &mut self,
+        hook: impl FnMut(&Event<UserEvent>, &mut World) + 'static,
+    ) -> &mut Self {
+        self.post.push(Box::new(hook));
+        self
+    }
+
+    /// Returns `true` if at least one post-hook is registered.
+    pub(crate) fn has_post_hooks(world: &World) -> bool {
+        world
+            .get_non_send_resource::<Self>()
+            .is_some_and(|hooks| !hooks.post.is_empty())
+    }
+
+    /// Runs the pre-hooks registered in `world` with `event`.
+    pub(crate) fn run_pre_hooks(world: &mut World, event: &Event<UserEvent>) {
+        Self::run_hooks(world, event, |hooks| &mut hooks.pre);
+    }
+
+    /// Runs the post-hooks registered in `world` with `event`.
+    pub(crate) fn run_post_hooks(world: &mut World, event: &Event<UserEvent>) {
+        Self::run_hooks(world, event, |hooks| &mut hooks.post);
+    }
+
+    fn run_hooks(
+        world: &mut World,
+        event: &Event<UserEvent>,
+        select: fn(&mut Self) -> &mut Vec<WinitEventHook>,
+    ) {
+        let has_hooks = world
+            .get_non_send_resource_mut::<Self>()
+            .is_some_and(|mut hooks| !select(&mut hooks).is_empty());
+        if !has_hooks {
+            return;
+        }
+
+        // The hooks need mutable access to the world, so the resource is taken out while they run.
+        let Some(mut hooks) = world.remove_non_send_resource::<Self>() else {
+            return;
+        };
+        for hook in select(&mut hooks).iter_mut() {
+            hook(event, world);
+        }
+
+        // Keep hooks registered by the hooks themselves.
+        if let Some(mut added) = world.remove_non_send_resource::<Self>() {
+            hooks.pre.append(&mut added.pre);
+            hooks.post.append(&mut added.post);
+        }
+        world.insert_non_send_resource(hooks);
+    }
+}
+
+pub(crate) trait AppSendEvent {
+    fn send(&mut self, event: impl Into<WinitEvent>);
+}
+
+impl AppSendEvent for Vec<WinitEvent> {
+    fn send(&mut self, event: impl Into<WinitEvent>) {
+        self.push(Into::<WinitEvent>::into(event));
+    }
+}
+
+/// Sent before each of the final updates the runner performs once an [`AppExit`] has been
+/// detected, as configured by [`WinitSettings::exit_updates`](crate::WinitSettings::exit_updates).
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct AppWillExit {
+    /// The exit that is about to happen.
+    pub exit: AppExit,
+    /// Number of updates that will still run after this one.
+    pub remaining_updates: u32,
+}
+
+/// Why the [`winit`] event loop started a new iteration, sent every time it wakes up.
+///
+/// This mirrors [`StartCause`], with the instant the event loop actually woke up added, so
+/// frame-pacing and diagnostics code can measure how late timer wakeups are.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WinitStartCause {
+    /// The `requested_resume` instant of a
+    /// [`ControlFlow::WaitUntil`](winit::event_loop::ControlFlow::WaitUntil) has been reached.
+    ResumeTimeReached {
+        /// When the event loop started waiting.
+        start: Instant,
+        /// When the event loop asked to be woken up.
+        requested_resume: Instant,
+        /// When the event loop was actually woken up.
+        actual_resume: Instant,
+    },
+    /// The event loop was woken up by an event before its `requested_resume` instant, if any.
+    WaitCancelled {
+        /// When the event loop started waiting.
+        start: Instant,
+        /// When the event loop asked to be woken up, if it was waiting with a timeout.
+        requested_resume: Option<Instant>,
+        /// When the event loop was actually woken up.
+        actual_resume: Instant,
+    },
+    /// The event loop is running with
+    /// [`ControlFlow::Poll`](winit::event_loop::ControlFlow::Poll).
+    Poll,
+    /// The event loop has just been started.
+    Init,
+}
+
+impl WinitStartCause {
+    pub(crate) fn new(cause: StartCause, now: Instant) -> Self {
+        match cause {
+            StartCause::ResumeTimeReached {
+                start,
+                requested_resume,
+            } => Self::ResumeTimeReached {
+                start,
+                requested_resume,
+                actual_resume: now,
+            },
+            StartCause::WaitCancelled {
+                start,
+                requested_resume,
+            } => Self::WaitCancelled {
+                start,
+                requested_resume,
+                actual_resume: now,
+            },
+            StartCause::Poll => Self::Poll,
+            StartCause::Init => Self::Init,
+        }
+    }
+
+    /// Returns for how long the event loop waited before waking up, if it was waiting.
+    pub fn wait_duration(&self) -> Option<Duration> {
+        match *self {
+            Self::ResumeTimeReached {
+                start,
+                actual_resume,
+                ..
+            }
+            | Self::WaitCancelled {
+                start,
+                actual_resume,
+                ..
+            } => Some(actual_resume.saturating_duration_since(start)),
+            _ => None,
+        }
+    }
+
+    /// Returns how late the event loop woke up after the requested instant, if it was waiting
+    /// for one.
+    pub fn resume_delay(&self) -> Option<Duration> {
+        match *self {
+            Self::ResumeTimeReached {
+                requested_resume,
+                actual_resume,
+                ..
+            } => Some(actual_resume.saturating_duration_since(requested_resume)),
+            _ => None,
+        }
+    }
+}
+
 /// Wraps all `bevy_window` events in a common enum.
 ///
 /// Read these events with `EventReader<WinitEvent>` if you need to
@@ -36,6 +555,7 @@ pub enum WinitEvent {
     ApplicationLifetime(ApplicationLifetime),
     CursorEntered(CursorEntered),
     CursorLeft(CursorLeft),
+    CursorTransferred(CursorTransferred),
     CursorMoved(CursorMoved),
     FileDragAndDrop(FileDragAndDrop),
     Ime(Ime),
@@ -48,9 +568,11 @@ pub enum WinitEvent {
     WindowFocused(WindowFocused),
     WindowMoved(WindowMoved),
     WindowOccluded(WindowOccluded),
+    WindowRedrawRequested(WindowRedrawRequested),
     WindowResized(WindowResized),
     WindowScaleFactorChanged(WindowScaleFactorChanged),
     WindowThemeChanged(WindowThemeChanged),
+    WindowCycleRequested(WindowCycleRequested),
 
     MouseButtonInput(MouseButtonInput),
     MouseMotion(MouseMotion),
@@ -64,6 +586,40 @@ pub enum WinitEvent {
     KeyboardInput(KeyboardInput),
 }
 
+impl WinitEvent {
+    /// Returns `true` if the event was produced by user input, as opposed to a change of the
+    /// window or application state.
+    pub fn is_input(&self) -> bool {
+        matches!(
+            self,
+            Self::CursorMoved(_)
+                | Self::Ime(_)
+                | Self::ReceivedCharacter(_)
+                | Self::MouseButtonInput(_)
+                | Self::MouseMotion(_)
+                | Self::MouseWheel(_)
+                | Self::TouchpadMagnify(_)
+                | Self::TouchpadRotate(_)
+                | Self::TouchInput(_)
+                | Self::KeyboardInput(_)
+        )
+    }
+}
+
+/// An input [`WinitEvent`] tagged with its position in the app's input stream.
+///
+/// Bevy sends each kind of input as its own event type, which loses the relative ordering of e.g.
+/// a click and a key press received in the same update. Reading `EventReader<SequencedInput>`
+/// gives every input event in the order it was received, and the `sequence` numbers keep
+/// increasing across updates so events can be ordered even once buffered by the app.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct SequencedInput {
+    /// Position of the event in the input stream, starting at 0 for the first input of the app.
+    pub sequence: u64,
+    /// The input event.
+    pub event: WinitEvent,
+}
+
 impl From<ApplicationLifetime> for WinitEvent {
     fn from(e: ApplicationLifetime) -> Self {
         Self::ApplicationLifetime(e)
@@ -79,6 +635,11 @@ impl From<CursorLeft> for WinitEvent {
         Self::CursorLeft(e)
     }
 }
+impl From<CursorTransferred> for WinitEvent {
+    fn from(e: CursorTransferred) -> Self {
+        Self::CursorTransferred(e)
+    }
+}
 impl From<CursorMoved> for WinitEvent {
     fn from(e: CursorMoved) -> Self {
         Self::CursorMoved(e)
@@ -129,11 +690,21 @@ impl From<WindowFocused> for WinitEvent {
         Self::WindowFocused(e)
     }
 }
+impl From<WindowCycleRequested> for WinitEvent {
+    fn from(e: WindowCycleRequested) -> Self {
+        Self::WindowCycleRequested(e)
+    }
+}
 impl From<WindowMoved> for WinitEvent {
     fn from(e: WindowMoved) -> Self {
         Self::WindowMoved(e)
     }
 }
+impl From<WindowRedrawRequested> for WinitEvent {
+    fn from(e: WindowRedrawRequested) -> Self {
+        Self::WindowRedrawRequested(e)
+    }
+}
 impl From<WindowOccluded> for WinitEvent {
     fn from(e: WindowOccluded) -> Self {
         Self::WindowOccluded(e)
@@ -190,91 +761,280 @@ impl From<KeyboardInput> for WinitEvent {
     }
 }
 
-/// Forwards buffered [`WinitEvent`] events to the app.
-pub(crate) fn forward_winit_events(buffered_events: &mut Vec<WinitEvent>, app: &mut App) {
+/// Forwards the translated [`WinitEvent`] events to the app, followed by a [`Timestamped`] event
+/// for the input events translated from a raw event.
+pub(crate) fn forward_winit_events(world: &mut World) {
+    let mut buffer = world.non_send_resource_mut::<WinitEventBuffer>();
+    let buffered_events = mem::take(&mut buffer.translated);
+    let mut received = mem::take(&mut buffer.translated_received)
+        .into_iter()
+        .peekable();
     if buffered_events.is_empty() {
         return;
     }
-    for winit_event in buffered_events.iter() {
+    for (index, winit_event) in buffered_events.iter().enumerate() {
+        let timestamp = received
+            .next_if(|(received_index, _)| *received_index == index)
+            .map(|(_, timestamp)| timestamp);
         match winit_event.clone() {
             WinitEvent::ApplicationLifetime(e) => {
-                app.world_mut().send_event(e);
+                world.send_event(e);
             }
             WinitEvent::CursorEntered(e) => {
-                app.world_mut().send_event(e);
+                world.send_event(e);
             }
             WinitEvent::CursorLeft(e) => {
-                app.world_mut().send_event(e);
+                world.send_event(e);
+            }
+            WinitEvent::CursorTransferred(e) => {
+                world.send_event(e);
             }
             WinitEvent::CursorMoved(e) => {
-                app.world_mut().send_event(e);
+                send_timestamped(world, e, timestamp);
             }
             WinitEvent::FileDragAndDrop(e) => {
-                app.world_mut().send_event(e);
+                world.send_event(e);
             }
             WinitEvent::Ime(e) => {
-                app.world_mut().send_event(e);
+                world.send_event(e);
             }
             WinitEvent::ReceivedCharacter(e) => {
-                app.world_mut().send_event(e);
+                world.send_event(e);
             }
             WinitEvent::RequestRedraw(e) => {
-                app.world_mut().send_event(e);
+                world.send_event(e);
             }
             WinitEvent::WindowBackendScaleFactorChanged(e) => {
-                app.world_mut().send_event(e);
+                world.send_event(e);
             }
             WinitEvent::WindowCloseRequested(e) => {
-                app.world_mut().send_event(e);
+                world.send_event(e);
             }
             WinitEvent::WindowCreated(e) => {
-                app.world_mut().send_event(e);
+                world.send_event(e);
             }
             WinitEvent::WindowDestroyed(e) => {
-                app.world_mut().send_event(e);
+                world.send_event(e);
             }
             WinitEvent::WindowFocused(e) => {
-                app.world_mut().send_event(e);
+                world.send_event(e);
             }
             WinitEvent::WindowMoved(e) => {
-                app.world_mut().send_event(e);
+                world.send_event(e);
             }
             WinitEvent::WindowOccluded(e) => {
-                app.world_mut().send_event(e);
+                world.send_event(e);
+            }
+            WinitEvent::WindowRedrawRequested(e) => {
+                world.send_event(e);
             }
             WinitEvent::WindowResized(e) => {
-                app.world_mut().send_event(e);
+                world.send_event(e);
             }
             WinitEvent::WindowScaleFactorChanged(e) => {
-                app.world_mut().send_event(e);
+                world.send_event(e);
             }
             WinitEvent::WindowThemeChanged(e) => {
-                app.world_mut().send_event(e);
+                world.send_event(e);
+            }
+            WinitEvent::WindowCycleRequested(e) => {
+                world.send_event(e);
             }
             WinitEvent::MouseButtonInput(e) => {
-                app.world_mut().send_event(e);
+                send_timestamped(world, e, timestamp);
             }
             WinitEvent::MouseMotion(e) => {
-                app.world_mut().send_event(e);
+                world.send_event(e);
             }
             WinitEvent::MouseWheel(e) => {
-                app.world_mut().send_event(e);
+                world.send_event(e);
             }
             WinitEvent::TouchpadMagnify(e) => {
-                app.world_mut().send_event(e);
+                world.send_event(e);
             }
             WinitEvent::TouchpadRotate(e) => {
-                app.world_mut().send_event(e);
+                world.send_event(e);
             }
             WinitEvent::TouchInput(e) => {
-                app.world_mut().send_event(e);
+                send_timestamped(world, e, timestamp);
             }
             WinitEvent::KeyboardInput(e) => {
-                app.world_mut().send_event(e);
+                send_timestamped(world, e, timestamp);
             }
         }
     }
-    app.world_mut()
+
+    let mut buffer = world.non_send_resource_mut::<WinitEventBuffer>();
+    let sequenced_inputs = buffered_events
+        .iter()
+        .filter(|event| event.is_input())
+        .map(|event| {
+            let sequence = buffer.next_input_sequence;
+            buffer.next_input_sequence += 1;
+            SequencedInput {
+                sequence,
+                event: event.clone(),
+            }
+        })
+        .collect::<Vec<_>>();
+    world
+        .resource_mut::<Events<SequencedInput>>()
+        .send_batch(sequenced_inputs);
+    world
         .resource_mut::<Events<WinitEvent>>()
-        .send_batch(buffered_events.drain(..));
+        .send_batch(buffered_events);
+}
+
+/// Sends `event`, followed by a [`Timestamped`] event if its `timestamp` is known and the app
+/// reads them.
+fn send_timestamped<E: bevy_ecs::event::Event + Clone>(
+    world: &mut World,
+    event: E,
+    timestamp: Option<Instant>,
+) {
+    world.send_event(event.clone());
+    if let (Some(timestamp), Some(mut timestamped)) = (
+        timestamp,
+        world.get_resource_mut::<Events<Timestamped<E>>>(),
+    ) {
+        timestamped.send(Timestamped { event, timestamp });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_input::{
+        keyboard::{Key, KeyCode},
+        ButtonState,
+    };
+
+    use super::*;
+
+    #[test]
+    fn filters_never_drop_user_events() {
+        let mut world = World::new();
+        let mut filters = WinitEventFilters::default();
+        filters.add("drop_everything", |_, _| false);
+        world.insert_resource(filters);
+
+        assert!(WinitEventFilters::accepts(
+            &world,
+            &Event::UserEvent(RequestRedraw)
+        ));
+        assert!(WinitEventFilters::accepts(&world, &Event::AboutToWait));
+    }
+
+    #[test]
+    fn injected_events_are_handled_in_order() {
+        let mut world = World::new();
+        world.init_non_send_resource::<WinitEventBuffer>();
+        world.init_resource::<Events<SequencedInput>>();
+        world.init_resource::<Events<WinitEvent>>();
+        world.init_resource::<Events<WindowFocused>>();
+        world.init_resource::<Events<RequestRedraw>>();
+
+        let window = world.spawn_empty().id();
+        let mut injector = WinitEventInjector::default();
+        injector.inject(Event::UserEvent(RequestRedraw));
+        injector.inject(Event::AboutToWait);
+        injector.inject_translated(WindowFocused {
+            window,
+            focused: true,
+        });
+        injector.inject_translated(RequestRedraw);
+        assert_eq!(injector.len(), 4);
+        world.insert_resource(injector);
+
+        let raw = WinitEventInjector::take(&mut world);
+        assert!(matches!(
+            raw.as_slice(),
+            [Event::UserEvent(RequestRedraw), Event::AboutToWait]
+        ));
+        assert!(world.resource::<WinitEventInjector>().is_empty());
+        assert!(WinitEventInjector::take(&mut world).is_empty());
+
+        forward_winit_events(&mut world);
+        let focused = world
+            .resource_mut::<Events<WindowFocused>>()
+            .drain()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            focused,
+            vec![WindowFocused {
+                window,
+                focused: true
+            }]
+        );
+        assert_eq!(world.resource::<Events<RequestRedraw>>().len(), 1);
+        assert_eq!(world.resource::<Events<WinitEvent>>().len(), 2);
+    }
+
+    #[test]
+    fn injected_input_is_sequenced() {
+        let mut world = World::new();
+        world.init_non_send_resource::<WinitEventBuffer>();
+        world.init_resource::<Events<SequencedInput>>();
+        world.init_resource::<Events<WinitEvent>>();
+        world.init_resource::<Events<KeyboardInput>>();
+
+        let window = world.spawn_empty().id();
+        let mut injector = WinitEventInjector::default();
+        for state in [ButtonState::Pressed, ButtonState::Released] {
+            injector.inject_translated(KeyboardInput {
+                key_code: KeyCode::Space,
+                logical_key: Key::Space,
+                state,
+                window,
+            });
+        }
+        world.insert_resource(injector);
+        WinitEventInjector::take(&mut world);
+        forward_winit_events(&mut world);
+
+        let sequences = world
+            .resource_mut::<Events<SequencedInput>>()
+            .drain()
+            .map(|input| input.sequence)
+            .collect::<Vec<_>>();
+        assert_eq!(sequences, vec![0, 1]);
+        assert_eq!(world.resource::<Events<KeyboardInput>>().len(), 2);
+    }
+
+    #[test]
+    fn translated_input_is_timestamped() {
+        let mut world = World::new();
+        world.init_non_send_resource::<WinitEventBuffer>();
+        world.init_resource::<Events<SequencedInput>>();
+        world.init_resource::<Events<WinitEvent>>();
+        world.init_resource::<Events<KeyboardInput>>();
+        world.init_resource::<Events<Timestamped<KeyboardInput>>>();
+
+        let window = world.spawn_empty().id();
+        let key = |state| KeyboardInput {
+            key_code: KeyCode::Space,
+            logical_key: Key::Space,
+            state,
+            window,
+        };
+        let received = Instant::now();
+        let mut buffer = world.non_send_resource_mut::<WinitEventBuffer>();
+        buffer.translated.push(key(ButtonState::Pressed).into());
+        // Injected events have no timestamp.
+        buffer.translated.push(key(ButtonState::Released).into());
+        buffer.translated_received.push((0, received));
+        forward_winit_events(&mut world);
+
+        assert_eq!(world.resource::<Events<KeyboardInput>>().len(), 2);
+        let timestamped = world
+            .resource_mut::<Events<Timestamped<KeyboardInput>>>()
+            .drain()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            timestamped,
+            vec![Timestamped {
+                event: key(ButtonState::Pressed),
+                timestamp: received,
+            }]
+        );
+    }
 }
@@ -1,22 +1,90 @@
 use bevy_a11y::AccessibilityRequested;
-use bevy_ecs::entity::Entity;
+use bevy_ecs::{entity::Entity, system::EntityCommands};
 
 use bevy_ecs::entity::EntityHashMap;
+use bevy_math::Vec2;
 use bevy_utils::{tracing::warn, HashMap};
 use bevy_window::{
-    CursorGrabMode, Window, WindowMode, WindowPosition, WindowResolution, WindowWrapper,
+    CursorGrabMode, CursorIcon, EnabledButtons, MonitorSelection, RawHandleWrapper, VideoModeInfo,
+    Window, WindowLevel, WindowMode, WindowPosition, WindowResizeConstraints, WindowResolution,
+    WindowTheme, WindowWrapper,
 };
 
 use winit::{
-    dpi::{LogicalSize, PhysicalPosition},
+    dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize},
+    error::ExternalError,
+    event_loop::EventLoopWindowTarget,
     monitor::MonitorHandle,
+    window::WindowBuilder,
 };
 
+#[cfg(target_arch = "wasm32")]
+use winit::platform::web::WindowExtWebSys;
+
 use crate::{
-    accessibility::{prepare_accessibility_for_window, AccessKitAdapters, WinitActionHandlers},
-    converters::{convert_enabled_buttons, convert_window_level, convert_window_theme},
+    accessibility::{
+        prepare_accessibility_for_window, AccessKitAdapters, AccessKitEnabled, WinitActionHandlers,
+    },
+    backend::{BackendWindow, WindowBackend},
+    converters::{
+        convert_cursor_icon, convert_enabled_buttons, convert_monitor, convert_video_mode,
+        convert_window_level, convert_window_theme, convert_winit_theme,
+    },
+    WinitWindowHandle,
 };
 
+/// A callback customizing the [`WindowBuilder`] of a window, see [`WindowBuilderHook`].
+pub type WindowBuilderHookFn = Box<dyn Fn(WindowBuilder, &Window) -> WindowBuilder>;
+
+/// Non-send resource holding callbacks that customize the `winit` [`WindowBuilder`] of each
+/// window before it is built.
+///
+/// This is an escape hatch to apply the platform extensions Bevy doesn't expose, e.g. the parent
+/// window on Windows or the tabbing identifier on macOS. The hooks run in the order they were
+/// added, after every property of the [`Window`] has been applied to the builder. The builder
+/// starts invisible, the window being shown once it is fully set up if [`Window::visible`] is set.
+///
+/// Plugins added before the [`WinitPlugin`](crate::WinitPlugin) can call
+/// `world.init_non_send_resource::<WindowBuilderHook>()` themselves to register their hooks.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_winit::WindowBuilderHook;
+/// // Keep the windows out of screen captures.
+/// fn protect_windows(mut hook: NonSendMut<WindowBuilderHook>) {
+///     hook.add(|builder, _window| builder.with_content_protected(true));
+/// }
+/// ```
+///
+/// Use `NonSendMut<WindowBuilderHook>` to access this resource.
+#[derive(Default)]
+pub struct WindowBuilderHook {
+    hooks: Vec<WindowBuilderHookFn>,
+}
+
+impl WindowBuilderHook {
+    /// Registers a callback run on the builder of each window created from now on.
+    pub fn add(
+        &mut self,
+        hook: impl Fn(WindowBuilder, &Window) -> WindowBuilder + 'static,
+    ) -> &mut Self {
+        self.hooks.push(Box::new(hook));
+        self
+    }
+
+    /// Removes every callback.
+    pub fn clear(&mut self) {
+        self.hooks.clear();
+    }
+
+    /// Runs the callbacks on `builder`, in the order they were added.
+    fn apply(&self, builder: WindowBuilder, window: &Window) -> WindowBuilder {
+        self.hooks
+            .iter()
+            .fold(builder, |builder, hook| hook(builder, window))
+    }
+}
+
 /// A resource mapping window entities to their `winit`-backend [`Window`](winit::window::Window)
 /// states.
 #[derive(Debug, Default)]
@@ -27,6 +95,8 @@ pub struct WinitWindows {
     pub entity_to_winit: EntityHashMap<winit::window::WindowId>,
     /// Maps `winit` window identifiers to entities.
     pub winit_to_entity: HashMap<winit::window::WindowId, Entity>,
+    /// The number of failed attempts to grab the cursor of the windows whose grab is retried.
+    pub(crate) cursor_grab_retries: EntityHashMap<u32>,
     // Many `winit` window functions (e.g. `set_window_icon`) can only be called on the main thread.
     // If they're called on other threads, the program might hang. This marker indicates that this
     // type is not thread-safe and will be `!Send` and `!Sync`.
@@ -35,6 +105,7 @@ pub struct WinitWindows {
 
 impl WinitWindows {
     /// Creates a `winit` window and associates it with our entity.
+    #[allow(clippy::too_many_arguments)]
     pub fn create_window(
         &mut self,
         event_loop: &winit::event_loop::EventLoopWindowTarget<crate::UserEvent>,
@@ -43,11 +114,15 @@ impl WinitWindows {
         adapters: &mut AccessKitAdapters,
         handlers: &mut WinitActionHandlers,
         accessibility_requested: &AccessibilityRequested,
+        accesskit_enabled: &AccessKitEnabled,
+        builder_hook: &WindowBuilderHook,
     ) -> &WindowWrapper<winit::window::Window> {
         let mut winit_window_builder = winit::window::WindowBuilder::new();
 
         // Due to a UIA limitation, winit windows need to be invisible for the
-        // AccessKit adapter is initialized.
+        // AccessKit adapter is initialized. This also lets the requested theme be applied to the
+        // titlebar before the window is first drawn, so dark windows don't flash a light titlebar
+        // on Windows. The window is shown once it's fully set up.
         winit_window_builder = winit_window_builder.with_visible(false);
 
         winit_window_builder = match window.mode {
@@ -73,6 +148,25 @@ impl WinitWindows {
                     winit_window_builder
                 }
             }
+            WindowMode::ExclusiveFullscreen {
+                monitor,
+                video_mode,
+            } => {
+                match get_selected_videomode(
+                    monitor,
+                    window,
+                    &video_mode,
+                    event_loop.available_monitors(),
+                    event_loop.primary_monitor(),
+                ) {
+                    Some(videomode) => winit_window_builder
+                        .with_fullscreen(Some(winit::window::Fullscreen::Exclusive(videomode))),
+                    None => {
+                        warn!("Could not find the monitor {monitor:?} or its video modes, ignoring exclusive fullscreen request for window {:?}", window.title);
+                        winit_window_builder
+                    }
+                }
+            }
             WindowMode::Windowed => {
                 if let Some(position) = winit_window_position(
                     &window.position,
@@ -99,8 +193,7 @@ impl WinitWindows {
             .with_resizable(window.resizable)
             .with_enabled_buttons(convert_enabled_buttons(window.enabled_buttons))
             .with_decorations(window.decorations)
-            .with_transparent(window.transparent)
-            .with_visible(window.visible);
+            .with_transparent(window.transparent);
 
         #[cfg(target_os = "windows")]
         {
@@ -108,6 +201,24 @@ impl WinitWindows {
             winit_window_builder = winit_window_builder.with_skip_taskbar(window.skip_taskbar);
         }
 
+        #[cfg(all(
+            feature = "x11",
+            any(
+                target_os = "linux",
+                target_os = "dragonfly",
+                target_os = "freebsd",
+                target_os = "netbsd",
+                target_os = "openbsd"
+            )
+        ))]
+        if window.override_redirect {
+            winit_window_builder =
+                winit::platform::x11::WindowBuilderExtX11::with_override_redirect(
+                    winit_window_builder,
+                    true,
+                );
+        }
+
         #[cfg(any(
             target_os = "linux",
             target_os = "dragonfly",
@@ -208,20 +319,29 @@ impl WinitWindows {
             winit_window_builder = winit_window_builder.with_append(true);
         }
 
+        let winit_window_builder = builder_hook.apply(winit_window_builder, window);
         let winit_window = winit_window_builder.build(event_loop).unwrap();
-        let name = window.title.clone();
-        prepare_accessibility_for_window(
-            &winit_window,
-            entity,
-            name,
-            accessibility_requested.clone(),
-            adapters,
-            handlers,
-        );
+        if accesskit_enabled.get() {
+            let name = window.title.clone();
+            prepare_accessibility_for_window(
+                &winit_window,
+                entity,
+                name,
+                accessibility_requested.clone(),
+                adapters,
+                handlers,
+            );
+        }
+
+        if window.visible {
+            winit_window.set_visible(true);
+        }
 
         // Do not set the grab mode on window creation if it's none. It can fail on mobile.
-        if window.cursor.grab_mode != CursorGrabMode::None {
-            attempt_grab(&winit_window, window.cursor.grab_mode);
+        if window.cursor.grab_mode != CursorGrabMode::None
+            && attempt_grab(&winit_window, window.cursor.grab_mode).is_err()
+        {
+            self.cursor_grab_retries.insert(entity, 0);
         }
 
         winit_window.set_cursor_visible(window.cursor.visible);
@@ -237,6 +357,8 @@ impl WinitWindows {
             }
         }
 
+        // Forget the previous window of the entity, if any, so the maps stay consistent.
+        self.remove_window(entity);
         self.entity_to_winit.insert(entity, winit_window.id());
         self.winit_to_entity.insert(winit_window.id(), entity);
 
@@ -253,6 +375,47 @@ impl WinitWindows {
             .and_then(|winit_id| self.windows.get(winit_id))
     }
 
+    /// Get the [`winit`] window id associated with our entity.
+    pub fn get_window_id(&self, entity: Entity) -> Option<winit::window::WindowId> {
+        self.entity_to_winit.get(&entity).copied()
+    }
+
+    /// Get the winit windows associated with `entities`, skipping the entities without one.
+    pub fn get_windows<'a>(
+        &'a self,
+        entities: impl IntoIterator<Item = Entity> + 'a,
+    ) -> impl Iterator<Item = (Entity, &'a WindowWrapper<winit::window::Window>)> + 'a {
+        entities
+            .into_iter()
+            .filter_map(|entity| Some((entity, self.get_window(entity)?)))
+    }
+
+    /// Iterates over the window entities and their [`winit`] window ids, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, winit::window::WindowId)> + '_ {
+        self.entity_to_winit
+            .iter()
+            .map(|(&entity, &winit_id)| (entity, winit_id))
+    }
+
+    /// Iterates over the window entities and their winit windows, in no particular order.
+    pub fn iter_windows(
+        &self,
+    ) -> impl Iterator<Item = (Entity, &WindowWrapper<winit::window::Window>)> + '_ {
+        self.entity_to_winit
+            .iter()
+            .filter_map(|(&entity, winit_id)| Some((entity, self.windows.get(winit_id)?)))
+    }
+
+    /// Returns the number of windows.
+    pub fn len(&self) -> usize {
+        self.entity_to_winit.len()
+    }
+
+    /// Returns `true` if there is no window.
+    pub fn is_empty(&self) -> bool {
+        self.entity_to_winit.is_empty()
+    }
+
     /// Get the entity associated with the winit window id.
     ///
     /// This is mostly just an intermediary step between us and winit.
@@ -267,12 +430,346 @@ impl WinitWindows {
         &mut self,
         entity: Entity,
     ) -> Option<WindowWrapper<winit::window::Window>> {
+        self.cursor_grab_retries.remove(&entity);
         let winit_id = self.entity_to_winit.remove(&entity)?;
         self.winit_to_entity.remove(&winit_id);
         self.windows.remove(&winit_id)
     }
 }
 
+/// What the [`WinitWindows`] need to create windows, see [`WinitWindows::create_window`].
+pub(crate) struct WinitCreateContext<'a> {
+    pub(crate) event_loop: &'a EventLoopWindowTarget<crate::UserEvent>,
+    pub(crate) adapters: &'a mut AccessKitAdapters,
+    pub(crate) handlers: &'a mut WinitActionHandlers,
+    pub(crate) accessibility_requested: &'a AccessibilityRequested,
+    pub(crate) accesskit_enabled: &'a AccessKitEnabled,
+    pub(crate) builder_hook: &'a WindowBuilderHook,
+}
+
+impl WindowBackend for WinitWindows {
+    type Window = WindowWrapper<winit::window::Window>;
+    type CreateContext<'a> = WinitCreateContext<'a>;
+
+    fn create_window(
+        &mut self,
+        context: &mut WinitCreateContext<'_>,
+        entity: Entity,
+        window: &Window,
+        commands: &mut EntityCommands,
+    ) -> &mut Self::Window {
+        let winit_window = WinitWindows::create_window(
+            self,
+            context.event_loop,
+            entity,
+            window,
+            context.adapters,
+            context.handlers,
+            context.accessibility_requested,
+            context.accesskit_enabled,
+            context.builder_hook,
+        );
+
+        if let Ok(handle_wrapper) = RawHandleWrapper::new(winit_window) {
+            commands.insert(handle_wrapper);
+        }
+        commands.insert(WinitWindowHandle::new(winit_window));
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            if window.fit_canvas_to_parent {
+                let canvas = winit_window
+                    .canvas()
+                    .expect("window.canvas() can only be called in main thread.");
+                let style = canvas.style();
+                style.set_property("width", "100%").unwrap();
+                style.set_property("height", "100%").unwrap();
+            }
+        }
+
+        let winit_id = winit_window.id();
+        self.windows.get_mut(&winit_id).unwrap()
+    }
+
+    fn window(&self, entity: Entity) -> Option<&Self::Window> {
+        self.get_window(entity)
+    }
+
+    fn window_mut(&mut self, entity: Entity) -> Option<&mut Self::Window> {
+        self.entity_to_winit
+            .get(&entity)
+            .and_then(|winit_id| self.windows.get_mut(winit_id))
+    }
+
+    fn destroy_window(&mut self, entity: Entity) -> bool {
+        self.remove_window(entity).is_some()
+    }
+
+    fn cursor_grab_applied(&mut self, entity: Entity, granted: bool) {
+        if granted {
+            self.cursor_grab_retries.remove(&entity);
+        } else {
+            self.cursor_grab_retries.insert(entity, 0);
+        }
+    }
+}
+
+// The methods of `winit` sharing their name with the ones of `BackendWindow` are called on
+// `**self`, as calling them on `self` would call the `BackendWindow` methods again.
+impl BackendWindow for WindowWrapper<winit::window::Window> {
+    fn scale_factor(&self) -> f32 {
+        (**self).scale_factor() as f32
+    }
+
+    fn theme(&self) -> Option<WindowTheme> {
+        (**self).theme().map(convert_winit_theme)
+    }
+
+    fn has_focus(&self) -> bool {
+        (**self).has_focus()
+    }
+
+    fn focus(&mut self) {
+        self.focus_window();
+    }
+
+    fn request_inner_size(&mut self, size: PhysicalSize<u32>) -> Option<PhysicalSize<u32>> {
+        (**self).request_inner_size(size)
+    }
+
+    fn set_title(&mut self, title: &str) {
+        (**self).set_title(title);
+    }
+
+    fn set_mode(&mut self, window: &Window) -> Result<(), String> {
+        let new_mode = match window.mode {
+            WindowMode::BorderlessFullscreen => Some(winit::window::Fullscreen::Borderless(None)),
+            mode @ (WindowMode::Fullscreen | WindowMode::SizedFullscreen) => {
+                let current_monitor = self
+                    .current_monitor()
+                    .ok_or("could not determine the current monitor")?;
+                let videomode = match mode {
+                    WindowMode::Fullscreen => get_best_videomode(&current_monitor),
+                    WindowMode::SizedFullscreen => get_fitting_videomode(
+                        &current_monitor,
+                        window.width() as u32,
+                        window.height() as u32,
+                    ),
+                    _ => unreachable!(),
+                };
+                Some(winit::window::Fullscreen::Exclusive(videomode))
+            }
+            WindowMode::ExclusiveFullscreen {
+                monitor,
+                video_mode,
+            } => {
+                let videomode = get_selected_videomode(
+                    monitor,
+                    window,
+                    &video_mode,
+                    self.available_monitors(),
+                    self.primary_monitor(),
+                )
+                .ok_or_else(|| {
+                    format!("could not find the monitor {monitor:?} or its video modes")
+                })?;
+                Some(winit::window::Fullscreen::Exclusive(videomode))
+            }
+            WindowMode::Windowed => None,
+        };
+
+        if self.fullscreen() != new_mode {
+            self.set_fullscreen(new_mode);
+        }
+        Ok(())
+    }
+
+    fn set_position(&mut self, window: &Window) {
+        if let Some(position) = winit_window_position(
+            &window.position,
+            &window.resolution,
+            self.available_monitors(),
+            self.primary_monitor(),
+            self.current_monitor(),
+        ) {
+            let should_set = match self.outer_position() {
+                Ok(current_position) => current_position != position,
+                _ => true,
+            };
+
+            if should_set {
+                self.set_outer_position(position);
+            }
+        }
+    }
+
+    fn set_cursor_position(&mut self, position: Vec2) -> Result<(), String> {
+        (**self)
+            .set_cursor_position(PhysicalPosition::new(position.x, position.y))
+            .map_err(|err| err.to_string())
+    }
+
+    fn set_cursor_icon(&mut self, icon: CursorIcon) {
+        (**self).set_cursor_icon(convert_cursor_icon(icon));
+    }
+
+    fn set_cursor_grab(&mut self, grab_mode: CursorGrabMode) -> bool {
+        attempt_grab(self, grab_mode).is_ok()
+    }
+
+    fn set_cursor_visible(&mut self, visible: bool) {
+        (**self).set_cursor_visible(visible);
+    }
+
+    fn set_cursor_hit_test(&mut self, hit_test: bool) -> Result<(), String> {
+        (**self)
+            .set_cursor_hittest(hit_test)
+            .map_err(|err| err.to_string())
+    }
+
+    fn set_decorations(&mut self, decorations: bool) {
+        if decorations != self.is_decorated() {
+            (**self).set_decorations(decorations);
+        }
+    }
+
+    fn set_resizable(&mut self, resizable: bool) {
+        if resizable != self.is_resizable() {
+            (**self).set_resizable(resizable);
+        }
+    }
+
+    fn set_enabled_buttons(&mut self, enabled_buttons: EnabledButtons) {
+        (**self).set_enabled_buttons(convert_enabled_buttons(enabled_buttons));
+    }
+
+    fn set_resize_constraints(&mut self, constraints: WindowResizeConstraints) {
+        let constraints = constraints.check_constraints();
+        let min_inner_size = LogicalSize {
+            width: constraints.min_width,
+            height: constraints.min_height,
+        };
+        let max_inner_size = LogicalSize {
+            width: constraints.max_width,
+            height: constraints.max_height,
+        };
+
+        self.set_min_inner_size(Some(min_inner_size));
+        if constraints.max_width.is_finite() && constraints.max_height.is_finite() {
+            self.set_max_inner_size(Some(max_inner_size));
+        } else {
+            self.set_max_inner_size(None::<LogicalSize<f32>>);
+        }
+    }
+
+    fn set_maximized(&mut self, maximized: bool) {
+        (**self).set_maximized(maximized);
+    }
+
+    fn set_minimized(&mut self, minimized: bool) {
+        (**self).set_minimized(minimized);
+    }
+
+    fn set_window_level(&mut self, window_level: WindowLevel) {
+        (**self).set_window_level(convert_window_level(window_level));
+    }
+
+    fn set_ime_allowed(&mut self, allowed: bool) {
+        (**self).set_ime_allowed(allowed);
+    }
+
+    fn set_ime_position(&mut self, position: Vec2) {
+        self.set_ime_cursor_area(
+            LogicalPosition::new(position.x, position.y),
+            PhysicalSize::new(10, 10),
+        );
+    }
+
+    fn set_theme(&mut self, theme: Option<WindowTheme>) {
+        (**self).set_theme(theme.map(convert_window_theme));
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        (**self).set_visible(visible);
+    }
+
+    fn set_opacity(&mut self, opacity: f32) {
+        let opacity = opacity.clamp(0.0, 1.0);
+
+        #[cfg(target_arch = "wasm32")]
+        if let Some(canvas) = self.canvas() {
+            if let Err(err) = canvas.style().set_property("opacity", &opacity.to_string()) {
+                warn!("Could not set the canvas opacity: {:?}", err);
+            }
+        }
+
+        #[cfg(all(target_os = "linux", feature = "x11"))]
+        if set_x11_opacity(self, opacity) {
+            return;
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        bevy_utils::warn_once!("`Window::opacity` is not supported on this platform");
+    }
+}
+
+/// Sets the `_NET_WM_WINDOW_OPACITY` property of an X11 window, which compositing window managers
+/// apply to the whole window. Returns `false` if `window` isn't an X11 window.
+#[cfg(all(target_os = "linux", feature = "x11"))]
+fn set_x11_opacity(window: &winit::window::Window, opacity: f32) -> bool {
+    use std::sync::OnceLock;
+
+    use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+    use x11rb::{
+        connection::Connection,
+        errors::ReplyError,
+        protocol::xproto::{AtomEnum, ConnectionExt, PropMode},
+        rust_connection::RustConnection,
+        wrapper::ConnectionExt as _,
+    };
+
+    // The window manager reads the property, so any connection to the X server can set it.
+    static CONNECTION: OnceLock<Option<RustConnection>> = OnceLock::new();
+
+    let x11_window = match window.window_handle().map(|handle| handle.as_raw()) {
+        Ok(RawWindowHandle::Xlib(handle)) => handle.window as u32,
+        Ok(RawWindowHandle::Xcb(handle)) => handle.window.get(),
+        _ => return false,
+    };
+    let Some(connection) = CONNECTION
+        .get_or_init(|| x11rb::connect(None).ok().map(|(connection, _)| connection))
+        .as_ref()
+    else {
+        return false;
+    };
+
+    let result = (|| -> Result<(), ReplyError> {
+        let atom = connection
+            .intern_atom(false, b"_NET_WM_WINDOW_OPACITY")?
+            .reply()?
+            .atom;
+        if opacity < 1.0 {
+            let opacity = (f64::from(opacity) * f64::from(u32::MAX)) as u32;
+            connection.change_property32(
+                PropMode::REPLACE,
+                x11_window,
+                atom,
+                AtomEnum::CARDINAL,
+                &[opacity],
+            )?;
+        } else {
+            // Opaque windows don't need the compositor to blend them.
+            connection.delete_property(x11_window, atom)?;
+        }
+        connection.flush()?;
+        Ok(())
+    })();
+    if let Err(err) = result {
+        warn!("Could not set the opacity of the X11 window: {err}");
+    }
+    true
+}
+
 /// Gets the "best" video mode which fits the given dimensions.
 ///
 /// The heuristic for "best" prioritizes width, height, and refresh rate in that order.
@@ -329,8 +826,12 @@ pub fn get_best_videomode(monitor: &MonitorHandle) -> winit::monitor::VideoMode
     modes.first().unwrap().clone()
 }
 
-pub(crate) fn attempt_grab(winit_window: &winit::window::Window, grab_mode: CursorGrabMode) {
-    let grab_result = match grab_mode {
+/// Applies `grab_mode`, falling back to the other grab mode if the platform doesn't support it.
+pub(crate) fn attempt_grab(
+    winit_window: &winit::window::Window,
+    grab_mode: CursorGrabMode,
+) -> Result<(), ExternalError> {
+    match grab_mode {
         CursorGrabMode::None => winit_window.set_cursor_grab(winit::window::CursorGrabMode::None),
         CursorGrabMode::Confined => winit_window
             .set_cursor_grab(winit::window::CursorGrabMode::Confined)
@@ -338,16 +839,59 @@ pub(crate) fn attempt_grab(winit_window: &winit::window::Window, grab_mode: Curs
         CursorGrabMode::Locked => winit_window
             .set_cursor_grab(winit::window::CursorGrabMode::Locked)
             .or_else(|_e| winit_window.set_cursor_grab(winit::window::CursorGrabMode::Confined)),
-    };
+    }
+}
 
-    if let Err(err) = grab_result {
-        let err_desc = match grab_mode {
-            CursorGrabMode::Confined | CursorGrabMode::Locked => "grab",
-            CursorGrabMode::None => "ungrab",
-        };
+/// Gets the video mode closest to `video_mode` from the monitor selected by `monitor_selection`
+/// for `window`, see [`MonitorSelection::select`].
+///
+/// Returns `None` if the monitor can't be found or has no video modes.
+pub fn get_selected_videomode(
+    monitor_selection: MonitorSelection,
+    window: &Window,
+    video_mode: &VideoModeInfo,
+    available_monitors: impl Iterator<Item = MonitorHandle>,
+    primary_monitor: Option<MonitorHandle>,
+) -> Option<winit::monitor::VideoMode> {
+    let monitors = available_monitors
+        .map(|monitor| {
+            (
+                convert_monitor(&monitor),
+                primary_monitor.as_ref() == Some(&monitor),
+                monitor,
+            )
+        })
+        .collect::<Vec<_>>();
+    let index = monitor_selection.select(
+        window,
+        monitors.iter().map(|(info, primary, _)| (info, *primary)),
+    )?;
+    closest_video_mode(
+        monitors[index].2.video_modes(),
+        video_mode,
+        convert_video_mode,
+    )
+}
 
-        bevy_utils::tracing::error!("Unable to {} cursor: {}", err_desc, err);
-    }
+/// Returns the video mode closest to `video_mode`, `info` describing each of the `video_modes`.
+///
+/// The heuristic for "closest" prioritizes the resolution, refresh rate and bit depth in that
+/// order.
+fn closest_video_mode<M>(
+    video_modes: impl IntoIterator<Item = M>,
+    video_mode: &VideoModeInfo,
+    info: impl Fn(&M) -> VideoModeInfo,
+) -> Option<M> {
+    let target = video_mode.physical_size;
+    video_modes.into_iter().min_by_key(|mode| {
+        let mode = info(mode);
+        (
+            mode.physical_size.x.abs_diff(target.x) + mode.physical_size.y.abs_diff(target.y),
+            mode.refresh_rate_millihertz
+                .abs_diff(video_mode.refresh_rate_millihertz),
+            mode.bit_depth.abs_diff(video_mode.bit_depth),
+        )
+    })
 }
 
 /// Compute the physical window position for a given [`WindowPosition`].
@@ -409,3 +953,56 @@ pub fn winit_window_position(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::UVec2;
+
+    use super::*;
+
+    fn video_mode(width: u32, height: u32, refresh_rate: u32, bit_depth: u16) -> VideoModeInfo {
+        VideoModeInfo {
+            physical_size: UVec2::new(width, height),
+            bit_depth,
+            refresh_rate_millihertz: refresh_rate * 1000,
+        }
+    }
+
+    #[test]
+    fn closest_video_mode_prefers_the_resolution_then_the_refresh_rate() {
+        let video_modes = [
+            video_mode(1280, 720, 144, 32),
+            video_mode(1920, 1080, 60, 32),
+            video_mode(1920, 1080, 120, 24),
+            video_mode(1920, 1080, 120, 32),
+            video_mode(2560, 1440, 144, 32),
+        ];
+        let closest = |target| closest_video_mode(video_modes, &target, |mode| *mode);
+
+        assert_eq!(
+            closest(video_mode(1920, 1080, 120, 32)),
+            Some(video_mode(1920, 1080, 120, 32))
+        );
+        // The resolution wins over the refresh rate.
+        assert_eq!(
+            closest(video_mode(1920, 1080, 144, 32)),
+            Some(video_mode(1920, 1080, 120, 32))
+        );
+        assert_eq!(
+            closest(video_mode(2000, 1100, 60, 16)),
+            Some(video_mode(1920, 1080, 60, 32))
+        );
+        assert_eq!(
+            closest(video_mode(1920, 1080, 100, 24)),
+            Some(video_mode(1920, 1080, 120, 24))
+        );
+        assert_eq!(
+            closest_video_mode(
+                [],
+                &video_mode(1920, 1080, 60, 32),
+                |mode: &VideoModeInfo| *mode
+            ),
+            None
+        );
+    }
+}